@@ -9,10 +9,10 @@
 //!     cargo run --example oauth_refresh
 //!
 //! Acquire the refresh token via `auth::exchange_code(..., offline=true)`
-//! during the standard OAuth code-grant flow.
+//! during the standard OAuth code-grant flow. Apps authorized through PKCE
+//! have no secret and use `RefreshConfig::pkce(client_id, refresh_token)`.
 
 use rusty_dropbox_sdk::api;
-use rusty_dropbox_sdk::api::Service;
 use rusty_dropbox_sdk::Client;
 use rusty_dropbox_sdk::RefreshConfig;
 
@@ -37,36 +37,29 @@ async fn main() -> anyhow::Result<()> {
     let client = Client::with_refresh(
         access,
         14_400, // expires_in (seconds), from the OAuth response
-        RefreshConfig {
-            client_id,
-            client_secret,
-            refresh_token,
-        },
+        RefreshConfig::new(client_id, client_secret, refresh_token),
     );
 
-    // `client.call(...)` runs the closure with the current token; if the
+    // `client.execute(&req)` runs the request with the current token; if the
     // token is expired it refreshes first, and if the server returns 401 it
-    // mints a new token and replays the closure once.
+    // mints a new token and replays the request once.
+    let req = api::files::list_folder::ListFolderRequest {
+        payload: Some(api::files::ListFolderArgs {
+            path: String::new(),
+            recursive: Some(false),
+            include_media_info: None,
+            include_deleted: None,
+            include_has_explicit_shared_members: None,
+            include_mounted_folders: None,
+            limit: Some(10),
+            shared_link: None,
+            include_property_groups: None,
+            include_non_downloadable_files: None,
+        }),
+        ..Default::default()
+    };
     let response = client
-        .call(|token| async move {
-            api::files::list_folder::ListFolderRequest {
-                access_token: &token,
-                payload: Some(api::files::ListFolderArgs {
-                    path: String::new(),
-                    recursive: Some(false),
-                    include_media_info: None,
-                    include_deleted: None,
-                    include_has_explicit_shared_members: None,
-                    include_mounted_folders: None,
-                    limit: Some(10),
-                    shared_link: None,
-                    include_property_groups: None,
-                    include_non_downloadable_files: None,
-                }),
-            }
-            .call()
-            .await
-        })
+        .execute(&req)
         .await?
         .expect("empty list_folder response");
 
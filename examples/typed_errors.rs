@@ -1,14 +1,14 @@
 //! Recover typed Dropbox errors instead of inspecting error strings.
 //!
 //! Calls `get_metadata` on a path that doesn't exist, then downcasts the
-//! returned anyhow::Error to `TypedError<LookupError>` so we can match on
-//! `LookupError::NotFound`.
+//! returned anyhow::Error to `TypedError<GetMetadataError>` so we can match
+//! on `path/not_found`.
 //!
 //! Run with:
 //!     DROPBOX_TOKEN=<your-token> cargo run --example typed_errors
 
 use rusty_dropbox_sdk::api;
-use rusty_dropbox_sdk::api::files::LookupError;
+use rusty_dropbox_sdk::api::files::{GetMetadataError, LookupError};
 use rusty_dropbox_sdk::api::Service;
 use rusty_dropbox_sdk::TypedError;
 
@@ -36,10 +36,12 @@ async fn main() -> anyhow::Result<()> {
     match req.call().await {
         Ok(_) => println!("metadata fetched (the path actually existed!)"),
         Err(e) => {
-            if let Some(holder) = e.downcast_ref::<TypedError<LookupError>>() {
+            if let Some(holder) = e.downcast_ref::<TypedError<GetMetadataError>>() {
                 match holder.get() {
-                    LookupError::NotFound => println!("expected: path not found"),
-                    other => println!("other lookup error: {:?}", other),
+                    GetMetadataError::Path {
+                        path: LookupError::NotFound,
+                    } => println!("expected: path not found"),
+                    other => println!("other get_metadata error: {:?}", other),
                 }
             } else {
                 eprintln!("non-typed error: {e}");
@@ -1,4 +1,5 @@
 pub use crate::models::{
-    account, auth, check, contacts, file_properties, file_requests, files, openid, sharing, users,
+    account, auth, check, common, contacts, file_properties, file_requests, files, openid, sharing,
+    team, team_log, users,
 };
 pub use crate::traits::Service;
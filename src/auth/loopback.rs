@@ -0,0 +1,357 @@
+//! Finish the OAuth flow from a CLI without copy-pasting the code.
+//!
+//! [`LoopbackRedirect`] listens on an ephemeral `127.0.0.1` port and
+//! catches Dropbox's redirect; [`authorize_loopback`] wraps the whole round
+//! trip: build the authorize URL, hand it to the caller to open, wait for
+//! the callback, check `state`, answer the browser and exchange the code.
+//!
+//! Register `http://127.0.0.1` as a redirect URI for the app in the Dropbox
+//! App Console. There is no built-in deadline; wrap the future in
+//! `tokio::time::timeout` if the user may never come back.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use super::{
+    authorize_url, authorize_url_pkce, base64url, exchange_code_pkce_with, exchange_code_with,
+    Pkce, Tokens,
+};
+use crate::Client;
+
+/// Largest request head accepted from the browser.
+const MAX_REQUEST: usize = 8 * 1024;
+
+/// How long a connection gets to send its request head. Browsers open
+/// speculative connections that may never send one; dropping them keeps
+/// the real callback from queueing behind them.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+const DONE_PAGE: &str = "<!doctype html><title>Dropbox</title>\
+<p>Authorization complete. You can close this tab and return to the app.</p>";
+
+const FAILED_PAGE: &str = "<!doctype html><title>Dropbox</title>\
+<p>Authorization failed. Return to the app for details.</p>";
+
+/// Run the authorization-code flow through a loopback redirect and return
+/// the exchanged tokens.
+///
+/// `open` gets the authorize URL; launch a browser with it or print it.
+/// With a `client_secret` the classic flow is used, without one PKCE.
+/// Pass `offline = true` to get a refresh token.
+///
+/// ```ignore
+/// let tokens = auth::authorize_loopback(&Client::new(""), "app-key", None, true, |url| {
+///     println!("Open {url} to continue");
+/// })
+/// .await?;
+/// ```
+pub async fn authorize_loopback(
+    client: &Client,
+    client_id: &str,
+    client_secret: Option<&str>,
+    offline: bool,
+    open: impl FnOnce(&str),
+) -> Result<Tokens> {
+    let redirect = LoopbackRedirect::bind().await?;
+    let redirect_uri = redirect.redirect_uri().to_string();
+    match client_secret {
+        Some(secret) => {
+            open(&authorize_url(
+                client_id,
+                &redirect_uri,
+                Some(redirect.state()),
+                offline,
+            ));
+            let code = redirect.wait_for_code().await?;
+            exchange_code_with(client, client_id, secret, &code, &redirect_uri).await
+        }
+        None => {
+            let pkce = Pkce::try_new()?;
+            open(&authorize_url_pkce(
+                client_id,
+                Some(&redirect_uri),
+                Some(redirect.state()),
+                offline,
+                &pkce,
+            ));
+            let code = redirect.wait_for_code().await?;
+            exchange_code_pkce_with(
+                client,
+                client_id,
+                &code,
+                pkce.verifier(),
+                Some(&redirect_uri),
+            )
+            .await
+        }
+    }
+}
+
+/// A one-shot HTTP listener on `127.0.0.1` for the OAuth redirect, with a
+/// random `state` to put in the authorize URL.
+#[derive(Debug)]
+pub struct LoopbackRedirect {
+    listener: TcpListener,
+    redirect_uri: String,
+    state: String,
+}
+
+impl LoopbackRedirect {
+    /// Bind an ephemeral port.
+    pub async fn bind() -> Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .context("bind loopback redirect listener")?;
+        let port = listener.local_addr()?.port();
+        let mut nonce = [0u8; 16];
+        getrandom::getrandom(&mut nonce).context("generate OAuth state")?;
+        Ok(Self {
+            listener,
+            redirect_uri: format!("http://127.0.0.1:{port}/"),
+            state: base64url(&nonce),
+        })
+    }
+
+    /// `http://127.0.0.1:<port>/`, to pass as `redirect_uri`.
+    pub fn redirect_uri(&self) -> &str {
+        &self.redirect_uri
+    }
+
+    /// The `state` value the callback must carry back.
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    /// Wait for Dropbox to redirect the browser back and return the
+    /// authorization code. Requests for other paths (a favicon, say) get a
+    /// 404 and are otherwise ignored, and connections that send nothing are
+    /// dropped after a couple of seconds. A callback with the wrong `state` or
+    /// an `error` parameter ends the wait with an error.
+    pub async fn wait_for_code(self) -> Result<String> {
+        loop {
+            let (mut stream, _) = self
+                .listener
+                .accept()
+                .await
+                .context("accept OAuth redirect")?;
+            let target =
+                match tokio::time::timeout(REQUEST_TIMEOUT, read_request_target(&mut stream)).await
+                {
+                    Ok(Ok(target)) => target,
+                    Ok(Err(_)) | Err(_) => continue,
+                };
+            let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+            if path != "/" {
+                let _ = respond(&mut stream, "404 Not Found", "").await;
+                continue;
+            }
+            let outcome = self.check_callback(query);
+            let (status, page) = match outcome {
+                Ok(_) => ("200 OK", DONE_PAGE),
+                Err(_) => ("400 Bad Request", FAILED_PAGE),
+            };
+            let _ = respond(&mut stream, status, page).await;
+            return outcome;
+        }
+    }
+
+    fn check_callback(&self, query: &str) -> Result<String> {
+        let param = |name: &str| {
+            query
+                .split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(k, _)| *k == name)
+                .map(|(_, v)| urldecode(v))
+        };
+        if param("state").as_deref() != Some(self.state.as_str()) {
+            bail!("OAuth redirect carried the wrong state; ignoring its code");
+        }
+        if let Some(error) = param("error") {
+            let description = param("error_description").unwrap_or_default();
+            return Err(anyhow!("{error}: {description}")).context("authorization was not granted");
+        }
+        param("code").context("OAuth redirect had no code")
+    }
+}
+
+/// Read the request head and return its target, e.g. `/?code=..&state=..`.
+async fn read_request_target(stream: &mut TcpStream) -> Result<String> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+        if head.len() > MAX_REQUEST {
+            bail!("request head too large");
+        }
+    }
+    let head = String::from_utf8_lossy(&head);
+    let mut parts = head.lines().next().unwrap_or_default().split(' ');
+    match (parts.next(), parts.next()) {
+        (Some("GET"), Some(target)) => Ok(target.to_string()),
+        _ => bail!("not a GET request"),
+    }
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, body: &str) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/html; charset=utf-8\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Decode a query-string value: `+` is a space, `%XX` a byte.
+fn urldecode(s: &str) -> String {
+    let mut out = Vec::with_capacity(s.len());
+    let mut bytes = s.bytes();
+    while let Some(b) = bytes.next() {
+        match b {
+            b'+' => out.push(b' '),
+            b'%' => {
+                let hex: Vec<u8> = bytes.by_ref().take(2).collect();
+                match std::str::from_utf8(&hex)
+                    .ok()
+                    .and_then(|h| u8::from_str_radix(h, 16).ok())
+                {
+                    Some(byte) => out.push(byte),
+                    None => {
+                        out.push(b'%');
+                        out.extend_from_slice(&hex);
+                    }
+                }
+            }
+            _ => out.push(b),
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests_utils::ScriptedTransport;
+
+    /// Play the browser: follow the redirect to `url` and return the
+    /// status line and body of the page served back.
+    async fn visit(url: String) -> (String, String) {
+        let rest = url.strip_prefix("http://").unwrap();
+        let (host, target) = rest.split_at(rest.find('/').unwrap());
+        let mut stream = TcpStream::connect(host).await.unwrap();
+        stream
+            .write_all(format!("GET {target} HTTP/1.1\r\nHost: {host}\r\n\r\n").as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        (head.lines().next().unwrap().to_string(), body.to_string())
+    }
+
+    fn query_param(url: &str, name: &str) -> String {
+        let query = url.split_once('?').unwrap().1;
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| urldecode(v))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn completes_the_flow_from_a_local_callback() {
+        let fake = ScriptedTransport::new();
+        fake.reply(
+            200,
+            &[],
+            r#"{"access_token":"a","expires_in":14400,"token_type":"bearer","refresh_token":"r"}"#,
+        );
+        let client = Client::new("").with_transport(fake.clone());
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let browser = tokio::spawn(async move {
+            let authorize: String = rx.await.unwrap();
+            let redirect = query_param(&authorize, "redirect_uri");
+            let state = query_param(&authorize, "state");
+            assert!(authorize.contains("code_challenge_method=S256"));
+            visit(format!("{redirect}favicon.ico")).await;
+            visit(format!("{redirect}?code=the%2Fcode&state={state}")).await
+        });
+
+        let tokens = authorize_loopback(&client, "app-key", None, true, |url| {
+            tx.send(url.to_string()).unwrap();
+        })
+        .await
+        .unwrap();
+        assert_eq!(tokens.refresh_token.as_deref(), Some("r"));
+
+        let (status, body) = browser.await.unwrap();
+        assert_eq!(status, "HTTP/1.1 200 OK");
+        assert!(body.contains("close this tab"));
+
+        let seen = fake.seen.lock().unwrap();
+        let form = String::from_utf8_lossy(seen[0].body.as_deref().unwrap()).into_owned();
+        assert!(form.contains("code=the%2Fcode"));
+        assert!(form.contains("code_verifier="));
+        assert!(form.contains("redirect_uri=http%3A%2F%2F127.0.0.1%3A"));
+    }
+
+    #[tokio::test]
+    async fn an_idle_connection_does_not_hold_up_the_callback() {
+        let redirect = LoopbackRedirect::bind().await.unwrap();
+        let host = redirect
+            .redirect_uri()
+            .trim_start_matches("http://")
+            .trim_end_matches('/')
+            .to_string();
+        // A speculative preconnect that never sends a request.
+        let idle = TcpStream::connect(&host).await.unwrap();
+        let url = format!(
+            "{}?code=c&state={}",
+            redirect.redirect_uri(),
+            redirect.state()
+        );
+        let browser = tokio::spawn(visit(url));
+
+        let code = tokio::time::timeout(REQUEST_TIMEOUT * 3, redirect.wait_for_code())
+            .await
+            .expect("callback served despite the idle connection")
+            .unwrap();
+        assert_eq!(code, "c");
+        assert_eq!(browser.await.unwrap().0, "HTTP/1.1 200 OK");
+        drop(idle);
+    }
+
+    #[tokio::test]
+    async fn rejects_a_callback_with_the_wrong_state() {
+        let redirect = LoopbackRedirect::bind().await.unwrap();
+        let url = format!("{}?code=c&state=forged", redirect.redirect_uri());
+        let browser = tokio::spawn(visit(url));
+
+        let err = redirect.wait_for_code().await.unwrap_err();
+        assert!(err.to_string().contains("wrong state"), "{err}");
+        assert_eq!(browser.await.unwrap().0, "HTTP/1.1 400 Bad Request");
+    }
+
+    #[tokio::test]
+    async fn surfaces_a_denied_authorization() {
+        let redirect = LoopbackRedirect::bind().await.unwrap();
+        let url = format!(
+            "{}?error=access_denied&error_description=The+user+chose+not+to+give+your+app+access&state={}",
+            redirect.redirect_uri(),
+            redirect.state()
+        );
+        let browser = tokio::spawn(visit(url));
+
+        let err = redirect.wait_for_code().await.unwrap_err();
+        assert!(format!("{err:#}").contains("access_denied: The user chose not"));
+        browser.await.unwrap();
+    }
+}
@@ -5,12 +5,28 @@
 //! `token_access_type=offline` to the authorize URL, then exchange it via
 //! `oauth2/token` whenever the access token expires.
 //!
+//! Apps that can't keep a client secret (desktop, CLI, mobile) use PKCE
+//! instead: generate a [`Pkce`] pair, send the user to
+//! [`authorize_url_pkce`], then call [`exchange_code_pkce`] with the
+//! verifier and refresh with [`refresh_pkce`]. No secret is sent at any
+//! step.
+//!
+//! CLIs can skip the copy-paste step entirely: [`authorize_loopback`]
+//! catches the redirect on a `127.0.0.1` port and returns the tokens.
+//!
 //! See <https://developers.dropbox.com/oauth-guide> for the full flow.
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use crate::endpoints::{get_endpoint_url, Endpoint};
+use crate::transport::{HttpRequest, HttpResponse};
+use crate::Client;
+
+mod loopback;
+
+pub use loopback::{authorize_loopback, LoopbackRedirect};
 
 const AUTHORIZE_URL: &str = "https://www.dropbox.com/oauth2/authorize";
 
@@ -54,13 +70,39 @@ pub fn authorize_url(
     redirect_uri: &str,
     state: Option<&str>,
     offline: bool,
+) -> String {
+    build_authorize_url(client_id, Some(redirect_uri), state, offline, None)
+}
+
+/// [`authorize_url`] for the PKCE flow: adds the S256 `code_challenge` of
+/// `pkce`. Without a `redirect_uri` Dropbox shows the code to the user to
+/// paste back into the app, which suits CLIs.
+pub fn authorize_url_pkce(
+    client_id: &str,
+    redirect_uri: Option<&str>,
+    state: Option<&str>,
+    offline: bool,
+    pkce: &Pkce,
+) -> String {
+    build_authorize_url(client_id, redirect_uri, state, offline, Some(pkce))
+}
+
+fn build_authorize_url(
+    client_id: &str,
+    redirect_uri: Option<&str>,
+    state: Option<&str>,
+    offline: bool,
+    pkce: Option<&Pkce>,
 ) -> String {
     let mut url = format!(
-        "{}?client_id={}&response_type=code&redirect_uri={}",
+        "{}?client_id={}&response_type=code",
         AUTHORIZE_URL,
         urlencode(client_id),
-        urlencode(redirect_uri),
     );
+    if let Some(uri) = redirect_uri {
+        url.push_str("&redirect_uri=");
+        url.push_str(&urlencode(uri));
+    }
     if offline {
         url.push_str("&token_access_type=offline");
     }
@@ -68,9 +110,78 @@ pub fn authorize_url(
         url.push_str("&state=");
         url.push_str(&urlencode(s));
     }
+    if let Some(pkce) = pkce {
+        url.push_str("&code_challenge=");
+        url.push_str(pkce.challenge());
+        url.push_str("&code_challenge_method=S256");
+    }
     url
 }
 
+/// PKCE (RFC 7636) code verifier and its S256 challenge.
+///
+/// Keep the verifier between building the authorize URL and exchanging the
+/// code; it stands in for the client secret. `Debug` doesn't print it.
+#[derive(Clone)]
+pub struct Pkce {
+    verifier: String,
+    challenge: String,
+}
+
+impl Pkce {
+    /// A fresh pair: 32 random bytes from the OS, base64url-encoded into a
+    /// 43-character verifier. Fails only if the OS random number generator
+    /// is unavailable.
+    pub fn try_new() -> Result<Self> {
+        let mut bytes = [0u8; 32];
+        getrandom::getrandom(&mut bytes).context("generate PKCE verifier")?;
+        Ok(Self::from_verifier(base64url(&bytes)))
+    }
+
+    /// Like [`Pkce::try_new`].
+    ///
+    /// # Panics
+    ///
+    /// If the OS random number generator is unavailable.
+    pub fn new() -> Self {
+        Self::try_new().expect("OS random number generator unavailable")
+    }
+
+    /// Rebuild the pair from a verifier saved earlier, e.g. by a process
+    /// that handles the redirect separately.
+    pub fn from_verifier(verifier: impl Into<String>) -> Self {
+        let verifier = verifier.into();
+        let challenge = base64url(&Sha256::digest(verifier.as_bytes()));
+        Self {
+            verifier,
+            challenge,
+        }
+    }
+
+    pub fn verifier(&self) -> &str {
+        &self.verifier
+    }
+
+    pub fn challenge(&self) -> &str {
+        &self.challenge
+    }
+}
+
+impl Default for Pkce {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for Pkce {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pkce")
+            .field("verifier", &"<redacted>")
+            .field("challenge", &self.challenge)
+            .finish()
+    }
+}
+
 /// Exchange the authorization `code` (from your redirect callback) for an
 /// access token + refresh token.
 pub async fn exchange_code(
@@ -78,6 +189,25 @@ pub async fn exchange_code(
     client_secret: &str,
     code: &str,
     redirect_uri: &str,
+) -> Result<Tokens> {
+    exchange_code_with(
+        &Client::new(String::new()),
+        client_id,
+        client_secret,
+        code,
+        redirect_uri,
+    )
+    .await
+}
+
+/// [`exchange_code`] through a [`Client`]: its transport, rate limiter and
+/// middleware see the call. The client's own token is not used.
+pub async fn exchange_code_with(
+    client: &Client,
+    client_id: &str,
+    client_secret: &str,
+    code: &str,
+    redirect_uri: &str,
 ) -> Result<Tokens> {
     let form = [
         ("grant_type", "authorization_code"),
@@ -86,67 +216,202 @@ pub async fn exchange_code(
         ("client_secret", client_secret),
         ("redirect_uri", redirect_uri),
     ];
-    let resp = crate::AsyncClient
-        .post(token_url())
-        .form(&form)
-        .send()
+    let resp = client
+        .send_http(Endpoint::OAuth2TokenPost, form_request(token_url(), &form))
+        .await
+        .context("oauth2/token send failed")?;
+    parse_tokens(resp, "oauth2/token")
+}
+
+/// Exchange a PKCE authorization `code` using the verifier from the
+/// [`Pkce`] pair that built the authorize URL. `redirect_uri` must match
+/// the one in that URL, if any.
+pub async fn exchange_code_pkce(
+    client_id: &str,
+    code: &str,
+    code_verifier: &str,
+    redirect_uri: Option<&str>,
+) -> Result<Tokens> {
+    exchange_code_pkce_with(
+        &Client::new(String::new()),
+        client_id,
+        code,
+        code_verifier,
+        redirect_uri,
+    )
+    .await
+}
+
+/// [`exchange_code_pkce`] through a [`Client`]'s transport and middleware.
+pub async fn exchange_code_pkce_with(
+    client: &Client,
+    client_id: &str,
+    code: &str,
+    code_verifier: &str,
+    redirect_uri: Option<&str>,
+) -> Result<Tokens> {
+    let mut form = vec![
+        ("grant_type", "authorization_code"),
+        ("code", code),
+        ("client_id", client_id),
+        ("code_verifier", code_verifier),
+    ];
+    if let Some(uri) = redirect_uri {
+        form.push(("redirect_uri", uri));
+    }
+    let resp = client
+        .send_http(Endpoint::OAuth2TokenPost, form_request(token_url(), &form))
         .await
-        .context("oauth2/token send failed")?
-        .error_for_status()
-        .context("oauth2/token returned non-2xx")?;
-    resp.json().await.context("oauth2/token parse")
+        .context("oauth2/token send failed")?;
+    parse_tokens(resp, "oauth2/token")
 }
 
 /// Get a fresh access token using a refresh token. The refresh token itself
 /// stays valid across this call.
 pub async fn refresh(client_id: &str, client_secret: &str, refresh_token: &str) -> Result<Tokens> {
-    let form = [
-        ("grant_type", "refresh_token"),
-        ("refresh_token", refresh_token),
-        ("client_id", client_id),
-        ("client_secret", client_secret),
-    ];
-    let resp = crate::AsyncClient
-        .post(token_url())
-        .form(&form)
-        .send()
+    refresh_with(
+        &Client::new(String::new()),
+        client_id,
+        Some(client_secret),
+        refresh_token,
+    )
+    .await
+}
+
+/// [`refresh`] for tokens obtained through PKCE: only the `client_id` is
+/// sent.
+pub async fn refresh_pkce(client_id: &str, refresh_token: &str) -> Result<Tokens> {
+    refresh_with(&Client::new(String::new()), client_id, None, refresh_token).await
+}
+
+/// [`refresh`] through a specific client's transport and middleware. No
+/// `client_secret` means a PKCE refresh.
+pub(crate) async fn refresh_with(
+    client: &Client,
+    client_id: &str,
+    client_secret: Option<&str>,
+    refresh_token: &str,
+) -> Result<Tokens> {
+    let form = refresh_form(client_id, client_secret, refresh_token);
+    let resp = client
+        .send_http(Endpoint::OAuth2TokenPost, form_request(token_url(), &form))
         .await
-        .context("oauth2/token refresh send failed")?
-        .error_for_status()
-        .context("oauth2/token refresh returned non-2xx")?;
-    resp.json().await.context("oauth2/token refresh parse")
+        .context("oauth2/token refresh send failed")?;
+    parse_tokens(resp, "oauth2/token refresh")
 }
 
 /// Synchronous variant of [`refresh`] for blocking callers.
 pub fn refresh_sync(client_id: &str, client_secret: &str, refresh_token: &str) -> Result<Tokens> {
-    let form = [
-        ("grant_type", "refresh_token"),
-        ("refresh_token", refresh_token),
-        ("client_id", client_id),
-        ("client_secret", client_secret),
-    ];
-    let resp = crate::SyncClient
-        .post(token_url_sync())
-        .form(&form)
-        .send()
-        .context("oauth2/token refresh send failed")?
-        .error_for_status()
-        .context("oauth2/token refresh returned non-2xx")?;
-    resp.json().context("oauth2/token refresh parse")
+    refresh_sync_with(
+        &Client::new(String::new()),
+        client_id,
+        Some(client_secret),
+        refresh_token,
+    )
+}
+
+/// Synchronous variant of [`refresh_pkce`].
+pub fn refresh_pkce_sync(client_id: &str, refresh_token: &str) -> Result<Tokens> {
+    refresh_sync_with(&Client::new(String::new()), client_id, None, refresh_token)
+}
+
+/// [`refresh_sync`] through a specific client's transport and middleware.
+pub(crate) fn refresh_sync_with(
+    client: &Client,
+    client_id: &str,
+    client_secret: Option<&str>,
+    refresh_token: &str,
+) -> Result<Tokens> {
+    let form = refresh_form(client_id, client_secret, refresh_token);
+    let resp = client
+        .send_http_sync(
+            Endpoint::OAuth2TokenPost,
+            form_request(token_url_sync(), &form),
+        )
+        .context("oauth2/token refresh send failed")?;
+    parse_tokens(resp, "oauth2/token refresh")
 }
 
 /// Revoke the current access token. After this, both the access token and the
 /// refresh token (if any) are invalid.
 pub async fn revoke(access_token: &str) -> Result<()> {
-    crate::AsyncClient
-        .post("https://api.dropboxapi.com/2/auth/token/revoke")
-        .bearer_auth(access_token)
-        .send()
+    revoke_with(&Client::new(access_token)).await
+}
+
+/// [`revoke`] the token held by `client`, through its transport and
+/// middleware. The client's [`TokenStore`](crate::TokenStore), if any, is
+/// cleared too.
+pub async fn revoke_with(client: &Client) -> Result<()> {
+    let url = get_endpoint_url(Endpoint::AuthTokenRevokePost)
+        .2
+        .unwrap_or_else(|| get_endpoint_url(Endpoint::AuthTokenRevokePost).0);
+    let request = HttpRequest {
+        url,
+        headers: vec![(
+            "Authorization".to_string(),
+            format!("Bearer {}", client.token()),
+        )],
+        body: None,
+    };
+    let resp = client
+        .send_http(Endpoint::AuthTokenRevokePost, request)
         .await
-        .context("auth/token/revoke send failed")?
-        .error_for_status()
-        .context("auth/token/revoke returned non-2xx")?;
-    Ok(())
+        .context("auth/token/revoke send failed")?;
+    if !resp.is_success() {
+        return Err(anyhow!(
+            "HTTP {}: {}",
+            resp.status,
+            String::from_utf8_lossy(&resp.body)
+        ))
+        .context("auth/token/revoke returned non-2xx");
+    }
+    client.forget_stored_token()
+}
+
+fn refresh_form<'a>(
+    client_id: &'a str,
+    client_secret: Option<&'a str>,
+    refresh_token: &'a str,
+) -> Vec<(&'a str, &'a str)> {
+    let mut form = vec![
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token),
+        ("client_id", client_id),
+    ];
+    if let Some(secret) = client_secret {
+        form.push(("client_secret", secret));
+    }
+    form
+}
+
+/// `application/x-www-form-urlencoded` POST to `url`.
+fn form_request(url: String, form: &[(&str, &str)]) -> HttpRequest {
+    let body = form
+        .iter()
+        .map(|(k, v)| format!("{}={}", urlencode(k), urlencode(v)))
+        .collect::<Vec<_>>()
+        .join("&");
+    HttpRequest {
+        url,
+        headers: vec![(
+            "Content-Type".to_string(),
+            "application/x-www-form-urlencoded".to_string(),
+        )],
+        body: Some(body.into()),
+    }
+}
+
+/// Decode an `oauth2/token` response, labelling failures with `what`.
+fn parse_tokens(resp: HttpResponse, what: &str) -> Result<Tokens> {
+    if !resp.is_success() {
+        return Err(anyhow!(
+            "HTTP {}: {}",
+            resp.status,
+            String::from_utf8_lossy(&resp.body)
+        ))
+        .with_context(|| format!("{} returned non-2xx", what));
+    }
+    serde_json::from_slice(&resp.body).with_context(|| format!("{} parse", what))
 }
 
 /// Minimal URL form-encoding for the few characters that matter to OAuth2
@@ -165,9 +430,26 @@ fn urlencode(s: &str) -> String {
     out
 }
 
+/// Unpadded base64url (RFC 4648 §5), as PKCE requires.
+fn base64url(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+    let mut out = String::with_capacity((bytes.len() * 4).div_ceil(3));
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tests_utils::ScriptedTransport;
 
     #[test]
     fn authorize_url_basic() {
@@ -184,4 +466,61 @@ mod tests {
         assert!(url.contains("token_access_type=offline"));
         assert!(url.contains("state=xyz"));
     }
+
+    #[test]
+    fn base64url_without_padding() {
+        assert_eq!(base64url(b""), "");
+        assert_eq!(base64url(b"f"), "Zg");
+        assert_eq!(base64url(b"fo"), "Zm8");
+        assert_eq!(base64url(b"foo"), "Zm9v");
+        assert_eq!(base64url(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64url(&[0xfb, 0xff]), "-_8");
+    }
+
+    #[test]
+    fn pkce_challenge_is_s256_of_the_verifier() {
+        let pkce = Pkce::from_verifier("test-verifier-0123456789-abcdefghijklmnopqrstuvwxyz");
+        assert_eq!(
+            pkce.challenge(),
+            "nw4Cig7sk_DT0QSX9Qn-3UKs33fmbbp8bRuv1b5-wXw"
+        );
+
+        let fresh = Pkce::try_new().unwrap();
+        assert_eq!(fresh.verifier().len(), 43);
+        assert_ne!(fresh.verifier(), Pkce::new().verifier());
+        assert!(!format!("{fresh:?}").contains(fresh.verifier()));
+    }
+
+    #[test]
+    fn authorize_url_pkce_without_redirect() {
+        let pkce = Pkce::from_verifier("v".repeat(43));
+        let url = authorize_url_pkce("id", None, None, true, &pkce);
+        assert!(!url.contains("redirect_uri"));
+        assert!(url.contains(&format!("code_challenge={}", pkce.challenge())));
+        assert!(url.contains("code_challenge_method=S256"));
+        assert!(url.contains("token_access_type=offline"));
+    }
+
+    #[tokio::test]
+    async fn pkce_exchange_and_refresh_send_no_secret() {
+        let fake = ScriptedTransport::new();
+        let tokens =
+            r#"{"access_token":"a","expires_in":14400,"token_type":"bearer","refresh_token":"r"}"#;
+        fake.reply(200, &[], tokens).reply(200, &[], tokens);
+        let client = Client::new("").with_transport(fake.clone());
+
+        exchange_code_pkce_with(&client, "id", "code", "verifier", None)
+            .await
+            .unwrap();
+        refresh_with(&client, "id", None, "r").await.unwrap();
+
+        let seen = fake.seen.lock().unwrap();
+        let exchange = String::from_utf8_lossy(seen[0].body.as_deref().unwrap()).into_owned();
+        assert!(exchange.contains("code_verifier=verifier"));
+        let refresh = String::from_utf8_lossy(seen[1].body.as_deref().unwrap()).into_owned();
+        assert!(refresh.contains("grant_type=refresh_token"));
+        for body in [exchange, refresh] {
+            assert!(!body.contains("client_secret"), "{body}");
+        }
+    }
 }
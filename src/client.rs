@@ -2,44 +2,172 @@
 //!
 //! Two construction paths:
 //! - `Client::new(token)` — bring-your-own short-lived access token.
-//! - `Client::with_refresh(client_id, client_secret, refresh_token)` — the
+//! - `Client::with_refresh(token, expires_in, RefreshConfig)` — the
 //!   client now knows how to mint fresh access tokens via Dropbox's
-//!   `oauth2/token` refresh grant. Call `client.ensure_fresh()` (async) or
+//!   `oauth2/token` refresh grant, with or without a client secret
+//!   (PKCE). Call `client.ensure_fresh()` (async) or
 //!   `client.ensure_fresh_sync()` (blocking) before a request when you've
 //!   been idle long enough that the access token might have expired.
 //!
-//! Note: the request macros still take `access_token: &str`, so callers must
-//! pass `client.token()` per request. A future revision can let macros
-//! transparently re-issue with a refreshed token on 401.
+//! Any `*Request` can be run through a client with [`Client::execute`] (or
+//! [`Client::execute_sync`]). The request's own `access_token` is ignored;
+//! the client's token is used, refreshed proactively when expired and once
+//! more on a 401 before the call is replayed. Requests derive `Default`, so
+//! the token field can simply be left out:
+//!
+//! ```ignore
+//! let req = ListFolderRequest { payload: Some(args), ..Default::default() };
+//! let result = client.execute(&req).await?;
+//! ```
+//!
+//! Transport settings (timeouts, proxy, root certificates, pool limits,
+//! User-Agent suffix) are per client: [`Client::with_http_config`] derives a
+//! client that shares this one's token state but talks through its own
+//! connection pool, and [`Client::with_transport`] swaps the HTTP stack out
+//! entirely. [`Client::with_rate_limiter`] puts every request made through
+//! the client and its clones behind one shared [`RateLimiter`], and
+//! [`Client::with_middleware`] adds a [`Middleware`] that sees (and may veto)
+//! every request the client sends. [`Client::with_path_root`] scopes
+//! requests to a team space or shared namespace, and
+//! [`Client::with_team_root`] picks the account's root automatically.
+//! With a team access token, [`Client::with_select_user`] and
+//! [`Client::with_select_admin`] act on behalf of one team member.
+//! [`Client::with_token_store`] persists the token so it survives restarts.
+//!
+//! Refreshes are single-flight: when many tasks share a client and the
+//! token expires, one `oauth2/token` call is made and the others wait for
+//! its result. [`Client::with_refresh_window`] renews the token a little
+//! before it expires instead of on the first call after. The blocking
+//! entry points wait for an in-flight refresh by blocking their thread, so
+//! mixing them with async calls on the same client is only supported on a
+//! multi-threaded runtime (blocking calls from `spawn_blocking` or plain
+//! threads): on a current-thread runtime the thread they block may be the
+//! one that has to finish the async refresh, and the two deadlock.
+//!
+//! With the `tracing` feature every call runs in a `dropbox.call` span, and
+//! retries, token refreshes and upload-session steps are logged inside it.
+//! `Debug` output and traces never include tokens or secrets.
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
 use std::sync::{Arc, RwLock};
+
+use crate::endpoints::Endpoint;
+use crate::middleware::{Middleware, ResponseInfo, Vetoed};
+use crate::models::common::PathRoot;
+use crate::models::users::get_current_account::GetCurrentAccountRequest;
+use crate::models::users::RootInfo;
+use crate::rate_limit::RateLimiter;
+use crate::retry::RetryPolicy;
+use crate::telemetry;
+use crate::token_store::{StoredToken, TokenStore};
+use crate::transport::{
+    BodyStream, HttpConfig, HttpRequest, HttpResponse, ReqwestTransport, StreamingResponse,
+    Transport,
+};
 use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone)]
 pub struct Client {
     inner: Arc<Inner>,
+    transport: Arc<dyn Transport>,
+    retry: RetryPolicy,
+    limiter: Option<Arc<RateLimiter>>,
+    middleware: Arc<[Arc<dyn Middleware>]>,
+    path_root: Option<PathRoot>,
+    acting_as: Option<ActingAs>,
+    refresh_window: Duration,
+    #[cfg(feature = "tracing")]
+    trace_api_args: bool,
+}
+
+/// Team member a team-linked client acts as.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ActingAs {
+    /// `Dropbox-API-Select-User`: act as the member.
+    User(String),
+    /// `Dropbox-API-Select-Admin`: act as the admin, with access to the
+    /// member folders of the whole team.
+    Admin(String),
 }
 
 #[derive(Debug)]
 struct Inner {
     token: RwLock<TokenState>,
     refresh: Option<RefreshConfig>,
+    /// Shared by every clone; set in place by [`Client::with_token_store`].
+    token_store: RwLock<Option<Arc<dyn TokenStore>>>,
+    /// Held for the whole refresh grant, so concurrent refreshes queue up
+    /// and find the token already renewed.
+    refresh_lock: tokio::sync::Mutex<()>,
 }
 
-#[derive(Debug)]
 struct TokenState {
     access_token: String,
     expires_at: Option<Instant>,
+    /// Bumped on every refresh; tells a caller whether the token it used
+    /// has been replaced since.
+    generation: u64,
 }
 
-#[derive(Debug, Clone)]
+impl std::fmt::Debug for TokenState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenState")
+            .field("access_token", &"<redacted>")
+            .field("expires_at", &self.expires_at)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+/// What [`Client::with_refresh`] needs to run the refresh grant.
+#[derive(Clone)]
 pub struct RefreshConfig {
     pub client_id: String,
-    pub client_secret: String,
+    /// `None` for apps authorized through PKCE, which refresh with the
+    /// `client_id` alone.
+    pub client_secret: Option<String>,
     pub refresh_token: String,
 }
 
+impl RefreshConfig {
+    /// Refresh with the app's client secret.
+    pub fn new(
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        refresh_token: impl Into<String>,
+    ) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: Some(client_secret.into()),
+            refresh_token: refresh_token.into(),
+        }
+    }
+
+    /// Refresh a token obtained through the PKCE flow; no secret.
+    pub fn pkce(client_id: impl Into<String>, refresh_token: impl Into<String>) -> Self {
+        Self {
+            client_id: client_id.into(),
+            client_secret: None,
+            refresh_token: refresh_token.into(),
+        }
+    }
+}
+
+impl std::fmt::Debug for RefreshConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RefreshConfig")
+            .field("client_id", &self.client_id)
+            .field(
+                "client_secret",
+                &self.client_secret.as_ref().map(|_| "<redacted>"),
+            )
+            .field("refresh_token", &"<redacted>")
+            .finish()
+    }
+}
+
 impl Client {
     /// Construct a client with an existing short-lived access token. No
     /// refresh capability — once it expires (~4h after issue) every request
@@ -50,16 +178,29 @@ impl Client {
                 token: RwLock::new(TokenState {
                     access_token: token.into(),
                     expires_at: None,
+                    generation: 0,
                 }),
                 refresh: None,
+                token_store: RwLock::new(None),
+                refresh_lock: Default::default(),
             }),
+            transport: ReqwestTransport::shared_default(),
+            retry: RetryPolicy::default(),
+            limiter: None,
+            middleware: Arc::new([]),
+            path_root: None,
+            acting_as: None,
+            refresh_window: Duration::ZERO,
+            #[cfg(feature = "tracing")]
+            trace_api_args: false,
         }
     }
 
     /// Construct a client that knows how to refresh its own access token.
-    /// Pass the OAuth app's client_id, client_secret, and a long-lived
-    /// refresh token (acquired via [`crate::auth::exchange_code`] with
-    /// `offline=true`).
+    /// Pass the OAuth app's client_id, client_secret (unless the token came
+    /// from the PKCE flow), and a long-lived refresh token (acquired via
+    /// [`crate::auth::exchange_code`] or [`crate::auth::exchange_code_pkce`]
+    /// with `offline=true`).
     pub fn with_refresh(
         access_token: impl Into<String>,
         expires_in_secs: u64,
@@ -70,9 +211,279 @@ impl Client {
                 token: RwLock::new(TokenState {
                     access_token: access_token.into(),
                     expires_at: Some(Instant::now() + Duration::from_secs(expires_in_secs)),
+                    generation: 0,
                 }),
                 refresh: Some(cfg),
+                token_store: RwLock::new(None),
+                refresh_lock: Default::default(),
             }),
+            transport: ReqwestTransport::shared_default(),
+            retry: RetryPolicy::default(),
+            limiter: None,
+            middleware: Arc::new([]),
+            path_root: None,
+            acting_as: None,
+            refresh_window: Duration::ZERO,
+            #[cfg(feature = "tracing")]
+            trace_api_args: false,
+        }
+    }
+
+    /// Derive a client that uses `config` for its HTTP transport. The new
+    /// client shares this one's token and refresh state, so a refresh through
+    /// either is seen by both — handy for keeping a long-timeout client for
+    /// longpoll next to a short-timeout one for RPC.
+    ///
+    /// Fails if the proxy URL or a root certificate is invalid.
+    pub fn with_http_config(&self, config: HttpConfig) -> Result<Self> {
+        Ok(self.with_transport(Arc::new(ReqwestTransport::new(config)?)))
+    }
+
+    /// Derive a client that sends every request — endpoint calls, OAuth
+    /// refreshes and helper streams — through `transport`. Token and refresh
+    /// state stay shared with this client.
+    pub fn with_transport(&self, transport: Arc<dyn Transport>) -> Self {
+        Self {
+            transport,
+            ..self.clone()
+        }
+    }
+
+    /// Derive a client that retries according to `policy`. Use
+    /// `client.with_retry_policy(RetryPolicy::none())` to switch retries off
+    /// for a single request.
+    pub fn with_retry_policy(&self, policy: RetryPolicy) -> Self {
+        Self {
+            retry: policy,
+            ..self.clone()
+        }
+    }
+
+    /// The retry policy applied to every call made through this client.
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    /// Derive a client whose requests — endpoint calls, retries and the
+    /// streaming helpers — wait on `limiter` before going out, and report
+    /// every 429 to it so the whole client backs off together. Clones and
+    /// clients derived from the result share the same limiter; pass the same
+    /// `Arc` to several clients to make them share one budget.
+    pub fn with_rate_limiter(&self, limiter: Arc<RateLimiter>) -> Self {
+        Self {
+            limiter: Some(limiter),
+            ..self.clone()
+        }
+    }
+
+    /// The limiter set with [`Client::with_rate_limiter`], if any.
+    pub fn rate_limiter(&self) -> Option<&Arc<RateLimiter>> {
+        self.limiter.as_ref()
+    }
+
+    /// Derive a client whose `dropbox.call` trace spans also record the
+    /// `Dropbox-API-Arg` header. Off by default because the argument holds
+    /// file paths and other user data; access tokens are never recorded.
+    #[cfg(feature = "tracing")]
+    pub fn with_traced_api_args(&self, enabled: bool) -> Self {
+        Self {
+            trace_api_args: enabled,
+            ..self.clone()
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    pub(crate) fn traces_api_args(&self) -> bool {
+        self.trace_api_args
+    }
+
+    /// Derive a client that runs `middleware` around every request, after
+    /// any middleware already registered: `before` hooks run in registration
+    /// order, `after` and `failed` hooks in reverse. Clones and clients
+    /// derived from the result keep the whole chain.
+    pub fn with_middleware(&self, middleware: Arc<dyn Middleware>) -> Self {
+        let mut chain = self.middleware.to_vec();
+        chain.push(middleware);
+        Self {
+            middleware: chain.into(),
+            ..self.clone()
+        }
+    }
+
+    /// Derive a client that sends `Dropbox-API-Path-Root: root` with every
+    /// request, so paths resolve against that namespace instead of the
+    /// user's home. Use a derived client per call to target a single
+    /// request: `client.with_path_root(PathRoot::namespace_id(id)).execute(&req)`.
+    pub fn with_path_root(&self, root: PathRoot) -> Self {
+        Self {
+            path_root: Some(root),
+            ..self.clone()
+        }
+    }
+
+    /// The path root set with [`Client::with_path_root`], if any.
+    pub fn path_root(&self) -> Option<&PathRoot> {
+        self.path_root.as_ref()
+    }
+
+    /// Derive a client rooted at the account's root namespace, read from
+    /// `users/get_current_account`. For team-space members that is the
+    /// team space, making team folders visible; for everyone else paths
+    /// keep resolving against the home namespace.
+    pub async fn with_team_root(&self) -> Result<Self> {
+        let account = self
+            .execute(&GetCurrentAccountRequest::default())
+            .await?
+            .context("users/get_current_account returned empty")?;
+        Ok(self.with_path_root(account_root(&account.payload.root_info)))
+    }
+
+    /// Blocking variant of [`Client::with_team_root`].
+    pub fn with_team_root_sync(&self) -> Result<Self> {
+        let account = self
+            .execute_sync(&GetCurrentAccountRequest::default())?
+            .context("users/get_current_account returned empty")?;
+        Ok(self.with_path_root(account_root(&account.payload.root_info)))
+    }
+
+    /// Derive a client that acts on behalf of team member `member_id`
+    /// (`Dropbox-API-Select-User`). Needs a team access token with member
+    /// file access; every `files`, `sharing`, `file_properties` (etc.) call
+    /// then runs as that member. Replaces any [`Client::with_select_admin`].
+    pub fn with_select_user(&self, member_id: impl Into<String>) -> Self {
+        Self {
+            acting_as: Some(ActingAs::User(member_id.into())),
+            ..self.clone()
+        }
+    }
+
+    /// Derive a client that acts as team admin `member_id`
+    /// (`Dropbox-API-Select-Admin`), which can see every team member's
+    /// folders through the team space. Replaces any
+    /// [`Client::with_select_user`].
+    pub fn with_select_admin(&self, member_id: impl Into<String>) -> Self {
+        Self {
+            acting_as: Some(ActingAs::Admin(member_id.into())),
+            ..self.clone()
+        }
+    }
+
+    /// Headers that scope a request to a namespace or team member, added to
+    /// user-route calls (see [`Endpoint::is_user_route`]) and the streaming
+    /// helpers; `team/*`, `team_log/*` and OAuth calls go without them.
+    pub(crate) fn context_headers(&self) -> Vec<(String, String)> {
+        let mut headers = Vec::new();
+        if let Some(root) = &self.path_root {
+            headers.push(("Dropbox-API-Path-Root".to_string(), root.header_value()));
+        }
+        match &self.acting_as {
+            Some(ActingAs::User(id)) => {
+                headers.push(("Dropbox-API-Select-User".to_string(), id.clone()))
+            }
+            Some(ActingAs::Admin(id)) => {
+                headers.push(("Dropbox-API-Select-Admin".to_string(), id.clone()))
+            }
+            None => {}
+        }
+        headers
+    }
+
+    /// Send one request through the rate limiter, the middleware chain and
+    /// the transport. No retries; see `dispatch` for those.
+    pub(crate) async fn send_http(
+        &self,
+        endpoint: Endpoint,
+        request: HttpRequest,
+    ) -> Result<HttpResponse> {
+        if let Some(limiter) = &self.limiter {
+            limiter.acquire().await;
+        }
+        let request = self.intercept(endpoint, request)?;
+        let started = Instant::now();
+        let outcome = self.transport.send(request).await;
+        self.observe(
+            endpoint,
+            outcome
+                .as_ref()
+                .map(|r| ResponseInfo::new(r.status, &r.headers, started.elapsed())),
+        );
+        outcome
+    }
+
+    /// Blocking variant of [`Client::send_http`].
+    pub(crate) fn send_http_sync(
+        &self,
+        endpoint: Endpoint,
+        request: HttpRequest,
+    ) -> Result<HttpResponse> {
+        if let Some(limiter) = &self.limiter {
+            limiter.acquire_sync();
+        }
+        let request = self.intercept(endpoint, request)?;
+        let started = Instant::now();
+        let outcome = self.transport.send_sync(request);
+        self.observe(
+            endpoint,
+            outcome
+                .as_ref()
+                .map(|r| ResponseInfo::new(r.status, &r.headers, started.elapsed())),
+        );
+        outcome
+    }
+
+    /// Streaming variant of [`Client::send_http`]; `after` hooks run as soon
+    /// as the headers arrive.
+    pub(crate) async fn send_http_streaming(
+        &self,
+        endpoint: Endpoint,
+        request: HttpRequest,
+        body: Option<BodyStream>,
+    ) -> Result<StreamingResponse> {
+        if let Some(limiter) = &self.limiter {
+            limiter.acquire().await;
+        }
+        let request = self.intercept(endpoint, request)?;
+        let started = Instant::now();
+        let outcome = self.transport.send_streaming(request, body).await;
+        self.observe(
+            endpoint,
+            outcome
+                .as_ref()
+                .map(|r| ResponseInfo::new(r.status, &r.headers, started.elapsed())),
+        );
+        outcome
+    }
+
+    /// Run the `before` hooks; the first refusal vetoes the request.
+    fn intercept(&self, endpoint: Endpoint, mut request: HttpRequest) -> Result<HttpRequest> {
+        for middleware in self.middleware.iter() {
+            middleware
+                .before(endpoint, &mut request)
+                .map_err(|err| err.context(Vetoed { endpoint }))?;
+        }
+        Ok(request)
+    }
+
+    /// Report one exchange to the middleware chain and the rate limiter. A
+    /// 429 pauses every request through the limiter for `Retry-After` (or
+    /// the policy's initial backoff when the header is missing).
+    fn observe(&self, endpoint: Endpoint, outcome: Result<ResponseInfo<'_>, &anyhow::Error>) {
+        match outcome {
+            Ok(info) => {
+                if let (Some(limiter), 429) = (&self.limiter, info.status) {
+                    let wait = crate::dispatch::retry_after(info.headers)
+                        .unwrap_or(self.retry.initial_backoff);
+                    limiter.pause_for(wait);
+                }
+                for middleware in self.middleware.iter().rev() {
+                    middleware.after(endpoint, &info);
+                }
+            }
+            Err(err) => {
+                for middleware in self.middleware.iter().rev() {
+                    middleware.failed(endpoint, err);
+                }
+            }
         }
     }
 
@@ -91,22 +502,42 @@ impl Client {
         }
     }
 
+    /// Renew the access token this long before it expires instead of on
+    /// the first call after. Zero (the default) refreshes only once the
+    /// token has expired.
+    pub fn with_refresh_window(&self, window: Duration) -> Self {
+        Self {
+            refresh_window: window,
+            ..self.clone()
+        }
+    }
+
+    /// True if the token expires within the refresh window.
+    fn needs_refresh(&self) -> bool {
+        match self.inner.token.read().unwrap().expires_at {
+            Some(t) => Instant::now() + self.refresh_window >= t,
+            None => false,
+        }
+    }
+
+    /// Counts refreshes. Read it before sending; pass it to
+    /// [`Client::refresh_rejected`] if the token is then rejected.
+    pub(crate) fn token_generation(&self) -> u64 {
+        self.inner.token.read().unwrap().generation
+    }
+
     /// Refresh the access token using the registered refresh config. No-op
-    /// if no refresh config or token isn't expired yet.
+    /// if no refresh config or token isn't due yet. Concurrent callers share
+    /// one refresh: the rest wait for it and then find the token fresh.
     pub async fn ensure_fresh(&self) -> Result<()> {
-        if !self.is_expired() {
+        if !self.can_refresh() || !self.needs_refresh() {
             return Ok(());
         }
-        let cfg = match &self.inner.refresh {
-            Some(c) => c.clone(),
-            None => return Ok(()),
-        };
-        let tokens =
-            crate::auth::refresh(&cfg.client_id, &cfg.client_secret, &cfg.refresh_token).await?;
-        let mut state = self.inner.token.write().unwrap();
-        state.access_token = tokens.access_token;
-        state.expires_at = Some(Instant::now() + Duration::from_secs(tokens.expires_in));
-        Ok(())
+        let _guard = self.inner.refresh_lock.lock().await;
+        if !self.needs_refresh() {
+            return Ok(());
+        }
+        self.refresh("expired").await
     }
 
     /// Unconditionally mint a new access token via the refresh grant. No-op
@@ -114,30 +545,38 @@ impl Client {
     /// `call` when a 401 comes back despite our expiry clock saying the
     /// token was still fresh.
     pub async fn force_refresh(&self) -> Result<()> {
-        let cfg = match &self.inner.refresh {
-            Some(c) => c.clone(),
-            None => return Ok(()),
-        };
-        let tokens =
-            crate::auth::refresh(&cfg.client_id, &cfg.client_secret, &cfg.refresh_token).await?;
-        let mut state = self.inner.token.write().unwrap();
-        state.access_token = tokens.access_token;
-        state.expires_at = Some(Instant::now() + Duration::from_secs(tokens.expires_in));
-        Ok(())
+        self.refresh_rejected(self.token_generation()).await
     }
 
     /// Sync variant of [`Client::force_refresh`].
     pub fn force_refresh_sync(&self) -> Result<()> {
-        let cfg = match &self.inner.refresh {
-            Some(c) => c.clone(),
-            None => return Ok(()),
-        };
-        let tokens =
-            crate::auth::refresh_sync(&cfg.client_id, &cfg.client_secret, &cfg.refresh_token)?;
-        let mut state = self.inner.token.write().unwrap();
-        state.access_token = tokens.access_token;
-        state.expires_at = Some(Instant::now() + Duration::from_secs(tokens.expires_in));
-        Ok(())
+        self.refresh_rejected_sync(self.token_generation())
+    }
+
+    /// The token of `generation` was rejected: refresh it, unless another
+    /// caller already has, in which case the current token is the new one.
+    pub(crate) async fn refresh_rejected(&self, generation: u64) -> Result<()> {
+        if !self.can_refresh() {
+            return Ok(());
+        }
+        let _guard = self.inner.refresh_lock.lock().await;
+        if self.token_generation() != generation {
+            return Ok(());
+        }
+        self.refresh("rejected").await
+    }
+
+    /// Sync variant of [`Client::refresh_rejected`]. Blocks on the refresh
+    /// lock like [`Client::ensure_fresh_sync`].
+    pub(crate) fn refresh_rejected_sync(&self, generation: u64) -> Result<()> {
+        if !self.can_refresh() {
+            return Ok(());
+        }
+        let _guard = futures::executor::block_on(self.inner.refresh_lock.lock());
+        if self.token_generation() != generation {
+            return Ok(());
+        }
+        self.refresh_sync("rejected")
     }
 
     /// Run an async closure that builds and executes a request, auto-
@@ -157,41 +596,225 @@ impl Client {
         Fut: std::future::Future<Output = Result<T>>,
     {
         self.ensure_fresh().await?;
+        let generation = self.token_generation();
         let token = self.token();
         match f(token).await {
             Ok(v) => Ok(v),
             Err(e) => {
                 // If the error is specifically an Unauthorized wrapped in the
                 // ApiError enum, try one force-refresh + replay.
-                let is_401 = e
-                    .downcast_ref::<crate::errors::ApiError>()
-                    .is_some_and(|api| matches!(api, crate::errors::ApiError::Unauthorized(_)));
-                if !is_401 || self.inner.refresh.is_none() {
+                if !is_unauthorized(&e) || self.inner.refresh.is_none() {
                     return Err(e);
                 }
-                self.force_refresh().await?;
+                self.refresh_rejected(generation).await?;
                 let token = self.token();
                 f(token).await
             }
         }
     }
 
-    /// Sync version of [`Client::ensure_fresh`] for blocking callers.
+    /// Blocking variant of [`Client::call`]: refreshes first if the token
+    /// has expired, and replays once with a new token if the closure fails
+    /// with `ApiError::Unauthorized`.
+    ///
+    /// ```ignore
+    /// client.call_sync(|token| {
+    ///     req::ListFolderRequest { access_token: &token, payload: Some(...) }
+    ///         .call_sync()
+    /// })?;
+    /// ```
+    pub fn call_sync<T, F>(&self, mut f: F) -> Result<T>
+    where
+        F: FnMut(String) -> Result<T>,
+    {
+        self.ensure_fresh_sync()?;
+        let generation = self.token_generation();
+        match f(self.token()) {
+            Ok(v) => Ok(v),
+            Err(e) => {
+                if !is_unauthorized(&e) || self.inner.refresh.is_none() {
+                    return Err(e);
+                }
+                self.refresh_rejected_sync(generation)?;
+                f(self.token())
+            }
+        }
+    }
+
+    /// Execute any request through this client. Equivalent to
+    /// `request.call()` but with the client's token, proactive refresh and
+    /// 401 force-refresh-and-replay.
+    pub fn execute<O, R>(&self, request: &R) -> BoxFuture<'static, Result<Option<O>>>
+    where
+        R: crate::api::Service<O>,
+    {
+        request.call_with(self)
+    }
+
+    /// Blocking variant of [`Client::execute`].
+    pub fn execute_sync<O, R>(&self, request: &R) -> Result<Option<O>>
+    where
+        R: crate::api::Service<O>,
+    {
+        request.call_sync_with(self)
+    }
+
+    /// True if the client was built with a `RefreshConfig`.
+    pub(crate) fn can_refresh(&self) -> bool {
+        self.inner.refresh.is_some()
+    }
+
+    /// Sync version of [`Client::ensure_fresh`] for blocking callers. Waits
+    /// for an in-flight refresh by blocking the thread; see the module docs
+    /// for why that rules out sharing the client with async callers on a
+    /// current-thread runtime.
     pub fn ensure_fresh_sync(&self) -> Result<()> {
-        if !self.is_expired() {
+        if !self.can_refresh() || !self.needs_refresh() {
             return Ok(());
         }
+        let _guard = futures::executor::block_on(self.inner.refresh_lock.lock());
+        if !self.needs_refresh() {
+            return Ok(());
+        }
+        self.refresh_sync("expired")
+    }
+
+    /// Run the refresh grant and store the new token. `reason` only labels
+    /// the trace event.
+    async fn refresh(&self, reason: &'static str) -> Result<()> {
+        let cfg = match &self.inner.refresh {
+            Some(c) => c.clone(),
+            None => return Ok(()),
+        };
+        telemetry::token_refresh(reason);
+        let tokens = crate::auth::refresh_with(
+            self,
+            &cfg.client_id,
+            cfg.client_secret.as_deref(),
+            &cfg.refresh_token,
+        )
+        .await
+        .map_err(|err| {
+            telemetry::token_refresh_failed(&err);
+            err
+        })?;
+        self.store(tokens.access_token, tokens.expires_in);
+        Ok(())
+    }
+
+    /// Blocking variant of [`Client::refresh`].
+    fn refresh_sync(&self, reason: &'static str) -> Result<()> {
         let cfg = match &self.inner.refresh {
             Some(c) => c.clone(),
             None => return Ok(()),
         };
-        let tokens =
-            crate::auth::refresh_sync(&cfg.client_id, &cfg.client_secret, &cfg.refresh_token)?;
-        let mut state = self.inner.token.write().unwrap();
-        state.access_token = tokens.access_token;
-        state.expires_at = Some(Instant::now() + Duration::from_secs(tokens.expires_in));
+        telemetry::token_refresh(reason);
+        let tokens = crate::auth::refresh_sync_with(
+            self,
+            &cfg.client_id,
+            cfg.client_secret.as_deref(),
+            &cfg.refresh_token,
+        )
+        .map_err(|err| {
+            telemetry::token_refresh_failed(&err);
+            err
+        })?;
+        self.store(tokens.access_token, tokens.expires_in);
         Ok(())
     }
+
+    fn store(&self, access_token: String, expires_in: u64) {
+        {
+            let mut state = self.inner.token.write().unwrap();
+            state.access_token = access_token;
+            state.expires_at = Some(Instant::now() + Duration::from_secs(expires_in));
+            state.generation += 1;
+        }
+        telemetry::token_refreshed(expires_in);
+        // The new token is already in use; a store that can't keep up only
+        // costs a refresh after the next restart, so don't fail the call.
+        if let Err(err) = self.persist() {
+            telemetry::token_store_failed(&err);
+        }
+    }
+
+    /// Use `store` for this client's token: start from the saved token if
+    /// there is one, and save the token again after every refresh. An empty
+    /// store is seeded with the client's current token.
+    ///
+    /// The store is installed in the token state every clone shares, so
+    /// clones made before this call load from and save to it as well.
+    /// Replaces any store set earlier.
+    pub fn with_token_store(self, store: Arc<dyn TokenStore>) -> Result<Self> {
+        let saved = store.load().context("load stored token")?;
+        let seed = saved.is_none();
+        if let Some(saved) = saved {
+            let mut state = self.inner.token.write().unwrap();
+            state.access_token = saved.access_token;
+            state.expires_at = saved.expires_at.map(instant_at);
+            state.generation += 1;
+        }
+        *self.inner.token_store.write().unwrap() = Some(store);
+        if seed && !self.token().is_empty() {
+            self.persist().context("save token")?;
+        }
+        Ok(self)
+    }
+
+    /// Save the current token to the token store, if any.
+    fn persist(&self) -> Result<()> {
+        let Some(store) = self.token_store() else {
+            return Ok(());
+        };
+        let token = {
+            let state = self.inner.token.read().unwrap();
+            StoredToken {
+                access_token: state.access_token.clone(),
+                expires_at: state.expires_at.map(wall_clock_at),
+                refresh_token: self.inner.refresh.as_ref().map(|r| r.refresh_token.clone()),
+            }
+        };
+        store.save(&token)
+    }
+
+    /// Drop the saved token after it was revoked.
+    pub(crate) fn forget_stored_token(&self) -> Result<()> {
+        match self.token_store() {
+            Some(store) => store.delete().context("delete stored token"),
+            None => Ok(()),
+        }
+    }
+
+    fn token_store(&self) -> Option<Arc<dyn TokenStore>> {
+        self.inner.token_store.read().unwrap().clone()
+    }
+}
+
+/// True if `err` is the 401 a stale token gets.
+fn is_unauthorized(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<crate::errors::ApiError>()
+        .is_some_and(|api| matches!(api, crate::errors::ApiError::Unauthorized(_)))
+}
+
+/// Wall-clock time of a monotonic deadline, for storage.
+fn wall_clock_at(deadline: Instant) -> DateTime<Utc> {
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    Utc::now() + chrono::Duration::from_std(remaining).unwrap_or_default()
+}
+
+/// Monotonic deadline for a stored wall-clock time; past times are due now.
+fn instant_at(at: DateTime<Utc>) -> Instant {
+    let remaining = (at - Utc::now()).to_std().unwrap_or_default();
+    Instant::now() + remaining
+}
+
+/// The path root for an account: its root namespace on team spaces, home
+/// otherwise.
+fn account_root(info: &RootInfo) -> PathRoot {
+    match info {
+        RootInfo::Team(team) => PathRoot::root(team.root_namespace_id.clone()),
+        RootInfo::User(_) => PathRoot::home(),
+    }
 }
 
 #[cfg(test)]
@@ -251,7 +874,7 @@ mod tests {
                 14400, // not-yet-expired by clock — only the 401 should trigger refresh
                 RefreshConfig {
                     client_id: "id".into(),
-                    client_secret: "secret".into(),
+                    client_secret: Some("secret".into()),
                     refresh_token: "rt".into(),
                 },
             );
@@ -283,6 +906,584 @@ mod tests {
         .await;
     }
 
+    #[cfg(feature = "test-utils")]
+    #[tokio::test]
+    async fn execute_refreshes_on_401_and_replays() {
+        use crate::api;
+        use crate::tests_utils::with_test_server_async;
+
+        with_test_server_async(|mut server| async move {
+            let m_initial = server
+                .mock("POST", "/2/users/get_current_account")
+                .match_header("Authorization", "Bearer stale-token")
+                .with_status(401)
+                .with_body(
+                    r#"{"error_summary":"expired_access_token/.","error":{".tag":"expired_access_token"}}"#,
+                )
+                .expect(1)
+                .create_async()
+                .await;
+            let m_refresh = server
+                .mock("POST", "/oauth2/token")
+                .with_status(200)
+                .with_header("Content-Type", "application/json")
+                .with_body(
+                    r#"{"access_token":"new-token","expires_in":14400,"token_type":"bearer"}"#,
+                )
+                .expect(1)
+                .create_async()
+                .await;
+            let m_retry = server
+                .mock("POST", "/2/users/get_current_account")
+                .match_header("Authorization", "Bearer new-token")
+                .with_status(200)
+                .with_body("")
+                .expect(1)
+                .create_async()
+                .await;
+
+            let client = Client::with_refresh(
+                "stale-token",
+                14400,
+                RefreshConfig {
+                    client_id: "id".into(),
+                    client_secret: Some("secret".into()),
+                    refresh_token: "rt".into(),
+                },
+            );
+            let req = api::users::get_current_account::GetCurrentAccountRequest::default();
+            client
+                .execute(&req)
+                .await
+                .expect("execute should refresh and succeed");
+            assert_eq!(client.token(), "new-token");
+
+            m_initial.assert();
+            m_refresh.assert();
+            m_retry.assert();
+        })
+        .await;
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn execute_sync_refreshes_expired_token_first() {
+        use crate::api;
+        use crate::tests_utils::with_test_server_sync;
+
+        with_test_server_sync(|mut server| {
+            let m_refresh = server
+                .mock("POST", "/oauth2/token")
+                .with_status(200)
+                .with_header("Content-Type", "application/json")
+                .with_body(
+                    r#"{"access_token":"new-token","expires_in":14400,"token_type":"bearer"}"#,
+                )
+                .expect(1)
+                .create();
+            let m_call = server
+                .mock("POST", "/2/users/get_current_account")
+                .match_header("Authorization", "Bearer new-token")
+                .with_status(200)
+                .with_body("")
+                .expect(1)
+                .create();
+
+            let client = Client::with_refresh(
+                "stale-token",
+                0,
+                RefreshConfig {
+                    client_id: "id".into(),
+                    client_secret: Some("secret".into()),
+                    refresh_token: "rt".into(),
+                },
+            );
+            let req = api::users::get_current_account::GetCurrentAccountRequest::default();
+            client
+                .execute_sync(&req)
+                .expect("execute_sync should refresh and succeed");
+
+            m_refresh.assert();
+            m_call.assert();
+        });
+    }
+
+    #[test]
+    fn call_sync_refreshes_on_401_and_retries() {
+        use crate::errors::ApiError;
+        use crate::tests_utils::ScriptedTransport;
+
+        let fake = ScriptedTransport::new();
+        fake.reply(
+            200,
+            &[],
+            r#"{"access_token":"new-token","expires_in":14400,"token_type":"bearer"}"#,
+        );
+        let client = Client::with_refresh("stale-token", 14400, RefreshConfig::pkce("id", "rt"))
+            .with_transport(fake.clone());
+
+        let mut tokens = Vec::new();
+        let out = client
+            .call_sync(|token| {
+                tokens.push(token.clone());
+                if token == "stale-token" {
+                    return Err(ApiError::Unauthorized(anyhow::anyhow!("expired")).into());
+                }
+                Ok(token)
+            })
+            .expect("call_sync should refresh and succeed");
+        assert_eq!(out, "new-token");
+        assert_eq!(tokens, ["stale-token", "new-token"]);
+        assert_eq!(fake.calls(), 1);
+    }
+
+    #[test]
+    fn execute_sync_refreshes_on_401_and_replays() {
+        use crate::tests_utils::ScriptedTransport;
+
+        let fake = ScriptedTransport::new();
+        fake.reply(
+            401,
+            &[],
+            r#"{"error_summary":"expired_access_token/.","error":{".tag":"expired_access_token"}}"#,
+        )
+        .reply(
+            200,
+            &[],
+            r#"{"access_token":"new-token","expires_in":14400,"token_type":"bearer"}"#,
+        )
+        .reply(
+            200,
+            &[],
+            r#"{"used":1,"allocation":{".tag":"individual","allocated":2}}"#,
+        );
+        let client = Client::with_refresh("stale-token", 14400, RefreshConfig::pkce("id", "rt"))
+            .with_transport(fake.clone());
+
+        let req = crate::api::users::get_space_usage::GetSpaceUsageRequest::default();
+        client.execute_sync(&req).expect("replayed after refresh");
+        let seen = fake.seen.lock().unwrap();
+        assert_eq!(seen.len(), 3);
+        assert!(seen[1].url.ends_with("/oauth2/token"));
+        assert_eq!(seen[2].header("Authorization"), Some("Bearer new-token"));
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[tokio::test]
+    async fn with_http_config_uses_own_transport_and_shares_token() {
+        use crate::api;
+        use crate::tests_utils::with_test_server_async;
+
+        with_test_server_async(|mut server| async move {
+            let mock = server
+                .mock("POST", "/2/users/get_current_account")
+                .match_header(
+                    "User-Agent",
+                    format!("{} my-app/1.2", crate::USER_AGENT).as_str(),
+                )
+                .match_header("Authorization", "Bearer abc")
+                .with_status(200)
+                .with_body("")
+                .expect(1)
+                .create_async()
+                .await;
+
+            let base = Client::new("abc");
+            let client = base
+                .with_http_config(HttpConfig {
+                    timeout: Some(Duration::from_secs(30)),
+                    user_agent_suffix: Some("my-app/1.2".into()),
+                    ..Default::default()
+                })
+                .expect("valid config");
+            assert_eq!(client.token(), base.token());
+
+            let req = api::users::get_current_account::GetCurrentAccountRequest::default();
+            client.execute(&req).await.expect("call succeeds");
+            mock.assert();
+        })
+        .await;
+    }
+
+    #[test]
+    fn debug_output_redacts_secrets() {
+        let c = Client::with_refresh(
+            "access-secret",
+            60,
+            RefreshConfig {
+                client_id: "app".into(),
+                client_secret: Some("app-secret".into()),
+                refresh_token: "refresh-secret".into(),
+            },
+        );
+        let debug = format!("{c:?}");
+        assert!(debug.contains("app"));
+        assert!(!debug.contains("access-secret"));
+        assert!(!debug.contains("app-secret"));
+        assert!(!debug.contains("refresh-secret"));
+    }
+
+    #[test]
+    fn with_team_root_switches_team_space_members_to_the_root_namespace() {
+        use crate::api::common::PathRoot;
+        use crate::tests_utils::ScriptedTransport;
+
+        const TEAM_ACCOUNT: &str = r#"{"account_id":"dbid:1","name":{"given_name":"A","surname":"B","familiar_name":"A","display_name":"A B","abbreviated_name":"AB"},"email":"a@b.c","email_verified":true,"disabled":false,"locale":"en","referral_link":"https://db.tt/x","is_paired":false,"account_type":{".tag":"business"},"root_info":{".tag":"team","root_namespace_id":"100","home_namespace_id":"200","home_path":"/A B"}}"#;
+
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], TEAM_ACCOUNT).reply(
+            200,
+            &[],
+            r#"{"used":1,"allocation":{".tag":"individual","allocated":2}}"#,
+        );
+        let base = Client::new("t").with_transport(fake.clone());
+
+        let client = base.with_team_root_sync().expect("account read");
+        assert_eq!(client.path_root(), Some(&PathRoot::root("100")));
+        assert_eq!(base.path_root(), None);
+
+        let req = crate::api::users::get_space_usage::GetSpaceUsageRequest::default();
+        client.execute_sync(&req).expect("call succeeds");
+        let seen = fake.seen.lock().unwrap();
+        assert_eq!(seen[0].header("Dropbox-API-Path-Root"), None);
+        assert_eq!(
+            seen[1].header("Dropbox-API-Path-Root"),
+            Some(r#"{".tag":"root","root":"100"}"#)
+        );
+    }
+
+    #[test]
+    fn rejected_path_root_decodes_to_its_union() {
+        use crate::api::common::{PathRoot, PathRootError};
+        use crate::tests_utils::ScriptedTransport;
+        use crate::{DropboxError, DropboxErrorKind, TypedError};
+
+        let fake = ScriptedTransport::new();
+        fake.reply(
+            422,
+            &[],
+            r#"{"error_summary":"no_permission/..","error":{".tag":"no_permission"}}"#,
+        );
+        let client = Client::new("t")
+            .with_transport(fake)
+            .with_path_root(PathRoot::namespace_id("9"));
+
+        let req = crate::api::users::get_space_usage::GetSpaceUsageRequest::default();
+        let err = client.execute_sync(&req).expect_err("422");
+        let details = err.downcast_ref::<DropboxError>().expect("DropboxError");
+        assert_eq!(details.kind, DropboxErrorKind::PathRoot);
+        assert!(matches!(
+            err.downcast_ref::<TypedError<PathRootError>>()
+                .map(TypedError::get),
+            Some(PathRootError::NoPermission)
+        ));
+    }
+
+    #[test]
+    fn select_user_and_admin_replace_each_other() {
+        use crate::tests_utils::ScriptedTransport;
+
+        const SPACE_USAGE: &str = r#"{"used":1,"allocation":{".tag":"individual","allocated":2}}"#;
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], SPACE_USAGE)
+            .reply(200, &[], SPACE_USAGE);
+        let team = Client::new("team-token").with_transport(fake.clone());
+        let req = crate::api::users::get_space_usage::GetSpaceUsageRequest::default();
+
+        let member = team.with_select_user("dbmid:member");
+        member.execute_sync(&req).expect("call succeeds");
+        member
+            .with_select_admin("dbmid:admin")
+            .execute_sync(&req)
+            .expect("call succeeds");
+
+        let seen = fake.seen.lock().unwrap();
+        assert_eq!(
+            seen[0].header("Dropbox-API-Select-User"),
+            Some("dbmid:member")
+        );
+        assert_eq!(seen[0].header("Dropbox-API-Select-Admin"), None);
+        assert_eq!(seen[1].header("Dropbox-API-Select-User"), None);
+        assert_eq!(
+            seen[1].header("Dropbox-API-Select-Admin"),
+            Some("dbmid:admin")
+        );
+    }
+
+    #[test]
+    fn team_routes_go_without_select_and_path_root_headers() {
+        use crate::tests_utils::ScriptedTransport;
+
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], r#"{"members":[],"cursor":"c","has_more":false}"#);
+        let admin = Client::new("team-token")
+            .with_transport(fake.clone())
+            .with_path_root(PathRoot::namespace_id("9"))
+            .with_select_admin("dbmid:admin");
+
+        let req = crate::api::team::members_list::MembersListRequest {
+            payload: Some(crate::api::team::MembersListArg::default()),
+            ..Default::default()
+        };
+        admin.execute_sync(&req).expect("call succeeds");
+
+        let seen = fake.seen.lock().unwrap();
+        assert!(seen[0].url.ends_with("/team/members/list_v2"));
+        assert_eq!(seen[0].header("Authorization"), Some("Bearer team-token"));
+        assert_eq!(seen[0].header("Dropbox-API-Select-Admin"), None);
+        assert_eq!(seen[0].header("Dropbox-API-Select-User"), None);
+        assert_eq!(seen[0].header("Dropbox-API-Path-Root"), None);
+    }
+
+    #[test]
+    fn token_store_is_seeded_and_updated_on_refresh() {
+        use crate::tests_utils::ScriptedTransport;
+        use crate::token_store::{MemoryTokenStore, TokenStore};
+
+        let fake = ScriptedTransport::new();
+        fake.reply(
+            200,
+            &[],
+            r#"{"access_token":"new","expires_in":14400,"token_type":"bearer"}"#,
+        )
+        .reply(
+            200,
+            &[],
+            r#"{"used":1,"allocation":{".tag":"individual","allocated":2}}"#,
+        );
+        let store = Arc::new(MemoryTokenStore::new());
+        let client = Client::with_refresh("old", 0, RefreshConfig::pkce("id", "rt"))
+            .with_transport(fake.clone())
+            .with_token_store(store.clone())
+            .unwrap();
+        assert_eq!(store.load().unwrap().unwrap().access_token, "old");
+
+        let req = crate::api::users::get_space_usage::GetSpaceUsageRequest::default();
+        client.execute_sync(&req).expect("refreshed");
+
+        let saved = store.load().unwrap().unwrap();
+        assert_eq!(saved.access_token, "new");
+        assert_eq!(saved.refresh_token.as_deref(), Some("rt"));
+        let left = saved.expires_at.unwrap() - Utc::now();
+        assert!(left > chrono::Duration::minutes(239), "{left}");
+    }
+
+    #[test]
+    fn token_store_reaches_clones_made_before_it() {
+        use crate::tests_utils::ScriptedTransport;
+        use crate::token_store::{MemoryTokenStore, TokenStore};
+
+        let fake = ScriptedTransport::new();
+        fake.reply(
+            200,
+            &[],
+            r#"{"access_token":"new","expires_in":14400,"token_type":"bearer"}"#,
+        );
+        let client = Client::with_refresh("old", 14400, RefreshConfig::pkce("id", "rt"))
+            .with_transport(fake.clone());
+        let early_clone = client.clone();
+        let store = Arc::new(MemoryTokenStore::new());
+        let _client = client.with_token_store(store.clone()).unwrap();
+
+        early_clone.force_refresh_sync().unwrap();
+        assert_eq!(store.load().unwrap().unwrap().access_token, "new");
+    }
+
+    #[test]
+    fn token_store_resumes_a_saved_session() {
+        use crate::tests_utils::ScriptedTransport;
+        use crate::token_store::{MemoryTokenStore, StoredToken, TokenStore};
+
+        let store = Arc::new(MemoryTokenStore::new());
+        store
+            .save(&StoredToken {
+                access_token: "saved".into(),
+                expires_at: Some(Utc::now() + chrono::Duration::hours(1)),
+                refresh_token: None,
+            })
+            .unwrap();
+        let fake = ScriptedTransport::new();
+        fake.reply(
+            200,
+            &[],
+            r#"{"used":1,"allocation":{".tag":"individual","allocated":2}}"#,
+        );
+        let client = Client::with_refresh("", 0, RefreshConfig::pkce("id", "rt"))
+            .with_transport(fake.clone())
+            .with_token_store(store)
+            .unwrap();
+        assert!(!client.is_expired());
+
+        let req = crate::api::users::get_space_usage::GetSpaceUsageRequest::default();
+        client.execute_sync(&req).expect("no refresh needed");
+        assert_eq!(fake.calls(), 1);
+        assert_eq!(
+            fake.seen.lock().unwrap()[0].header("Authorization"),
+            Some("Bearer saved")
+        );
+    }
+
+    /// Answers `oauth2/token` slowly, so concurrent callers pile up behind
+    /// the refresh, and every other call with an empty success.
+    #[derive(Debug, Default)]
+    struct SlowRefresh {
+        refreshes: std::sync::atomic::AtomicUsize,
+    }
+
+    impl SlowRefresh {
+        /// Count a refresh and say how long to take over it, if `request`
+        /// is one.
+        fn delay(&self, request: &HttpRequest) -> Option<Duration> {
+            if !request.url.ends_with("/oauth2/token") {
+                return None;
+            }
+            self.refreshes
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Some(Duration::from_millis(50))
+        }
+
+        fn answer(request: &HttpRequest) -> HttpResponse {
+            if request.url.ends_with("/oauth2/token") {
+                return HttpResponse::new(
+                    200,
+                    Vec::new(),
+                    r#"{"access_token":"fresh","expires_in":14400,"token_type":"bearer"}"#,
+                );
+            }
+            assert_eq!(request.header("Authorization"), Some("Bearer fresh"));
+            HttpResponse::new(
+                200,
+                Vec::new(),
+                r#"{"used":1,"allocation":{".tag":"individual","allocated":2}}"#,
+            )
+        }
+    }
+
+    impl Transport for SlowRefresh {
+        fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+            Box::pin(async move {
+                if let Some(delay) = self.delay(&request) {
+                    tokio::time::sleep(delay).await;
+                }
+                Ok(Self::answer(&request))
+            })
+        }
+
+        fn send_sync(&self, request: HttpRequest) -> Result<HttpResponse> {
+            if let Some(delay) = self.delay(&request) {
+                std::thread::sleep(delay);
+            }
+            Ok(Self::answer(&request))
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_callers_share_one_refresh() {
+        let transport = Arc::new(SlowRefresh::default());
+        let client = Client::with_refresh("old", 0, RefreshConfig::pkce("id", "rt"))
+            .with_transport(transport.clone());
+
+        let calls: Vec<_> = (0..8)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move {
+                    let req = crate::api::users::get_space_usage::GetSpaceUsageRequest::default();
+                    client.execute(&req).await
+                })
+            })
+            .collect();
+        for call in calls {
+            call.await.unwrap().expect("call after shared refresh");
+        }
+        assert_eq!(
+            transport
+                .refreshes
+                .load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn sync_and_async_callers_share_one_refresh() {
+        let transport = Arc::new(SlowRefresh::default());
+        let client = Client::with_refresh("old", 0, RefreshConfig::pkce("id", "rt"))
+            .with_transport(transport.clone());
+
+        let mut calls = Vec::new();
+        for _ in 0..4 {
+            let async_client = client.clone();
+            calls.push(tokio::spawn(async move {
+                let req = crate::api::users::get_space_usage::GetSpaceUsageRequest::default();
+                async_client.execute(&req).await.map(drop)
+            }));
+            let client = client.clone();
+            calls.push(tokio::task::spawn_blocking(move || {
+                let req = crate::api::users::get_space_usage::GetSpaceUsageRequest::default();
+                client.execute_sync(&req).map(drop)
+            }));
+        }
+        for call in calls {
+            call.await.unwrap().expect("call after shared refresh");
+        }
+        assert_eq!(
+            transport
+                .refreshes
+                .load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+    }
+
+    #[test]
+    fn rejection_of_an_already_replaced_token_skips_the_refresh() {
+        use crate::tests_utils::ScriptedTransport;
+
+        let fake = ScriptedTransport::new();
+        fake.reply(
+            200,
+            &[],
+            r#"{"access_token":"new","expires_in":14400,"token_type":"bearer"}"#,
+        );
+        let client = Client::with_refresh("old", 14400, RefreshConfig::pkce("id", "rt"))
+            .with_transport(fake.clone());
+
+        let sent_with = client.token_generation();
+        client.force_refresh_sync().unwrap();
+        assert_eq!(fake.calls(), 1);
+
+        // A request that went out with "old" comes back 401 after the
+        // refresh above: the token to retry with is already there.
+        client.refresh_rejected_sync(sent_with).unwrap();
+        assert_eq!(fake.calls(), 1);
+        assert_eq!(client.token(), "new");
+    }
+
+    #[test]
+    fn refresh_window_renews_before_expiry() {
+        use crate::tests_utils::ScriptedTransport;
+
+        let fake = ScriptedTransport::new();
+        fake.reply(
+            200,
+            &[],
+            r#"{"access_token":"new","expires_in":14400,"token_type":"bearer"}"#,
+        )
+        .reply(
+            200,
+            &[],
+            r#"{"used":1,"allocation":{".tag":"individual","allocated":2}}"#,
+        );
+        let client = Client::with_refresh("old", 60, RefreshConfig::pkce("id", "rt"))
+            .with_transport(fake.clone())
+            .with_refresh_window(Duration::from_secs(300));
+        assert!(!client.is_expired());
+
+        let req = crate::api::users::get_space_usage::GetSpaceUsageRequest::default();
+        client.execute_sync(&req).unwrap();
+        let seen = fake.seen.lock().unwrap();
+        assert!(seen[0].url.ends_with("/oauth2/token"));
+        assert_eq!(seen[1].header("Authorization"), Some("Bearer new"));
+    }
+
     #[test]
     fn with_refresh_marks_expiry() {
         let c = Client::with_refresh(
@@ -290,7 +1491,7 @@ mod tests {
             0,
             RefreshConfig {
                 client_id: "x".into(),
-                client_secret: "y".into(),
+                client_secret: Some("y".into()),
                 refresh_token: "r".into(),
             },
         );
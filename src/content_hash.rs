@@ -0,0 +1,184 @@
+//! Dropbox's content hash, for checking file contents end to end.
+//!
+//! The hash is SHA-256 over each 4 MiB block of the file, then SHA-256 over
+//! the concatenated block digests, written as lowercase hex. It is what
+//! `FileMetadata::content_hash` reports and what the upload arguments'
+//! `content_hash` fields accept.
+//!
+//! ```no_run
+//! use rusty_dropbox_sdk::content_hash::{self, ContentHasher};
+//!
+//! # fn run() -> anyhow::Result<()> {
+//! let local = content_hash::hash_file("photo.jpg")?;
+//!
+//! let mut hasher = ContentHasher::new();
+//! hasher.update(b"first part");
+//! hasher.update(b"second part");
+//! let streamed = hasher.finish();
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The `*_verified` variants of the download and upload helpers compare
+//! this hash with Dropbox's and fail with [`ContentHashMismatch`].
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::Path;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Bytes per block of the content hash.
+pub const BLOCK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Incremental content hash; feed it data in pieces of any size.
+#[derive(Debug, Clone, Default)]
+pub struct ContentHasher {
+    overall: Sha256,
+    block: Sha256,
+    block_len: usize,
+}
+
+impl ContentHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        while !data.is_empty() {
+            let take = data.len().min(BLOCK_SIZE - self.block_len);
+            self.block.update(&data[..take]);
+            self.block_len += take;
+            data = &data[take..];
+            if self.block_len == BLOCK_SIZE {
+                self.finish_block();
+            }
+        }
+    }
+
+    /// The hash of everything fed so far, as lowercase hex.
+    pub fn finish(mut self) -> String {
+        if self.block_len > 0 {
+            self.finish_block();
+        }
+        hex(&self.overall.finalize())
+    }
+
+    fn finish_block(&mut self) {
+        let digest = std::mem::take(&mut self.block).finalize();
+        self.overall.update(digest);
+        self.block_len = 0;
+    }
+}
+
+/// The content hash of `data`.
+pub fn content_hash(data: &[u8]) -> String {
+    let mut hasher = ContentHasher::new();
+    hasher.update(data);
+    hasher.finish()
+}
+
+/// The content hash of a local file, read in blocks.
+pub fn hash_file(path: impl AsRef<Path>) -> Result<String> {
+    let path = path.as_ref();
+    let file = std::fs::File::open(path).with_context(|| format!("open {}", path.display()))?;
+    hash_reader_sync(file).with_context(|| format!("read {}", path.display()))
+}
+
+/// The content hash of everything `reader` yields until EOF.
+pub async fn hash_reader<R: AsyncRead + Unpin>(mut reader: R) -> Result<String> {
+    let mut hasher = ContentHasher::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            return Ok(hasher.finish());
+        }
+        hasher.update(&buf[..n]);
+    }
+}
+
+/// Blocking variant of [`hash_reader`].
+pub fn hash_reader_sync<R: Read>(mut reader: R) -> Result<String> {
+    let mut hasher = ContentHasher::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            return Ok(hasher.finish());
+        }
+        hasher.update(&buf[..n]);
+    }
+}
+
+/// The bytes sent or received don't hash to what Dropbox has for the file.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("content hash mismatch for {path}: Dropbox has {expected}, local data hashes to {actual}")]
+pub struct ContentHashMismatch {
+    pub path: String,
+    /// The hash Dropbox reported.
+    pub expected: String,
+    /// The hash of the bytes on this side.
+    pub actual: String,
+}
+
+/// Compare Dropbox's hash for `path` with the local one.
+pub(crate) fn verify(path: &str, expected: Option<&str>, actual: String) -> Result<()> {
+    let expected =
+        expected.with_context(|| format!("Dropbox returned no content_hash for {path}"))?;
+    if expected != actual {
+        return Err(ContentHashMismatch {
+            path: path.to_string(),
+            expected: expected.to_string(),
+            actual,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_input_hashes_no_blocks() {
+        assert_eq!(
+            content_hash(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn hashes_the_block_digests_regardless_of_how_data_is_fed() {
+        let data: Vec<u8> = (0..BLOCK_SIZE + 10).map(|i| (i % 251) as u8).collect();
+        let mut overall = Sha256::new();
+        overall.update(Sha256::digest(&data[..BLOCK_SIZE]));
+        overall.update(Sha256::digest(&data[BLOCK_SIZE..]));
+        let expected = hex(&overall.finalize());
+
+        assert_eq!(content_hash(&data), expected);
+        let mut hasher = ContentHasher::new();
+        for piece in data.chunks(999_983) {
+            hasher.update(piece);
+        }
+        assert_eq!(hasher.finish(), expected);
+        assert_eq!(hash_reader_sync(&data[..]).unwrap(), expected);
+    }
+
+    #[test]
+    fn a_mismatch_is_a_typed_error() {
+        let err = verify("/f", Some("aa"), "bb".into()).unwrap_err();
+        let mismatch = err.downcast_ref::<ContentHashMismatch>().unwrap();
+        assert_eq!(
+            (mismatch.expected.as_str(), mismatch.actual.as_str()),
+            ("aa", "bb")
+        );
+        assert!(verify("/f", None, "bb".into()).is_err());
+        assert!(verify("/f", Some("bb"), "bb".into()).is_ok());
+    }
+}
@@ -0,0 +1,493 @@
+//! Shared request execution behind `implement_service!` and
+//! `implement_download_service!`.
+//!
+//! The macros only know how to turn a `*Request` into a [`PreparedRequest`]
+//! and how to map the decoded payload back into the endpoint's `*Response`
+//! struct. Everything in between — token lookup, proactive refresh, the
+//! [`RetryPolicy`](crate::RetryPolicy) loop and the 401
+//! force-refresh-and-replay — lives here so
+//! it behaves identically for every endpoint, sync and async.
+
+use anyhow::Result;
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+use std::time::{Duration, Instant};
+
+use crate::endpoints::{get_endpoint_url, headers::Headers, Endpoint};
+use crate::errors::{ApiError, DropboxError};
+use crate::middleware::Vetoed;
+use crate::models::common::PathRootError;
+use crate::retry::is_retryable_status;
+use crate::telemetry;
+use crate::traits::Utils;
+use crate::transport::{find_header, HttpRequest, HttpResponse};
+use crate::Client;
+
+/// Owned, token-free description of one API call. Built synchronously by the
+/// service macros (so the test URL override is read on the caller's thread)
+/// and then replayed as many times as retries and refreshes require.
+#[derive(Debug, Clone)]
+pub(crate) struct PreparedRequest {
+    pub endpoint: Endpoint,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Bytes>,
+    /// Result metadata travels in the `Dropbox-API-Result` response header
+    /// rather than the body (download-class endpoints).
+    pub result_in_header: bool,
+}
+
+impl PreparedRequest {
+    pub fn new<'a, U: Utils<'a>>(
+        request: &U,
+        endpoint: Endpoint,
+        headers: Vec<Headers>,
+    ) -> Result<Self> {
+        let (live, sync_test, async_test) = get_endpoint_url(endpoint);
+        let url = sync_test.or(async_test).unwrap_or(live);
+
+        let is_content_endpoint = headers
+            .iter()
+            .any(|h| matches!(h, Headers::ContentTypeAppOctetStream));
+        let result_in_header = headers
+            .iter()
+            .any(|h| matches!(h, Headers::DropboxApiResult));
+
+        let mut out = Vec::with_capacity(headers.len());
+        for h in &headers {
+            match h {
+                Headers::DropboxApiArg(_) => {
+                    let arg = serde_json::to_string(&request.payload())
+                        .map_err(|err| ApiError::Parsing(err.into()))?;
+                    out.push(("Dropbox-API-Arg".to_string(), arg));
+                }
+                // Response-side marker, never sent.
+                Headers::DropboxApiResult => {}
+                // The bearer comes from the executing `Client`.
+                Headers::TestAuthorization => {}
+                _ => {
+                    let (name, value) = h.get_str();
+                    out.push((name.to_string(), value.to_string()));
+                }
+            }
+        }
+
+        let body = if !is_content_endpoint {
+            match request.payload() {
+                Some(payload) => {
+                    if !headers
+                        .iter()
+                        .any(|h| matches!(h, Headers::ContentTypeAppJson))
+                    {
+                        let (name, value) = Headers::ContentTypeAppJson.get_str();
+                        out.push((name.to_string(), value.to_string()));
+                    }
+                    Some(Bytes::from(
+                        serde_json::to_vec(payload).map_err(|err| ApiError::Parsing(err.into()))?,
+                    ))
+                }
+                None => None,
+            }
+        } else {
+            request.content_body().map(Bytes::copy_from_slice)
+        };
+
+        Ok(Self {
+            endpoint,
+            url,
+            headers: out,
+            body,
+            result_in_header,
+        })
+    }
+}
+
+impl PreparedRequest {
+    /// The wire request for one attempt: `client`'s current token and
+    /// namespace headers, then the endpoint's own.
+    fn to_http(&self, client: &Client) -> HttpRequest {
+        let mut headers = Vec::with_capacity(self.headers.len() + 2);
+        if self.endpoint.needs_auth() {
+            headers.push((
+                "Authorization".to_string(),
+                format!("Bearer {}", client.token()),
+            ));
+        }
+        if self.endpoint.is_user_route() {
+            headers.extend(client.context_headers());
+        }
+        headers.extend(self.headers.iter().cloned());
+        HttpRequest {
+            url: self.url.clone(),
+            headers,
+            body: self.body.clone(),
+        }
+    }
+}
+
+impl HttpResponse {
+    /// Turn non-2xx statuses into errors; see [`HttpResponse::api_error`].
+    pub(crate) fn error_for_status<E: ErrorPayload>(&self, endpoint: Endpoint) -> Result<()> {
+        if self.is_success() {
+            return Ok(());
+        }
+        Err(self.api_error::<E>(endpoint))
+    }
+
+    /// The error for a non-2xx response from `endpoint`. The chain carries,
+    /// from the top: an `ApiError` (401 gets its own variant so `Client` can
+    /// tell an expired token from any other failure), the structured
+    /// `DropboxError`, and the endpoint's union decoded as `TypedError<E>`
+    /// (`TypedError<PathRootError>` for a rejected path root).
+    /// Callers can `downcast_ref` to any of them.
+    pub(crate) fn api_error<E: ErrorPayload>(&self, endpoint: Endpoint) -> anyhow::Error {
+        let text = String::from_utf8_lossy(&self.body);
+        let details = DropboxError::from_response(
+            self.status,
+            endpoint.route(),
+            self.header("X-Dropbox-Request-Id").map(str::to_string),
+            retry_after(&self.headers),
+            &text,
+        );
+        let summary = anyhow::anyhow!("{}", details);
+        let err = if self.status == 422 {
+            crate::errors::endpoint_error::<PathRootError>(details, &text)
+        } else {
+            crate::errors::endpoint_error::<E>(details, &text)
+        };
+        if self.status == 401 {
+            return err.context(ApiError::Unauthorized(summary));
+        }
+        err.context(ApiError::DropBox(summary))
+    }
+
+    /// Decode an RPC-style result from the body, or from the
+    /// `Dropbox-API-Result` header for download-class endpoints. An empty
+    /// source maps to `Ok(None)`.
+    pub(crate) fn into_payload<P: DeserializeOwned, E: ErrorPayload>(
+        self,
+        request: &PreparedRequest,
+    ) -> Result<Option<P>> {
+        self.error_for_status::<E>(request.endpoint)?;
+        let source = if request.result_in_header {
+            self.header("Dropbox-API-Result")
+                .unwrap_or_default()
+                .to_string()
+        } else {
+            String::from_utf8(self.body.to_vec()).map_err(|err| ApiError::Parsing(err.into()))?
+        };
+        if source.is_empty() {
+            return Ok(None);
+        }
+        let payload = serde_json::from_str(&source).map_err(|err| ApiError::Parsing(err.into()))?;
+        Ok(Some(payload))
+    }
+
+    /// Decode a download-style result: metadata from the header, raw bytes
+    /// from the body.
+    pub(crate) fn into_download<P: DeserializeOwned, E: ErrorPayload>(
+        self,
+        request: &PreparedRequest,
+    ) -> Result<Option<(P, Vec<u8>)>> {
+        self.error_for_status::<E>(request.endpoint)?;
+        let header_json = self.header("Dropbox-API-Result").unwrap_or_default();
+        if header_json.is_empty() {
+            return Ok(None);
+        }
+        let payload =
+            serde_json::from_str(header_json).map_err(|err| ApiError::Parsing(err.into()))?;
+        Ok(Some((payload, self.body.to_vec())))
+    }
+}
+
+/// Bounds for the per-endpoint error union an `ApiError` is decoded into.
+pub(crate) trait ErrorPayload:
+    DeserializeOwned + std::fmt::Debug + Send + Sync + 'static
+{
+}
+
+impl<T: DeserializeOwned + std::fmt::Debug + Send + Sync + 'static> ErrorPayload for T {}
+
+/// Seconds from a `Retry-After` header, if present and numeric.
+pub(crate) fn retry_after(headers: &[(String, String)]) -> Option<Duration> {
+    find_header(headers, "Retry-After")
+        .and_then(|s| s.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+/// What to do after one attempt: hand back the outcome, or wait and retry.
+fn next_step(
+    client: &Client,
+    request: &PreparedRequest,
+    attempt: u32,
+    started: Instant,
+    outcome: &Result<HttpResponse>,
+) -> Option<Duration> {
+    let policy = client.retry_policy();
+    let hint = match outcome {
+        Ok(r) if is_retryable_status(r.status) => retry_after(&r.headers),
+        Ok(_) => return None,
+        Err(err) if err.is::<Vetoed>() => return None,
+        Err(_) if policy.retry_network_errors && request.endpoint.is_idempotent() => None,
+        Err(_) => return None,
+    };
+    policy.next_delay(attempt, started.elapsed(), hint)
+}
+
+/// Wrap a failure that never produced a response. Middleware vetoes pass
+/// through untouched so callers can still `downcast_ref::<Vetoed>()`.
+fn transport_error(err: anyhow::Error) -> anyhow::Error {
+    if err.is::<Vetoed>() {
+        return err;
+    }
+    ApiError::Request(err).into()
+}
+
+fn send_with_retry_sync(client: &Client, request: &PreparedRequest) -> Result<HttpResponse> {
+    let started = Instant::now();
+    let mut attempt = 1u32;
+    loop {
+        let attempt_started = Instant::now();
+        let outcome = client.send_http_sync(request.endpoint, request.to_http(client));
+        telemetry::attempt(attempt, attempt_started, &outcome);
+        match next_step(client, request, attempt, started, &outcome) {
+            Some(wait) => {
+                telemetry::retrying(attempt, wait, &outcome);
+                std::thread::sleep(wait)
+            }
+            None => return outcome.map_err(transport_error),
+        }
+        attempt += 1;
+    }
+}
+
+async fn send_with_retry(client: &Client, request: &PreparedRequest) -> Result<HttpResponse> {
+    let started = Instant::now();
+    let mut attempt = 1u32;
+    loop {
+        let attempt_started = Instant::now();
+        let outcome = client
+            .send_http(request.endpoint, request.to_http(client))
+            .await;
+        telemetry::attempt(attempt, attempt_started, &outcome);
+        match next_step(client, request, attempt, started, &outcome) {
+            Some(wait) => {
+                telemetry::retrying(attempt, wait, &outcome);
+                tokio::time::sleep(wait).await
+            }
+            None => return outcome.map_err(transport_error),
+        }
+        attempt += 1;
+    }
+}
+
+/// Execute `request` with `client`'s token: refresh first if the token is
+/// past its expiry, and on a 401 force one refresh and replay the call.
+pub(crate) fn send_sync(client: &Client, request: &PreparedRequest) -> Result<HttpResponse> {
+    let span = telemetry::call_span(client, request);
+    let _entered = span.enter();
+    let started = Instant::now();
+    let outcome = send_with_refresh_sync(client, request);
+    span.finish(started, &outcome);
+    outcome
+}
+
+/// Async variant of [`send_sync`].
+pub(crate) async fn send(client: &Client, request: &PreparedRequest) -> Result<HttpResponse> {
+    let span = telemetry::call_span(client, request);
+    let started = Instant::now();
+    let outcome = span.instrument(send_with_refresh(client, request)).await;
+    span.finish(started, &outcome);
+    outcome
+}
+
+fn send_with_refresh_sync(client: &Client, request: &PreparedRequest) -> Result<HttpResponse> {
+    if !request.endpoint.needs_auth() {
+        return send_with_retry_sync(client, request);
+    }
+    client.ensure_fresh_sync()?;
+    let generation = client.token_generation();
+    let response = send_with_retry_sync(client, request)?;
+    if response.status == 401 && client.can_refresh() {
+        // Only refresh if nobody else has since this token went out.
+        client.refresh_rejected_sync(generation)?;
+        return send_with_retry_sync(client, request);
+    }
+    Ok(response)
+}
+
+async fn send_with_refresh(client: &Client, request: &PreparedRequest) -> Result<HttpResponse> {
+    if !request.endpoint.needs_auth() {
+        return send_with_retry(client, request).await;
+    }
+    client.ensure_fresh().await?;
+    let generation = client.token_generation();
+    let response = send_with_retry(client, request).await?;
+    if response.status == 401 && client.can_refresh() {
+        // Only refresh if nobody else has since this token went out.
+        client.refresh_rejected(generation).await?;
+        return send_with_retry(client, request).await;
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api;
+    use crate::tests_utils::ScriptedTransport;
+    use crate::{Client, RetryPolicy};
+    use std::time::Duration;
+
+    const RAW_SPACE_USAGE: &str = r#"{"used":1,"allocation":{".tag":"individual","allocated":2}}"#;
+
+    fn fast_retries() -> RetryPolicy {
+        RetryPolicy {
+            initial_backoff: Duration::from_millis(1),
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_network_errors_on_idempotent_endpoints() {
+        let fake = ScriptedTransport::new();
+        fake.fail("connection reset")
+            .reply(200, &[], RAW_SPACE_USAGE);
+        let client = Client::new("t")
+            .with_transport(fake.clone())
+            .with_retry_policy(fast_retries());
+
+        let req = api::users::get_space_usage::GetSpaceUsageRequest::default();
+        assert!(client.execute(&req).await.expect("retried").is_some());
+        assert_eq!(fake.calls(), 2);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_network_errors_on_writes() {
+        let fake = ScriptedTransport::new();
+        fake.fail("connection reset");
+        let client = Client::new("t")
+            .with_transport(fake.clone())
+            .with_retry_policy(fast_retries());
+
+        let req = api::files::delete::DeleteRequest {
+            payload: Some(api::files::DeleteArgs {
+                path: "/x".into(),
+                parent_rev: None,
+            }),
+            ..Default::default()
+        };
+        let err = client.execute(&req).await.expect_err("not retried");
+        assert!(matches!(
+            err.downcast_ref::<crate::errors::ApiError>(),
+            Some(crate::errors::ApiError::Request(_))
+        ));
+        assert_eq!(fake.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn retries_uploads_with_the_same_body() {
+        let fake = ScriptedTransport::new();
+        fake.reply(503, &[], "")
+            .reply(200, &[], r#"{"session_id":"s1"}"#);
+        let client = Client::new("t")
+            .with_transport(fake.clone())
+            .with_retry_policy(fast_retries());
+
+        let req = api::files::upload_session_start::UploadSessionStartRequest {
+            payload: Some(api::files::UploadSessionStartArg {
+                close: None,
+                session_type: None,
+                content_hash: None,
+            }),
+            data: Some(b"chunk".to_vec()),
+            ..Default::default()
+        };
+        client.execute(&req).await.expect("retried upload");
+        let seen = fake.seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[1].body.as_deref(), Some(&b"chunk"[..]));
+    }
+
+    #[test]
+    fn retry_policy_none_returns_first_failure() {
+        let fake = ScriptedTransport::new();
+        fake.reply(429, &[("Retry-After", "1")], "");
+        let client = Client::new("t")
+            .with_transport(fake.clone())
+            .with_retry_policy(RetryPolicy::none());
+
+        let req = api::users::get_space_usage::GetSpaceUsageRequest::default();
+        assert!(client.execute_sync(&req).is_err());
+        assert_eq!(fake.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn endpoint_errors_downcast_to_their_union() {
+        use crate::api::files::{DeleteError, LookupError};
+        use crate::errors::{ApiError, DropboxError, TypedError};
+
+        let fake = ScriptedTransport::new();
+        fake.reply(
+            409,
+            &[("X-Dropbox-Request-Id", "req-42")],
+            r#"{"error_summary": "path_lookup/not_found/..", "error": {".tag": "path_lookup", "path_lookup": {".tag": "not_found"}}}"#,
+        );
+        let client = Client::new("t").with_transport(fake.clone());
+
+        let req = api::files::delete::DeleteRequest {
+            payload: Some(api::files::DeleteArgs {
+                path: "/gone".into(),
+                parent_rev: None,
+            }),
+            ..Default::default()
+        };
+        let err = client.execute(&req).await.expect_err("409");
+        let typed = err
+            .downcast_ref::<TypedError<DeleteError>>()
+            .expect("DeleteError attached");
+        assert!(matches!(
+            typed.get(),
+            DeleteError::PathLookup {
+                path_lookup: LookupError::NotFound
+            }
+        ));
+
+        let details = err.downcast_ref::<DropboxError>().expect("details");
+        assert_eq!(details.status, 409);
+        assert_eq!(details.endpoint, "files/delete_v2");
+        assert_eq!(details.request_id.as_deref(), Some("req-42"));
+        assert_eq!(
+            details.error_summary.as_deref(),
+            Some("path_lookup/not_found/..")
+        );
+        assert!(matches!(
+            err.downcast_ref::<ApiError>(),
+            Some(ApiError::DropBox(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn a_429_pauses_every_request_sharing_the_limiter() {
+        use crate::RateLimiter;
+        use std::sync::Arc;
+        use std::time::Instant;
+
+        let fake = ScriptedTransport::new();
+        fake.reply(429, &[], "").reply(200, &[], RAW_SPACE_USAGE);
+        let client = Client::new("t")
+            .with_transport(fake.clone())
+            .with_rate_limiter(Arc::new(RateLimiter::new()))
+            .with_retry_policy(RetryPolicy {
+                initial_backoff: Duration::from_millis(300),
+                ..RetryPolicy::none()
+            });
+        let sibling = client.clone();
+
+        let req = api::users::get_space_usage::GetSpaceUsageRequest::default();
+        assert!(client.execute(&req).await.is_err());
+
+        let started = Instant::now();
+        sibling.execute(&req).await.expect("after pause");
+        assert!(started.elapsed() >= Duration::from_millis(250));
+    }
+}
@@ -2,7 +2,8 @@ pub mod headers;
 
 /// Enum representing api available endpoints
 /// It is passed to fhe function
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 #[allow(dead_code)]
 // Every variant ends in `Post` because every Dropbox v2 endpoint is POST;
 // the suffix is intentional and kept for forward-compat if any GET / WS
@@ -124,6 +125,40 @@ pub enum Endpoint {
     SharingUpdateFileMemberPost,
     SharingUpdateFolderMemberPost,
     SharingUpdateFolderPolicyPost,
+    TeamGetInfoPost,
+    TeamGroupsCreatePost,
+    TeamGroupsDeletePost,
+    TeamGroupsGetInfoPost,
+    TeamGroupsJobStatusGetPost,
+    TeamGroupsListPost,
+    TeamGroupsListContinuePost,
+    TeamGroupsMembersAddPost,
+    TeamGroupsMembersListPost,
+    TeamGroupsMembersListContinuePost,
+    TeamGroupsMembersRemovePost,
+    TeamGroupsMembersSetAccessTypePost,
+    TeamLogGetEventsPost,
+    TeamLogGetEventsContinuePost,
+    TeamMembersAddPost,
+    TeamMembersAddJobStatusGetPost,
+    TeamMembersGetInfoPost,
+    TeamMembersListPost,
+    TeamMembersListContinuePost,
+    TeamMembersRemovePost,
+    TeamMembersRemoveJobStatusGetPost,
+    TeamMembersSetProfilePost,
+    TeamMembersSuspendPost,
+    TeamMembersUnsuspendPost,
+    TeamNamespacesListPost,
+    TeamNamespacesListContinuePost,
+    TeamTeamFolderArchivePost,
+    TeamTeamFolderArchiveCheckPost,
+    TeamTeamFolderCreatePost,
+    TeamTeamFolderListPost,
+    TeamTeamFolderListContinuePost,
+    TeamTeamFolderPermanentlyDeletePost,
+    TeamTeamFolderRenamePost,
+    TeamTeamFolderUpdateSyncSettingsPost,
     UsersFeaturesGetValuesPost,
     UsersGetAccountPost,
     UsersGetAccountBatchPost,
@@ -132,6 +167,234 @@ pub enum Endpoint {
     OAuth2TokenPost,
 }
 
+impl Endpoint {
+    /// Route name as Dropbox documents it, e.g. `files/list_folder`.
+    pub fn route(&self) -> String {
+        let (live, _, _) = get_endpoint_url(*self);
+        let path = live.split_once(".com/").map_or(live.as_str(), |(_, p)| p);
+        path.strip_prefix("2/").unwrap_or(path).to_string()
+    }
+
+    /// False for the `noauth` routes, which reject an `Authorization` header.
+    pub fn needs_auth(&self) -> bool {
+        !matches!(self, Endpoint::FilesListFolderLongpollPost)
+    }
+
+    /// True for routes that run as a user, which are the only ones that
+    /// honour `Dropbox-API-Select-User`, `Dropbox-API-Select-Admin` and
+    /// `Dropbox-API-Path-Root`. `team/*`, `team_log/*`, app-auth and
+    /// `noauth` routes reject or ignore them.
+    pub fn is_user_route(&self) -> bool {
+        matches!(
+            self,
+            Endpoint::AccountSetProfilePhotoPost
+                | Endpoint::CheckUserPost
+                | Endpoint::ContactsDeleteManualContactsBatchPost
+                | Endpoint::ContactsDeleteManualContactsPost
+                | Endpoint::FilePropertiesPropertiesAddPost
+                | Endpoint::FilePropertiesPropertiesOverwritePost
+                | Endpoint::FilePropertiesPropertiesRemovePost
+                | Endpoint::FilePropertiesPropertiesSearchContinuePost
+                | Endpoint::FilePropertiesPropertiesSearchPost
+                | Endpoint::FilePropertiesPropertiesUpdatePost
+                | Endpoint::FilePropertiesTemplatesAddForUserPost
+                | Endpoint::FilePropertiesTemplatesGetForUserPost
+                | Endpoint::FilePropertiesTemplatesListForUserPost
+                | Endpoint::FilePropertiesTemplatesRemoveForUserPost
+                | Endpoint::FilePropertiesTemplatesUpdateForUserPost
+                | Endpoint::FileRequestsCountPost
+                | Endpoint::FileRequestsCreatePost
+                | Endpoint::FileRequestsDeleteAllClosedPost
+                | Endpoint::FileRequestsDeletePost
+                | Endpoint::FileRequestsGetPost
+                | Endpoint::FileRequestsListContinuePost
+                | Endpoint::FileRequestsListPost
+                | Endpoint::FileRequestsUpdatePost
+                | Endpoint::FilesCopyBatchCheckPost
+                | Endpoint::FilesCopyBatchPost
+                | Endpoint::FilesCopyPost
+                | Endpoint::FilesCopyReferenceGetPost
+                | Endpoint::FilesCopyReferenceSavePost
+                | Endpoint::FilesCreateFolderBatchCheckPost
+                | Endpoint::FilesCreateFolderBatchPost
+                | Endpoint::FilesCreateFolderPost
+                | Endpoint::FilesDeleteBatchCheckPost
+                | Endpoint::FilesDeleteBatchPost
+                | Endpoint::FilesDeletePost
+                | Endpoint::FilesDownloadPost
+                | Endpoint::FilesDownloadZipPost
+                | Endpoint::FilesExportPost
+                | Endpoint::FilesGetFileLockBatchPost
+                | Endpoint::FilesGetMetadataPost
+                | Endpoint::FilesGetPreviewPost
+                | Endpoint::FilesGetTemporaryLinkPost
+                | Endpoint::FilesGetTemporaryUploadLinkPost
+                | Endpoint::FilesGetThumbnailBatchPost
+                | Endpoint::FilesGetThumbnailPost
+                | Endpoint::FilesListFolderContinuePost
+                | Endpoint::FilesListFolderGetLatestCursorPost
+                | Endpoint::FilesListFolderPost
+                | Endpoint::FilesListRevisionsPost
+                | Endpoint::FilesLockFileBatchPost
+                | Endpoint::FilesMoveBatchCheckPost
+                | Endpoint::FilesMoveBatchPost
+                | Endpoint::FilesMovePost
+                | Endpoint::FilesPaperCreatePost
+                | Endpoint::FilesPaperUpdatePost
+                | Endpoint::FilesPermanentlyDeletePost
+                | Endpoint::FilesRestorePost
+                | Endpoint::FilesSaveUrlCheckJobStatusPost
+                | Endpoint::FilesSaveUrlPost
+                | Endpoint::FilesSearchContinuePost
+                | Endpoint::FilesSearchPost
+                | Endpoint::FilesTagsAddPost
+                | Endpoint::FilesTagsGetPost
+                | Endpoint::FilesTagsRemovePost
+                | Endpoint::FilesUnlockFileBatchPost
+                | Endpoint::FilesUploadPost
+                | Endpoint::FilesUploadSessionAppendBatchPost
+                | Endpoint::FilesUploadSessionAppendPost
+                | Endpoint::FilesUploadSessionFinishBatchCheckPost
+                | Endpoint::FilesUploadSessionFinishBatchPost
+                | Endpoint::FilesUploadSessionFinishPost
+                | Endpoint::FilesUploadSessionStartBatchPost
+                | Endpoint::FilesUploadSessionStartPost
+                | Endpoint::SharingAddFileMemberPost
+                | Endpoint::SharingAddFolderMemberPost
+                | Endpoint::SharingCheckJobStatusPost
+                | Endpoint::SharingCheckRemoveMemberJobStatusPost
+                | Endpoint::SharingCheckShareJobStatusPost
+                | Endpoint::SharingCreateSharedLinkWithSettingsPost
+                | Endpoint::SharingGetFileMetadataBatchPost
+                | Endpoint::SharingGetFileMetadataPost
+                | Endpoint::SharingGetFolderMetadataPost
+                | Endpoint::SharingGetSharedLinkFilePost
+                | Endpoint::SharingGetSharedLinkMetadataPost
+                | Endpoint::SharingListFileMembersBatchPost
+                | Endpoint::SharingListFileMembersContinuePost
+                | Endpoint::SharingListFileMembersPost
+                | Endpoint::SharingListFolderMembersContinuePost
+                | Endpoint::SharingListFolderMembersPost
+                | Endpoint::SharingListFoldersContinuePost
+                | Endpoint::SharingListFoldersPost
+                | Endpoint::SharingListMountableFoldersContinuePost
+                | Endpoint::SharingListMountableFoldersPost
+                | Endpoint::SharingListReceivedFilesContinuePost
+                | Endpoint::SharingListReceivedFilesPost
+                | Endpoint::SharingListSharedLinksPost
+                | Endpoint::SharingModifySharedLinksSettingsPost
+                | Endpoint::SharingMountFolderPost
+                | Endpoint::SharingRelinquishFileMembershipPost
+                | Endpoint::SharingRelinquishFolderMembershipPost
+                | Endpoint::SharingRemoveFileMember2Post
+                | Endpoint::SharingRemoveFolderMemberPost
+                | Endpoint::SharingRevokeSharedLinkPost
+                | Endpoint::SharingSetAccessInheritancePost
+                | Endpoint::SharingShareFolderPost
+                | Endpoint::SharingTransferFolderPost
+                | Endpoint::SharingUnmountFolderPost
+                | Endpoint::SharingUnshareFilePost
+                | Endpoint::SharingUnshareFolderPost
+                | Endpoint::SharingUpdateFileMemberPost
+                | Endpoint::SharingUpdateFolderMemberPost
+                | Endpoint::SharingUpdateFolderPolicyPost
+                | Endpoint::UsersFeaturesGetValuesPost
+                | Endpoint::UsersGetAccountBatchPost
+                | Endpoint::UsersGetAccountPost
+                | Endpoint::UsersGetCurrentAccountPost
+                | Endpoint::UsersGetSpaceUsagePost
+        )
+    }
+
+    /// True for read-only endpoints, where replaying a request whose outcome
+    /// is unknown (connection reset, timeout) cannot change server state.
+    /// The retry loop only retries transport errors for these.
+    pub fn is_idempotent(&self) -> bool {
+        matches!(
+            self,
+            Endpoint::CheckAppPost
+                | Endpoint::CheckUserPost
+                | Endpoint::FileRequestsCountPost
+                | Endpoint::FileRequestsGetPost
+                | Endpoint::FileRequestsListPost
+                | Endpoint::FileRequestsListContinuePost
+                | Endpoint::FilesCopyBatchCheckPost
+                | Endpoint::FilesCopyReferenceGetPost
+                | Endpoint::FilesCreateFolderBatchCheckPost
+                | Endpoint::FilesDeleteBatchCheckPost
+                | Endpoint::FilesDownloadPost
+                | Endpoint::FilesDownloadZipPost
+                | Endpoint::FilesExportPost
+                | Endpoint::FilesGetFileLockBatchPost
+                | Endpoint::FilesGetMetadataPost
+                | Endpoint::FilesGetPreviewPost
+                | Endpoint::FilesGetTemporaryLinkPost
+                | Endpoint::FilesGetThumbnailPost
+                | Endpoint::FilesGetThumbnailBatchPost
+                | Endpoint::FilesListFolderPost
+                | Endpoint::FilesListFolderContinuePost
+                | Endpoint::FilesListFolderGetLatestCursorPost
+                | Endpoint::FilesListFolderLongpollPost
+                | Endpoint::FilesListRevisionsPost
+                | Endpoint::FilesMoveBatchCheckPost
+                | Endpoint::FilesSaveUrlCheckJobStatusPost
+                | Endpoint::FilesSearchPost
+                | Endpoint::FilesSearchContinuePost
+                | Endpoint::FilesTagsGetPost
+                | Endpoint::FilesUploadSessionFinishBatchCheckPost
+                | Endpoint::FilePropertiesPropertiesSearchPost
+                | Endpoint::FilePropertiesPropertiesSearchContinuePost
+                | Endpoint::FilePropertiesTemplatesGetForUserPost
+                | Endpoint::FilePropertiesTemplatesListForUserPost
+                | Endpoint::OpenidUserInfoPost
+                | Endpoint::SharingCheckJobStatusPost
+                | Endpoint::SharingCheckRemoveMemberJobStatusPost
+                | Endpoint::SharingCheckShareJobStatusPost
+                | Endpoint::SharingGetFileMetadataPost
+                | Endpoint::SharingGetFileMetadataBatchPost
+                | Endpoint::SharingGetFolderMetadataPost
+                | Endpoint::SharingGetSharedLinkFilePost
+                | Endpoint::SharingGetSharedLinkMetadataPost
+                | Endpoint::SharingListFileMembersPost
+                | Endpoint::SharingListFileMembersBatchPost
+                | Endpoint::SharingListFileMembersContinuePost
+                | Endpoint::SharingListFolderMembersPost
+                | Endpoint::SharingListFolderMembersContinuePost
+                | Endpoint::SharingListFoldersPost
+                | Endpoint::SharingListFoldersContinuePost
+                | Endpoint::SharingListMountableFoldersPost
+                | Endpoint::SharingListMountableFoldersContinuePost
+                | Endpoint::SharingListReceivedFilesPost
+                | Endpoint::SharingListReceivedFilesContinuePost
+                | Endpoint::SharingListSharedLinksPost
+                | Endpoint::TeamGetInfoPost
+                | Endpoint::TeamGroupsGetInfoPost
+                | Endpoint::TeamGroupsJobStatusGetPost
+                | Endpoint::TeamGroupsListPost
+                | Endpoint::TeamGroupsListContinuePost
+                | Endpoint::TeamGroupsMembersListPost
+                | Endpoint::TeamGroupsMembersListContinuePost
+                | Endpoint::TeamLogGetEventsPost
+                | Endpoint::TeamLogGetEventsContinuePost
+                | Endpoint::TeamMembersAddJobStatusGetPost
+                | Endpoint::TeamMembersGetInfoPost
+                | Endpoint::TeamMembersListPost
+                | Endpoint::TeamMembersListContinuePost
+                | Endpoint::TeamMembersRemoveJobStatusGetPost
+                | Endpoint::TeamNamespacesListPost
+                | Endpoint::TeamNamespacesListContinuePost
+                | Endpoint::TeamTeamFolderArchiveCheckPost
+                | Endpoint::TeamTeamFolderListPost
+                | Endpoint::TeamTeamFolderListContinuePost
+                | Endpoint::UsersFeaturesGetValuesPost
+                | Endpoint::UsersGetAccountPost
+                | Endpoint::UsersGetAccountBatchPost
+                | Endpoint::UsersGetCurrentAccountPost
+                | Endpoint::UsersGetSpaceUsagePost
+        )
+    }
+}
+
 #[allow(unused_variables)]
 pub fn get_endpoint_url(endpoint: Endpoint) -> (String, Option<String>, Option<String>) {
     let url = match endpoint {
@@ -152,8 +415,10 @@ pub fn get_endpoint_url(endpoint: Endpoint) -> (String, Option<String>, Option<S
         Endpoint::FilesUploadPost => "https://content.dropboxapi.com/2/files/upload",
 
         Endpoint::FilesTagsGetPost => "https://api.dropboxapi.com/2/get",
-        Endpoint::FileRequestsListContinuePost => "https://api.dropboxapi.com/2/list/continue",
-        Endpoint::FileRequestsListPost => "https://api.dropboxapi.com/2/list",
+        Endpoint::FileRequestsListContinuePost => {
+            "https://api.dropboxapi.com/2/file_requests/list/continue"
+        }
+        Endpoint::FileRequestsListPost => "https://api.dropboxapi.com/2/file_requests/list_v2",
         Endpoint::FilePropertiesPropertiesAddPost => {
             "https://api.dropboxapi.com/2/file_properties/properties/add"
         }
@@ -193,6 +458,80 @@ pub fn get_endpoint_url(endpoint: Endpoint) -> (String, Option<String>, Option<S
         Endpoint::AuthTokenRevokePost => "https://api.dropboxapi.com/2/auth/token/revoke",
         Endpoint::FileRequestsUpdatePost => "https://api.dropboxapi.com/2/update",
         Endpoint::CheckUserPost => "https://api.dropboxapi.com/2/check/user",
+        Endpoint::TeamGetInfoPost => "https://api.dropboxapi.com/2/team/get_info",
+        Endpoint::TeamGroupsCreatePost => "https://api.dropboxapi.com/2/team/groups/create",
+        Endpoint::TeamGroupsDeletePost => "https://api.dropboxapi.com/2/team/groups/delete",
+        Endpoint::TeamGroupsGetInfoPost => "https://api.dropboxapi.com/2/team/groups/get_info",
+        Endpoint::TeamGroupsJobStatusGetPost => {
+            "https://api.dropboxapi.com/2/team/groups/job_status/get"
+        }
+        Endpoint::TeamGroupsListPost => "https://api.dropboxapi.com/2/team/groups/list",
+        Endpoint::TeamGroupsListContinuePost => {
+            "https://api.dropboxapi.com/2/team/groups/list/continue"
+        }
+        Endpoint::TeamGroupsMembersAddPost => {
+            "https://api.dropboxapi.com/2/team/groups/members/add"
+        }
+        Endpoint::TeamGroupsMembersListPost => {
+            "https://api.dropboxapi.com/2/team/groups/members/list"
+        }
+        Endpoint::TeamGroupsMembersListContinuePost => {
+            "https://api.dropboxapi.com/2/team/groups/members/list/continue"
+        }
+        Endpoint::TeamGroupsMembersRemovePost => {
+            "https://api.dropboxapi.com/2/team/groups/members/remove"
+        }
+        Endpoint::TeamGroupsMembersSetAccessTypePost => {
+            "https://api.dropboxapi.com/2/team/groups/members/set_access_type"
+        }
+        Endpoint::TeamLogGetEventsPost => "https://api.dropboxapi.com/2/team_log/get_events",
+        Endpoint::TeamLogGetEventsContinuePost => {
+            "https://api.dropboxapi.com/2/team_log/get_events/continue"
+        }
+        Endpoint::TeamMembersAddPost => "https://api.dropboxapi.com/2/team/members/add_v2",
+        Endpoint::TeamMembersAddJobStatusGetPost => {
+            "https://api.dropboxapi.com/2/team/members/add/job_status/get_v2"
+        }
+        Endpoint::TeamMembersGetInfoPost => "https://api.dropboxapi.com/2/team/members/get_info_v2",
+        Endpoint::TeamMembersListPost => "https://api.dropboxapi.com/2/team/members/list_v2",
+        Endpoint::TeamMembersListContinuePost => {
+            "https://api.dropboxapi.com/2/team/members/list/continue_v2"
+        }
+        Endpoint::TeamMembersRemovePost => "https://api.dropboxapi.com/2/team/members/remove",
+        Endpoint::TeamMembersRemoveJobStatusGetPost => {
+            "https://api.dropboxapi.com/2/team/members/remove/job_status/get"
+        }
+        Endpoint::TeamMembersSetProfilePost => {
+            "https://api.dropboxapi.com/2/team/members/set_profile_v2"
+        }
+        Endpoint::TeamMembersSuspendPost => "https://api.dropboxapi.com/2/team/members/suspend",
+        Endpoint::TeamMembersUnsuspendPost => "https://api.dropboxapi.com/2/team/members/unsuspend",
+        Endpoint::TeamNamespacesListPost => "https://api.dropboxapi.com/2/team/namespaces/list",
+        Endpoint::TeamNamespacesListContinuePost => {
+            "https://api.dropboxapi.com/2/team/namespaces/list/continue"
+        }
+        Endpoint::TeamTeamFolderArchivePost => {
+            "https://api.dropboxapi.com/2/team/team_folder/archive"
+        }
+        Endpoint::TeamTeamFolderArchiveCheckPost => {
+            "https://api.dropboxapi.com/2/team/team_folder/archive/check"
+        }
+        Endpoint::TeamTeamFolderCreatePost => {
+            "https://api.dropboxapi.com/2/team/team_folder/create"
+        }
+        Endpoint::TeamTeamFolderListPost => "https://api.dropboxapi.com/2/team/team_folder/list",
+        Endpoint::TeamTeamFolderListContinuePost => {
+            "https://api.dropboxapi.com/2/team/team_folder/list/continue"
+        }
+        Endpoint::TeamTeamFolderPermanentlyDeletePost => {
+            "https://api.dropboxapi.com/2/team/team_folder/permanently_delete"
+        }
+        Endpoint::TeamTeamFolderRenamePost => {
+            "https://api.dropboxapi.com/2/team/team_folder/rename"
+        }
+        Endpoint::TeamTeamFolderUpdateSyncSettingsPost => {
+            "https://api.dropboxapi.com/2/team/team_folder/update_sync_settings"
+        }
         Endpoint::UsersFeaturesGetValuesPost => {
             "https://api.dropboxapi.com/2/users/features/get_values"
         }
@@ -1,5 +1,6 @@
 use serde::{de::DeserializeOwned, Deserialize};
 use std::fmt::Debug;
+use std::time::Duration;
 
 /// Enum describing set of errors that can occur
 /// Thiserror macro to derive std::error::Error trait
@@ -20,14 +21,156 @@ pub enum ApiError {
     Unauthorized(anyhow::Error),
 }
 
-/// Decode a Dropbox error response body into a human-readable `anyhow::Error`.
+/// A non-2xx response from Dropbox, with everything needed to act on it or
+/// to quote it in a support ticket.
 ///
-/// Dropbox non-2xx bodies follow the envelope
-/// `{"error": <T>, "error_summary": "...", "user_message": {...}}` — this helper
-/// parses that envelope, formatting both the typed `error` and the `error_summary`
-/// into the returned error. If the body doesn't match the envelope shape it falls
-/// back to including the raw body text. Use `serde_json::Value` as `T` for endpoints
-/// that don't have a typed error enum.
+/// Attached to every endpoint failure alongside [`ApiError`] and, when the
+/// body decodes, the endpoint's [`TypedError`]:
+/// ```ignore
+/// if let Some(e) = err.downcast_ref::<DropboxError>() {
+///     eprintln!("{} failed ({}), request id {:?}", e.endpoint, e.status, e.request_id);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DropboxError {
+    pub status: u16,
+    /// Route that failed, e.g. `files/list_folder`.
+    pub endpoint: String,
+    /// `X-Dropbox-Request-Id` response header.
+    pub request_id: Option<String>,
+    /// `error_summary` from the envelope, e.g. `path/not_found/..`.
+    pub error_summary: Option<String>,
+    /// Localized message meant for end users, when Dropbox sends one.
+    pub user_message: Option<UserMessage>,
+    pub kind: DropboxErrorKind,
+}
+
+/// What kind of failure a [`DropboxError`] is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DropboxErrorKind {
+    /// 400: the request was malformed. Dropbox answers with plain text, kept
+    /// here verbatim.
+    BadInput { message: String },
+    /// 401: the token is missing, expired or revoked.
+    Unauthorized,
+    /// 403 or 409: the endpoint's own error union; see [`TypedError`].
+    Endpoint,
+    /// 429: back off for `retry_after` (from the header or the body).
+    RateLimited {
+        retry_after: Option<Duration>,
+        reason: RateLimitReason,
+    },
+    /// 422: the `Dropbox-API-Path-Root` header was rejected; the chain
+    /// carries `TypedError<PathRootError>`.
+    PathRoot,
+    /// 5xx.
+    Server,
+    /// Anything else.
+    Other,
+}
+
+/// Why a request was rate limited.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum RateLimitReason {
+    TooManyRequests,
+    TooManyWriteOperations,
+    #[serde(other)]
+    Other,
+}
+
+/// `user_message` from the error envelope.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct UserMessage {
+    pub locale: String,
+    pub text: String,
+}
+
+impl std::fmt::Display for DropboxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} failed with HTTP {}", self.endpoint, self.status)?;
+        match &self.kind {
+            DropboxErrorKind::BadInput { message } => write!(f, ": bad input: {}", message)?,
+            DropboxErrorKind::RateLimited { retry_after, .. } => {
+                write!(f, ": rate limited")?;
+                if let Some(wait) = retry_after {
+                    write!(f, ", retry after {}s", wait.as_secs())?;
+                }
+            }
+            _ => {}
+        }
+        if let Some(summary) = &self.error_summary {
+            write!(f, " (summary: {})", summary)?;
+        }
+        if let Some(id) = &self.request_id {
+            write!(f, " [request id {}]", id)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for DropboxError {}
+
+impl DropboxError {
+    /// Build from a failed response. `retry_after` is the parsed
+    /// `Retry-After` header; a 429 body's own `retry_after` is the fallback.
+    pub(crate) fn from_response(
+        status: u16,
+        endpoint: String,
+        request_id: Option<String>,
+        retry_after: Option<Duration>,
+        body: &str,
+    ) -> Self {
+        #[derive(Deserialize, Default)]
+        struct Envelope {
+            #[serde(default)]
+            error: serde_json::Value,
+            #[serde(default)]
+            error_summary: Option<String>,
+            #[serde(default)]
+            user_message: Option<UserMessage>,
+        }
+        let envelope = serde_json::from_str::<Envelope>(body).unwrap_or_default();
+
+        let kind = match status {
+            400 => DropboxErrorKind::BadInput {
+                message: body.trim().to_string(),
+            },
+            401 => DropboxErrorKind::Unauthorized,
+            403 | 409 => DropboxErrorKind::Endpoint,
+            429 => DropboxErrorKind::RateLimited {
+                retry_after: retry_after.or_else(|| {
+                    envelope.error["retry_after"]
+                        .as_u64()
+                        .map(Duration::from_secs)
+                }),
+                reason: serde_json::from_value(envelope.error["reason"].clone())
+                    .unwrap_or(RateLimitReason::Other),
+            },
+            422 => DropboxErrorKind::PathRoot,
+            500..=599 => DropboxErrorKind::Server,
+            _ => DropboxErrorKind::Other,
+        };
+        Self {
+            status,
+            endpoint,
+            request_id,
+            error_summary: envelope.error_summary,
+            user_message: envelope.user_message,
+            kind,
+        }
+    }
+
+    /// How long Dropbox asked us to wait, for 429s.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match &self.kind {
+            DropboxErrorKind::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
 /// Wrapper around a typed Dropbox error so it can ride along inside an
 /// `anyhow::Error` context chain. anyhow requires attached contexts to
 /// implement `Display + Debug + Send + Sync + 'static`; Dropbox error enums
@@ -59,41 +202,51 @@ impl<T> TypedError<T> {
     }
 }
 
-pub fn decode_dropbox_error<T: DeserializeOwned + Debug + Send + Sync + 'static>(
-    status: reqwest::StatusCode,
+/// Error chain for a failed endpoint call.
+///
+/// Dropbox non-2xx bodies follow the envelope
+/// `{"error": <T>, "error_summary": "...", "user_message": {...}}`. The
+/// envelope's `error` is attached as a [`TypedError<T>`] underneath
+/// `details`; if it doesn't fit `T` it is attached as
+/// `TypedError<serde_json::Value>` instead, and if the body isn't an
+/// envelope at all (400s, proxies) only `details` is returned. Use
+/// `serde_json::Value` as `T` for endpoints whose error is `Void`.
+pub(crate) fn endpoint_error<T: DeserializeOwned + Debug + Send + Sync + 'static>(
+    details: DropboxError,
     body: &str,
 ) -> anyhow::Error {
-    #[derive(Deserialize)]
-    struct Envelope<T> {
-        error: T,
-        #[serde(default)]
-        error_summary: Option<String>,
-    }
     match serde_json::from_str::<Envelope<T>>(body) {
-        Ok(env) => {
-            let msg = format!(
-                "HTTP {} — {:?} (summary: {})",
-                status,
-                env.error,
-                env.error_summary.as_deref().unwrap_or("-"),
-            );
-            anyhow::Error::new(TypedError(env.error)).context(msg)
-        }
-        Err(_) => anyhow::anyhow!("HTTP {}: {}", status, body),
+        Ok(env) => anyhow::Error::new(TypedError(env.error)).context(details),
+        Err(_) if !is_json_value::<T>() => endpoint_error::<serde_json::Value>(details, body),
+        Err(_) => anyhow::Error::new(details),
     }
 }
 
+#[derive(Deserialize)]
+struct Envelope<T> {
+    error: T,
+}
+
+fn is_json_value<T: 'static>() -> bool {
+    std::any::TypeId::of::<T>() == std::any::TypeId::of::<serde_json::Value>()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn decode<T: DeserializeOwned + Debug + Send + Sync + 'static>(
+        status: u16,
+        body: &str,
+    ) -> anyhow::Error {
+        let details = DropboxError::from_response(status, "files/test".into(), None, None, body);
+        endpoint_error::<T>(details, body)
+    }
+
     #[test]
     fn decodes_typed_envelope() {
         let body = r#"{"error_summary": "path/not_found/.", "error": {".tag": "not_found"}}"#;
-        let err = decode_dropbox_error::<serde_json::Value>(
-            reqwest::StatusCode::from_u16(409).unwrap(),
-            body,
-        );
+        let err = decode::<serde_json::Value>(409, body);
         let msg = err.to_string();
         assert!(msg.contains("409"));
         assert!(msg.contains("summary: path/not_found"));
@@ -103,8 +256,7 @@ mod tests {
     fn downcasts_to_typed_error() {
         use crate::api::files::LookupError;
         let body = r#"{"error_summary": "path/not_found/.", "error": {".tag": "not_found"}}"#;
-        let err =
-            decode_dropbox_error::<LookupError>(reqwest::StatusCode::from_u16(409).unwrap(), body);
+        let err = decode::<LookupError>(409, body);
         let typed = err
             .downcast_ref::<TypedError<LookupError>>()
             .expect("typed error attached as context");
@@ -112,13 +264,116 @@ mod tests {
     }
 
     #[test]
-    fn falls_back_to_raw_body_on_non_envelope() {
-        let body = "rate limited, try again in 30 seconds";
-        let err = decode_dropbox_error::<serde_json::Value>(
-            reqwest::StatusCode::from_u16(429).unwrap(),
+    fn decodes_nested_union_members() {
+        use crate::api::files::{ListFolderError, LookupError, RelocationError, WriteError};
+        let body = r#"{"error_summary": "path/not_found/..", "error": {".tag": "path", "path": {".tag": "not_found"}}}"#;
+        let err = decode::<ListFolderError>(409, body);
+        let typed = err.downcast_ref::<TypedError<ListFolderError>>().unwrap();
+        assert!(matches!(
+            typed.get(),
+            ListFolderError::Path {
+                path: LookupError::NotFound
+            }
+        ));
+
+        let body = r#"{"error_summary": "to/conflict/file/..", "error": {".tag": "to", "to": {".tag": "conflict", "conflict": {".tag": "file"}}}}"#;
+        let err = decode::<RelocationError>(409, body);
+        let typed = err.downcast_ref::<TypedError<RelocationError>>().unwrap();
+        assert!(matches!(
+            typed.get(),
+            RelocationError::To {
+                to: WriteError::Conflict { .. }
+            }
+        ));
+    }
+
+    #[test]
+    fn unknown_tags_decode_as_other() {
+        use crate::api::files::ListFolderError;
+        let body = r#"{"error_summary": "brand_new/..", "error": {".tag": "brand_new"}}"#;
+        let err = decode::<ListFolderError>(409, body);
+        let typed = err.downcast_ref::<TypedError<ListFolderError>>().unwrap();
+        assert!(matches!(typed.get(), ListFolderError::Other));
+    }
+
+    #[test]
+    fn falls_back_to_untyped_union_when_shape_differs() {
+        use crate::api::files::LookupError;
+        let body = r#"{"error_summary": "path/odd/.", "error": {".tag": "malformed_path", "malformed_path": 7}}"#;
+        let err = decode::<LookupError>(409, body);
+        assert!(err.downcast_ref::<TypedError<LookupError>>().is_none());
+        assert!(err
+            .downcast_ref::<TypedError<serde_json::Value>>()
+            .is_some());
+        assert!(err.to_string().contains("summary: path/odd"));
+    }
+
+    #[test]
+    fn structured_error_from_envelope() {
+        let body = r#"{"error_summary": "path/not_found/..", "error": {".tag": "path"}, "user_message": {"locale": "en", "text": "File not found"}}"#;
+        let err = DropboxError::from_response(
+            409,
+            "files/list_folder".into(),
+            Some("abc123".into()),
+            None,
+            body,
+        );
+        assert_eq!(err.kind, DropboxErrorKind::Endpoint);
+        assert_eq!(err.error_summary.as_deref(), Some("path/not_found/.."));
+        assert_eq!(err.user_message.as_ref().unwrap().text, "File not found");
+        let shown = err.to_string();
+        assert!(shown.contains("files/list_folder"));
+        assert!(shown.contains("abc123"));
+    }
+
+    #[test]
+    fn structured_error_bad_input_and_rate_limit() {
+        let err = DropboxError::from_response(
+            400,
+            "files/list_folder".into(),
+            None,
+            None,
+            "Error in call to API function \"files/list_folder\": missing path\n",
+        );
+        assert!(matches!(
+            &err.kind,
+            DropboxErrorKind::BadInput { message } if message.ends_with("missing path")
+        ));
+
+        let body = r#"{"error_summary": "too_many_write_operations/..", "error": {"reason": {".tag": "too_many_write_operations"}, "retry_after": 7}}"#;
+        let err = DropboxError::from_response(429, "files/upload".into(), None, None, body);
+        assert_eq!(
+            err.kind,
+            DropboxErrorKind::RateLimited {
+                retry_after: Some(Duration::from_secs(7)),
+                reason: RateLimitReason::TooManyWriteOperations,
+            }
+        );
+        let header_wins = DropboxError::from_response(
+            429,
+            "files/upload".into(),
+            None,
+            Some(Duration::from_secs(2)),
             body,
         );
+        assert_eq!(header_wins.retry_after(), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn non_envelope_bodies_carry_only_details() {
+        let body = "rate limited, try again in 30 seconds";
+        let err = decode::<serde_json::Value>(429, body);
         assert!(err.to_string().contains("429"));
-        assert!(err.to_string().contains("rate limited"));
+        assert!(err
+            .downcast_ref::<TypedError<serde_json::Value>>()
+            .is_none());
+        let details = err.downcast_ref::<DropboxError>().unwrap();
+        assert!(matches!(
+            details.kind,
+            DropboxErrorKind::RateLimited {
+                retry_after: None,
+                ..
+            }
+        ));
     }
 }
@@ -4,16 +4,38 @@
 //! Wraps `upload_session/{start, append_v2, finish}` so callers can feed in
 //! an `AsyncRead` stream and get back the final `FileMetadata` without
 //! tracking session cursors manually.
+//!
+//! [`ResumableUpload`] does the same from a seekable reader and hands out
+//! its [`UploadState`] after every chunk, so an upload interrupted by a
+//! crash or a dropped connection picks up where Dropbox left it:
+//!
+//! ```ignore
+//! let source = SourceId::of_file("backup.tar")?;
+//! let mut upload = ResumableUpload::new(&client, "/backup.tar", source, WriteMode::Add);
+//! if let Some(state) = load_saved_state()? {
+//!     upload = upload.resume_from(state);
+//! }
+//! let file = tokio::fs::File::open("backup.tar").await?;
+//! let meta = upload.run(file, |state| save_state(state)).await?;
+//! ```
 
 use crate::api::files::{
     upload_session_append::UploadSessionAppendRequest,
     upload_session_finish::UploadSessionFinishRequest,
     upload_session_start::UploadSessionStartRequest, CommitInfo, FileMetadata,
-    UploadSessionAppendArg, UploadSessionCursor, UploadSessionFinishArg, UploadSessionStartArg,
+    UploadSessionAppendArg, UploadSessionAppendError, UploadSessionCursor, UploadSessionFinishArg,
+    UploadSessionFinishError, UploadSessionLookupError, UploadSessionStartArg, WriteMode,
 };
-use crate::api::Service;
+use crate::content_hash::{self, ContentHasher};
+use crate::errors::TypedError;
+use crate::telemetry;
+use crate::Client;
 use anyhow::{Context, Result};
-use tokio::io::{AsyncRead, AsyncReadExt};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::SeekFrom;
+use std::path::Path;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
 
 /// Default chunk size. 4 MiB balances round-trip count against memory use.
 pub const DEFAULT_CHUNK_SIZE: usize = 4 * 1024 * 1024;
@@ -30,10 +52,52 @@ pub const DEFAULT_CHUNK_SIZE: usize = 4 * 1024 * 1024;
 pub async fn upload_large_file<R: AsyncRead + Unpin>(
     token: &str,
     path: &str,
+    reader: R,
+    chunk_size: usize,
+    mode: crate::api::files::WriteMode,
+) -> Result<FileMetadata> {
+    upload_large_file_with(&Client::new(token), path, reader, chunk_size, mode).await
+}
+
+/// [`upload_large_file`] through a [`Client`]: every session call uses the
+/// client's transport settings and token, with refresh and 401 replay.
+pub async fn upload_large_file_with<R: AsyncRead + Unpin>(
+    client: &Client,
+    path: &str,
+    reader: R,
+    chunk_size: usize,
+    mode: crate::api::files::WriteMode,
+) -> Result<FileMetadata> {
+    upload(client, path, reader, chunk_size, mode, false).await
+}
+
+/// [`upload_large_file_with`], with integrity checks. Every session call
+/// carries the content hash of its chunk, so Dropbox rejects a chunk that
+/// was corrupted on the way. The content hash of the committed file is
+/// then compared with the local one and a difference fails with
+/// [`ContentHashMismatch`](content_hash::ContentHashMismatch).
+pub async fn upload_large_file_verified<R: AsyncRead + Unpin>(
+    client: &Client,
+    path: &str,
+    reader: R,
+    chunk_size: usize,
+    mode: crate::api::files::WriteMode,
+) -> Result<FileMetadata> {
+    upload(client, path, reader, chunk_size, mode, true).await
+}
+
+async fn upload<R: AsyncRead + Unpin>(
+    client: &Client,
+    path: &str,
     mut reader: R,
     chunk_size: usize,
     mode: crate::api::files::WriteMode,
+    verify: bool,
 ) -> Result<FileMetadata> {
+    let mut hasher = ContentHasher::new();
+    // Content hash of one call's data, when verifying.
+    let chunk_hash = |data: &[u8]| verify.then(|| content_hash::content_hash(data));
+
     // Read first chunk and open the session.
     let mut first_chunk = vec![0u8; chunk_size];
     let mut first_read = 0usize;
@@ -46,21 +110,23 @@ pub async fn upload_large_file<R: AsyncRead + Unpin>(
     }
     first_chunk.truncate(first_read);
     let eof_after_first = first_read < chunk_size;
+    hasher.update(&first_chunk);
 
     let start_req = UploadSessionStartRequest {
-        access_token: token,
         payload: Some(UploadSessionStartArg {
             close: Some(eof_after_first),
             session_type: None,
-            content_hash: None,
+            content_hash: chunk_hash(&first_chunk),
         }),
         data: Some(first_chunk),
+        ..Default::default()
     };
-    let start_resp = start_req
-        .call()
+    let start_resp = client
+        .execute(&start_req)
         .await?
         .context("upload_session/start returned empty")?;
     let session_id = start_resp.payload.session_id;
+    telemetry::upload_step("start", &session_id, 0, first_read);
 
     let mut offset = first_read as u64;
 
@@ -77,27 +143,28 @@ pub async fn upload_large_file<R: AsyncRead + Unpin>(
                 read += n;
             }
             buf.truncate(read);
+            hasher.update(&buf);
 
             if read == chunk_size {
                 // Full chunk — append and continue.
                 let append_req = UploadSessionAppendRequest {
-                    access_token: token,
                     payload: Some(UploadSessionAppendArg {
                         cursor: UploadSessionCursor {
                             session_id: session_id.clone(),
                             offset,
                         },
                         close: Some(false),
-                        content_hash: None,
+                        content_hash: chunk_hash(&buf),
                     }),
                     data: Some(buf),
+                    ..Default::default()
                 };
-                let _ = append_req.call().await?;
+                let _ = client.execute(&append_req).await?;
+                telemetry::upload_step("append", &session_id, offset, read);
                 offset += read as u64;
             } else {
                 // Short read = last chunk. Break and let finish handle it.
                 let finish_req = UploadSessionFinishRequest {
-                    access_token: token,
                     payload: Some(UploadSessionFinishArg {
                         cursor: UploadSessionCursor {
                             session_id: session_id.clone(),
@@ -112,24 +179,28 @@ pub async fn upload_large_file<R: AsyncRead + Unpin>(
                             property_groups: None,
                             strict_conflict: None,
                         },
-                        content_hash: None,
+                        content_hash: chunk_hash(&buf),
                     }),
                     data: Some(buf),
+                    ..Default::default()
                 };
-                let resp = finish_req
-                    .call()
+                let resp = client
+                    .execute(&finish_req)
                     .await?
                     .context("upload_session/finish returned empty")?;
-                return Ok(resp.payload);
+                telemetry::upload_step("finish", &session_id, offset, read);
+                return committed(path, resp.payload, hasher, verify);
             }
         }
     }
 
     // First chunk already EOF — go straight to finish with empty body.
     let finish_req = UploadSessionFinishRequest {
-        access_token: token,
         payload: Some(UploadSessionFinishArg {
-            cursor: UploadSessionCursor { session_id, offset },
+            cursor: UploadSessionCursor {
+                session_id: session_id.clone(),
+                offset,
+            },
             commit: CommitInfo {
                 path: path.to_string(),
                 mode,
@@ -139,22 +210,316 @@ pub async fn upload_large_file<R: AsyncRead + Unpin>(
                 property_groups: None,
                 strict_conflict: None,
             },
-            content_hash: None,
+            content_hash: chunk_hash(&[]),
         }),
         data: Some(Vec::new()),
+        ..Default::default()
     };
-    let resp = finish_req
-        .call()
+    let resp = client
+        .execute(&finish_req)
         .await?
         .context("upload_session/finish returned empty")?;
-    Ok(resp.payload)
+    telemetry::upload_step("finish", &session_id, offset, 0);
+    committed(path, resp.payload, hasher, verify)
+}
+
+/// The committed file's metadata, once its hash checks out when verifying.
+fn committed(
+    path: &str,
+    meta: FileMetadata,
+    hasher: ContentHasher,
+    verify: bool,
+) -> Result<FileMetadata> {
+    if verify {
+        content_hash::verify(path, meta.content_hash.as_deref(), hasher.finish())?;
+    }
+    Ok(meta)
+}
+
+/// Dropbox drops upload sessions this long after they were opened.
+const SESSION_LIFETIME: chrono::Duration = chrono::Duration::days(7);
+
+/// Sessions this close to [`SESSION_LIFETIME`] are restarted rather than
+/// resumed, so they don't expire mid-upload.
+const SESSION_MARGIN: chrono::Duration = chrono::Duration::hours(1);
+
+/// Identifies the local data being uploaded, so saved state is only resumed
+/// against the same source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceId {
+    pub len: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<DateTime<Utc>>,
+}
+
+impl SourceId {
+    /// Size and modification time of a local file.
+    pub fn of_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let meta = std::fs::metadata(path).with_context(|| format!("stat {}", path.display()))?;
+        Ok(Self {
+            len: meta.len(),
+            modified: meta.modified().ok().map(DateTime::<Utc>::from),
+        })
+    }
+}
+
+/// Where a resumable upload stands. Save it from the checkpoint callback of
+/// [`ResumableUpload::run`] and hand it to [`ResumableUpload::resume_from`]
+/// after a restart.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadState {
+    pub session_id: String,
+    /// Bytes Dropbox has accepted; the next chunk starts here.
+    pub offset: u64,
+    /// Destination path in Dropbox.
+    pub path: String,
+    pub source: SourceId,
+    /// When the session was opened.
+    pub started_at: DateTime<Utc>,
+}
+
+impl UploadState {
+    /// True once the session is too old to resume safely.
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.started_at + SESSION_LIFETIME - SESSION_MARGIN
+    }
+}
+
+/// A chunked upload that can be resumed after the process restarts.
+#[derive(Debug)]
+pub struct ResumableUpload {
+    client: Client,
+    path: String,
+    mode: WriteMode,
+    source: SourceId,
+    chunk_size: usize,
+    resume: Option<UploadState>,
+}
+
+impl ResumableUpload {
+    pub fn new(
+        client: &Client,
+        path: impl Into<String>,
+        source: SourceId,
+        mode: WriteMode,
+    ) -> Self {
+        Self {
+            client: client.clone(),
+            path: path.into(),
+            mode,
+            source,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            resume: None,
+        }
+    }
+
+    /// Bytes per session call. Defaults to [`DEFAULT_CHUNK_SIZE`].
+    pub fn with_chunk_size(self, chunk_size: usize) -> Self {
+        Self { chunk_size, ..self }
+    }
+
+    /// Continue a session from saved state. State for another destination
+    /// or source, or for an expired session, is dropped and the upload
+    /// starts over in a new session.
+    pub fn resume_from(self, state: UploadState) -> Self {
+        Self {
+            resume: Some(state),
+            ..self
+        }
+    }
+
+    /// Upload everything `reader` holds. `checkpoint` gets the state once
+    /// the session is open and after every chunk Dropbox accepts; an error
+    /// from it stops the upload. The reader is sought to the session offset
+    /// before each chunk, so it must be the same source from its start.
+    ///
+    /// An `incorrect_offset` from Dropbox moves the upload to the offset it
+    /// reports. A session Dropbox no longer knows is restarted once.
+    pub async fn run<R, F>(self, mut reader: R, mut checkpoint: F) -> Result<FileMetadata>
+    where
+        R: AsyncRead + AsyncSeek + Unpin,
+        F: FnMut(&UploadState) -> Result<()>,
+    {
+        let resumable = self.resume.clone().filter(|state| {
+            state.path == self.path && state.source == self.source && !state.is_expired()
+        });
+        let mut state = match resumable {
+            Some(state) => state,
+            None => self.start(&mut checkpoint).await?,
+        };
+        let mut restarted = false;
+        let mut buf = vec![0u8; self.chunk_size];
+        loop {
+            reader
+                .seek(SeekFrom::Start(state.offset))
+                .await
+                .context("seek upload source")?;
+            let read = read_chunk(&mut reader, &mut buf).await?;
+            let data = buf[..read].to_vec();
+            let outcome = match read < self.chunk_size {
+                true => self.finish(&state, data).await.map(Some),
+                false => self.append(&state, data).await.map(|_| None),
+            };
+            match outcome {
+                Ok(Some(meta)) => return Ok(meta),
+                Ok(None) => {
+                    state.offset += read as u64;
+                    checkpoint(&state)?;
+                }
+                Err(err) => match session_fault(&err) {
+                    Some(SessionFault::Offset(correct)) if correct != state.offset => {
+                        state.offset = correct;
+                        checkpoint(&state)?;
+                    }
+                    Some(SessionFault::Gone) if !restarted => {
+                        restarted = true;
+                        state = self.start(&mut checkpoint).await?;
+                    }
+                    _ => return Err(err),
+                },
+            }
+        }
+    }
+
+    /// Open an empty session; all data then goes through append and finish.
+    async fn start<F>(&self, checkpoint: &mut F) -> Result<UploadState>
+    where
+        F: FnMut(&UploadState) -> Result<()>,
+    {
+        let request = UploadSessionStartRequest {
+            payload: Some(UploadSessionStartArg {
+                close: Some(false),
+                session_type: None,
+                content_hash: None,
+            }),
+            data: Some(Vec::new()),
+            ..Default::default()
+        };
+        let response = self
+            .client
+            .execute(&request)
+            .await?
+            .context("upload_session/start returned empty")?;
+        let state = UploadState {
+            session_id: response.payload.session_id,
+            offset: 0,
+            path: self.path.clone(),
+            source: self.source.clone(),
+            started_at: Utc::now(),
+        };
+        telemetry::upload_step("start", &state.session_id, 0, 0);
+        checkpoint(&state)?;
+        Ok(state)
+    }
+
+    async fn append(&self, state: &UploadState, data: Vec<u8>) -> Result<()> {
+        let len = data.len();
+        let request = UploadSessionAppendRequest {
+            payload: Some(UploadSessionAppendArg {
+                cursor: cursor(state),
+                close: Some(false),
+                content_hash: None,
+            }),
+            data: Some(data),
+            ..Default::default()
+        };
+        self.client.execute(&request).await?;
+        telemetry::upload_step("append", &state.session_id, state.offset, len);
+        Ok(())
+    }
+
+    async fn finish(&self, state: &UploadState, data: Vec<u8>) -> Result<FileMetadata> {
+        let len = data.len();
+        let request = UploadSessionFinishRequest {
+            payload: Some(UploadSessionFinishArg {
+                cursor: cursor(state),
+                commit: CommitInfo {
+                    path: self.path.clone(),
+                    mode: self.mode.clone(),
+                    autorename: true,
+                    client_modified: None,
+                    mute: false,
+                    property_groups: None,
+                    strict_conflict: None,
+                },
+                content_hash: None,
+            }),
+            data: Some(data),
+            ..Default::default()
+        };
+        let response = self
+            .client
+            .execute(&request)
+            .await?
+            .context("upload_session/finish returned empty")?;
+        telemetry::upload_step("finish", &state.session_id, state.offset, len);
+        Ok(response.payload)
+    }
+}
+
+fn cursor(state: &UploadState) -> UploadSessionCursor {
+    UploadSessionCursor {
+        session_id: state.session_id.clone(),
+        offset: state.offset,
+    }
+}
+
+/// Fill `buf` from `reader`, short only at EOF.
+async fn read_chunk<R: AsyncRead + Unpin>(reader: &mut R, buf: &mut [u8]) -> Result<usize> {
+    let mut read = 0;
+    while read < buf.len() {
+        let n = reader.read(&mut buf[read..]).await?;
+        if n == 0 {
+            break;
+        }
+        read += n;
+    }
+    Ok(read)
+}
+
+/// Session errors a resumable upload recovers from.
+enum SessionFault {
+    /// Dropbox expects the next chunk at this offset.
+    Offset(u64),
+    /// The session expired or was never there.
+    Gone,
+}
+
+fn session_fault(err: &anyhow::Error) -> Option<SessionFault> {
+    if let Some(TypedError(error)) = err.downcast_ref::<TypedError<UploadSessionAppendError>>() {
+        return match error {
+            UploadSessionAppendError::IncorrectOffset { correct_offset } => {
+                Some(SessionFault::Offset(*correct_offset))
+            }
+            UploadSessionAppendError::NotFound => Some(SessionFault::Gone),
+            _ => None,
+        };
+    }
+    match err.downcast_ref::<TypedError<UploadSessionFinishError>>()? {
+        TypedError(UploadSessionFinishError::LookupFailed { lookup_failed }) => match lookup_failed
+        {
+            UploadSessionLookupError::IncorrectOffset { correct_offset } => {
+                Some(SessionFault::Offset(*correct_offset))
+            }
+            UploadSessionLookupError::NotFound => Some(SessionFault::Gone),
+            _ => None,
+        },
+        _ => None,
+    }
 }
 
 #[cfg(all(test, feature = "test-utils"))]
 mod tests {
-    use super::upload_large_file;
+    use super::{
+        upload_large_file, upload_large_file_verified, ResumableUpload, SourceId, UploadState,
+    };
     use crate::api::files::WriteMode;
+    use crate::content_hash::content_hash;
     use crate::tests_utils::with_test_server_async;
+    use crate::Client;
+    use chrono::Utc;
+    use mockito::{Matcher, Mock, ServerGuard};
     use std::io::Cursor;
 
     #[tokio::test]
@@ -189,4 +554,186 @@ mod tests {
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn verified_upload_sends_chunk_hashes_and_checks_the_file() {
+        let hash = content_hash(b"hello");
+        let finish_resp = format!(
+            r#"{{"name":"hi.txt","id":"id:abc","client_modified":"2025-01-01T00:00:00Z","server_modified":"2025-01-01T00:00:00Z","rev":"r1","size":5,"path_lower":"/hi.txt","path_display":"/hi.txt","is_downloadable":true,"content_hash":"{hash}"}}"#
+        );
+
+        with_test_server_async(|mut server| async move {
+            let start_mock = server
+                .mock("POST", "/2/files/upload_session/start")
+                .match_header(
+                    "Dropbox-API-Arg",
+                    Matcher::Regex(format!(r#""content_hash":"{hash}""#)),
+                )
+                .with_status(200)
+                .with_header("Content-Type", "application/json")
+                .with_body(r#"{"session_id":"session-1"}"#)
+                .create_async()
+                .await;
+            let finish_mock = server
+                .mock("POST", "/2/files/upload_session/finish")
+                .match_header(
+                    "Dropbox-API-Arg",
+                    Matcher::Regex(format!(r#""content_hash":"{}""#, content_hash(b""))),
+                )
+                .with_status(200)
+                .with_header("Content-Type", "application/json")
+                .with_body(finish_resp)
+                .create_async()
+                .await;
+
+            let client = Client::new("test");
+            let reader = Cursor::new(b"hello".to_vec());
+            let meta = upload_large_file_verified(&client, "/hi.txt", reader, 4096, WriteMode::Add)
+                .await
+                .expect("upload_large_file_verified returned error");
+            assert_eq!(meta.size, 5);
+            start_mock.assert();
+            finish_mock.assert();
+        })
+        .await;
+    }
+
+    const DATA: &[u8] = b"abcdefghij";
+    const BIG_META: &str = r#"{"name":"big","id":"id:big","client_modified":"2025-01-01T00:00:00Z","server_modified":"2025-01-01T00:00:00Z","rev":"r1","size":10,"path_lower":"/big","path_display":"/big","is_downloadable":true}"#;
+
+    fn saved(session_id: &str, offset: u64) -> UploadState {
+        UploadState {
+            session_id: session_id.into(),
+            offset,
+            path: "/big".into(),
+            source: SourceId {
+                len: DATA.len() as u64,
+                modified: None,
+            },
+            started_at: Utc::now(),
+        }
+    }
+
+    async fn session_call(
+        server: &mut ServerGuard,
+        route: &str,
+        session_id: &str,
+        offset: u64,
+        body: &str,
+    ) -> Mock {
+        server
+            .mock("POST", route)
+            .match_header(
+                "Dropbox-API-Arg",
+                Matcher::Regex(format!(
+                    r#""session_id":"{session_id}","offset":{offset}\b"#
+                )),
+            )
+            .match_body(body)
+            .with_status(200)
+            .with_header("Content-Type", "application/json")
+            .with_body(match route {
+                "/2/files/upload_session/finish" => BIG_META,
+                _ => "null",
+            })
+            .create_async()
+            .await
+    }
+
+    /// A new session `s2` that takes all of `DATA` in chunks of 4.
+    async fn fresh_session(server: &mut ServerGuard) -> Vec<Mock> {
+        let start = server
+            .mock("POST", "/2/files/upload_session/start")
+            .with_status(200)
+            .with_header("Content-Type", "application/json")
+            .with_body(r#"{"session_id":"s2"}"#)
+            .create_async()
+            .await;
+        let append = "/2/files/upload_session/append_v2";
+        vec![
+            start,
+            session_call(server, append, "s2", 0, "abcd").await,
+            session_call(server, append, "s2", 4, "efgh").await,
+            session_call(server, "/2/files/upload_session/finish", "s2", 8, "ij").await,
+        ]
+    }
+
+    async fn run(state: UploadState) -> Vec<(String, u64)> {
+        let mut checkpoints = Vec::new();
+        let source = saved("", 0).source;
+        let meta = ResumableUpload::new(&Client::new("test"), "/big", source, WriteMode::Add)
+            .with_chunk_size(4)
+            .resume_from(state)
+            .run(Cursor::new(DATA.to_vec()), |state| {
+                checkpoints.push((state.session_id.clone(), state.offset));
+                Ok(())
+            })
+            .await
+            .expect("resumable upload returned error");
+        assert_eq!(meta.name, "big");
+        checkpoints
+    }
+
+    #[tokio::test]
+    async fn resumable_upload_follows_the_server_offset() {
+        with_test_server_async(|mut server| async move {
+            let behind = server
+                .mock("POST", "/2/files/upload_session/append_v2")
+                .match_header(
+                    "Dropbox-API-Arg",
+                    Matcher::Regex(r#""session_id":"s1","offset":4\b"#.into()),
+                )
+                .with_status(409)
+                .with_header("Content-Type", "application/json")
+                .with_body(r#"{"error_summary":"incorrect_offset/..","error":{".tag":"incorrect_offset","correct_offset":8}}"#)
+                .create_async()
+                .await;
+            let finish =
+                session_call(&mut server, "/2/files/upload_session/finish", "s1", 8, "ij").await;
+
+            assert_eq!(run(saved("s1", 4)).await, [("s1".to_string(), 8)]);
+            behind.assert();
+            finish.assert();
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn resumable_upload_restarts_an_expired_session() {
+        with_test_server_async(|mut server| async move {
+            let mocks = fresh_session(&mut server).await;
+
+            let mut expired = saved("s1", 4);
+            expired.started_at = Utc::now() - chrono::Duration::days(8);
+            let offsets: Vec<u64> = run(expired).await.into_iter().map(|(_, o)| o).collect();
+            assert_eq!(offsets, [0, 4, 8]);
+            mocks.iter().for_each(Mock::assert);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn resumable_upload_restarts_a_session_dropbox_lost() {
+        with_test_server_async(|mut server| async move {
+            let gone = server
+                .mock("POST", "/2/files/upload_session/append_v2")
+                .match_header(
+                    "Dropbox-API-Arg",
+                    Matcher::Regex(r#""session_id":"s1""#.into()),
+                )
+                .with_status(409)
+                .with_header("Content-Type", "application/json")
+                .with_body(r#"{"error_summary":"not_found/..","error":{".tag":"not_found"}}"#)
+                .create_async()
+                .await;
+            let mocks = fresh_session(&mut server).await;
+
+            let checkpoints = run(saved("s1", 4)).await;
+            assert_eq!(checkpoints[0], ("s2".to_string(), 0));
+            assert_eq!(checkpoints.len(), 3);
+            gone.assert();
+            mocks.iter().for_each(Mock::assert);
+        })
+        .await;
+    }
 }
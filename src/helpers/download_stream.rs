@@ -8,11 +8,14 @@
 //! and returns the parsed `FileMetadata` alongside a `Stream<Item = Bytes>`
 //! of body chunks, so callers can pipe to disk without buffering.
 
-use crate::api::files::{DownloadArg, FileMetadata};
+use crate::api::files::{DownloadArg, DownloadError, FileMetadata};
+use crate::content_hash::{self, ContentHasher};
 use crate::endpoints::{get_endpoint_url, Endpoint};
+use crate::transport::HttpRequest;
+use crate::Client;
 use anyhow::{Context, Result};
 use bytes::Bytes;
-use futures::stream::{Stream, StreamExt};
+use futures::stream::{self, Stream, StreamExt};
 
 /// Open a streaming download. Returns the metadata header (fully parsed) and
 /// a chunked byte stream of the file's body.
@@ -27,6 +30,16 @@ pub async fn download_stream(
     token: &str,
     path: &str,
 ) -> Result<(FileMetadata, impl Stream<Item = Result<Bytes>> + Unpin)> {
+    download_stream_with(&Client::new(token), path).await
+}
+
+/// [`download_stream`] through a [`Client`]: uses its transport settings and
+/// refreshes the token first if it has expired.
+pub async fn download_stream_with(
+    client: &Client,
+    path: &str,
+) -> Result<(FileMetadata, impl Stream<Item = Result<Bytes>> + Unpin)> {
+    client.ensure_fresh().await?;
     let arg = DownloadArg {
         path: path.to_string(),
         rev: None,
@@ -39,20 +52,31 @@ pub async fn download_stream(
         .2
         .unwrap_or_else(|| get_endpoint_url(Endpoint::FilesDownloadPost).0);
 
-    let resp = crate::AsyncClient
-        .post(url)
-        .bearer_auth(token)
-        .header("Dropbox-API-Arg", arg_json)
-        .send()
+    let mut request = HttpRequest {
+        url,
+        headers: vec![
+            (
+                "Authorization".to_string(),
+                format!("Bearer {}", client.token()),
+            ),
+            ("Dropbox-API-Arg".to_string(), arg_json),
+        ],
+        body: None,
+    };
+    request.headers.extend(client.context_headers());
+    let resp = client
+        .send_http_streaming(Endpoint::FilesDownloadPost, request, None)
         .await
-        .context("download request failed")?
-        .error_for_status()
-        .context("download returned non-2xx")?;
+        .context("download request failed")?;
+    if !resp.is_success() {
+        let buffered = resp.collect().await.context("download request failed")?;
+        return Err(buffered
+            .api_error::<DownloadError>(Endpoint::FilesDownloadPost)
+            .context("download returned non-2xx"));
+    }
 
     let meta_header = resp
-        .headers()
-        .get("Dropbox-API-Result")
-        .and_then(|v| v.to_str().ok())
+        .header("Dropbox-API-Result")
         .map(|s| s.replace('\n', ""))
         .context("Dropbox-API-Result header missing")?;
 
@@ -60,16 +84,52 @@ pub async fn download_stream(
         serde_json::from_str(&meta_header).context("parse Dropbox-API-Result")?;
 
     let stream = resp
-        .bytes_stream()
+        .body
         .map(|r| r.map_err(|e| anyhow::Error::new(e).context("download body stream")));
 
     Ok((meta, Box::pin(stream)))
 }
 
+/// [`download_stream_with`], checking the body against the file's content
+/// hash as it streams. On a mismatch the stream's last item is a
+/// [`ContentHashMismatch`](content_hash::ContentHashMismatch) error, so
+/// treat the data as unverified until the stream has ended cleanly.
+pub async fn download_stream_verified(
+    client: &Client,
+    path: &str,
+) -> Result<(FileMetadata, impl Stream<Item = Result<Bytes>> + Unpin)> {
+    let (meta, body) = download_stream_with(client, path).await?;
+    let expected = meta
+        .content_hash
+        .clone()
+        .with_context(|| format!("Dropbox returned no content_hash for {path}"))?;
+    let path = path.to_string();
+
+    let verified = stream::unfold(Some((body, ContentHasher::new())), move |state| {
+        let (path, expected) = (path.clone(), expected.clone());
+        async move {
+            let (mut body, mut hasher) = state?;
+            match body.next().await {
+                Some(Ok(chunk)) => {
+                    hasher.update(&chunk);
+                    Some((Ok(chunk), Some((body, hasher))))
+                }
+                Some(Err(err)) => Some((Err(err), None)),
+                None => content_hash::verify(&path, Some(&expected), hasher.finish())
+                    .err()
+                    .map(|err| (Err(err), None)),
+            }
+        }
+    });
+    Ok((meta, Box::pin(verified)))
+}
+
 #[cfg(all(test, feature = "test-utils"))]
 mod tests {
-    use super::download_stream;
+    use super::{download_stream, download_stream_verified};
+    use crate::content_hash::{content_hash, ContentHashMismatch};
     use crate::tests_utils::with_test_server_async;
+    use crate::Client;
     use futures::StreamExt;
 
     #[tokio::test]
@@ -102,4 +162,47 @@ mod tests {
         })
         .await;
     }
+
+    async fn verified_download(hash: &str) -> (Vec<u8>, Option<anyhow::Error>) {
+        let meta_json = format!(
+            r#"{{"name":"f.txt","id":"id:abc","client_modified":"2025-01-01T00:00:00Z","server_modified":"2025-01-01T00:00:00Z","rev":"r1","size":11,"path_lower":"/f.txt","path_display":"/f.txt","is_downloadable":true,"content_hash":"{hash}"}}"#
+        );
+        with_test_server_async(|mut server| async move {
+            let _mock = server
+                .mock("POST", "/2/files/download")
+                .with_status(200)
+                .with_header("Dropbox-API-Result", &meta_json)
+                .with_body("hello world")
+                .create_async()
+                .await;
+
+            let (_, mut stream) = download_stream_verified(&Client::new("test"), "/f.txt")
+                .await
+                .expect("download_stream_verified returned error");
+            let mut got = Vec::new();
+            while let Some(chunk) = stream.next().await {
+                match chunk {
+                    Ok(chunk) => got.extend_from_slice(&chunk),
+                    Err(err) => return (got, Some(err)),
+                }
+            }
+            (got, None)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn verified_download_accepts_matching_content() {
+        let (got, err) = verified_download(&content_hash(b"hello world")).await;
+        assert_eq!(got, b"hello world");
+        assert!(err.is_none());
+    }
+
+    #[tokio::test]
+    async fn verified_download_fails_on_corrupted_content() {
+        let (_, err) = verified_download(&content_hash(b"hello w0rld")).await;
+        let err = err.expect("mismatch not reported");
+        let mismatch = err.downcast_ref::<ContentHashMismatch>().unwrap();
+        assert_eq!(mismatch.actual, content_hash(b"hello world"));
+    }
 }
@@ -0,0 +1,971 @@
+//! Wait for launch-and-poll operations without writing the loop.
+//!
+//! Batch, sharing, team-member, team-group and team-folder operations
+//! answer either with their result or with an `async_job_id` to poll on a
+//! matching `*/check` or `*/job_status/get` route. [`launch`] sends the
+//! first request and returns a [`JobHandle`] covering both cases;
+//! [`JobHandle::wait`] polls with backoff until the job completes, fails or
+//! runs past its deadline. Every call goes through [`Client::execute`].
+//!
+//! ```ignore
+//! let request = DeleteBatchRequest {
+//!     payload: Some(DeleteBatchArgs { entries }),
+//!     ..Default::default()
+//! };
+//! let result = jobs::launch(&client, &request).await?.wait().await?;
+//! for entry in result.entries {
+//!     // DeleteBatchResultEntry::Success / ::Failure, per path
+//! }
+//! ```
+//!
+//! A job that fails as a whole comes back as an error carrying the route's
+//! failure union as a [`TypedError`], e.g.
+//! `TypedError<sharing::ShareFolderError>`. A job still running at the
+//! deadline ends with [`JobTimedOut`], whose id [`resume`] picks up again.
+
+use crate::api::{files, sharing, team};
+use crate::errors::TypedError;
+use crate::Client;
+use anyhow::{Context, Result};
+use futures::future::BoxFuture;
+use std::time::{Duration, Instant};
+
+/// How an operation answered its launch request.
+#[derive(Debug)]
+pub enum Launch<T> {
+    /// Finished within the request.
+    Complete(T),
+    /// Running; poll with this id.
+    AsyncJobId(String),
+}
+
+/// One poll of a running job.
+#[derive(Debug)]
+pub enum JobState<T> {
+    InProgress,
+    Complete(T),
+    /// The job as a whole failed; the error carries the typed reason.
+    Failed(anyhow::Error),
+}
+
+/// A request that launches an operation, with the route that polls it.
+pub trait AsyncJob {
+    type Output: Send + 'static;
+
+    /// Send the launch request.
+    fn launch(&self, client: &Client) -> BoxFuture<'static, Result<Launch<Self::Output>>>;
+
+    /// Blocking variant of [`AsyncJob::launch`].
+    fn launch_sync(&self, client: &Client) -> Result<Launch<Self::Output>>;
+
+    /// Poll the job once.
+    fn check(
+        client: &Client,
+        async_job_id: String,
+    ) -> BoxFuture<'static, Result<JobState<Self::Output>>>;
+
+    /// Blocking variant of [`AsyncJob::check`].
+    fn check_sync(client: &Client, async_job_id: String) -> Result<JobState<Self::Output>>;
+}
+
+/// The job was still running when the deadline passed. Nothing was
+/// cancelled; [`resume`] with `async_job_id` to keep waiting.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("async job {async_job_id} still in progress after {waited:?}")]
+pub struct JobTimedOut {
+    pub async_job_id: String,
+    pub waited: Duration,
+}
+
+/// Send `request` and return a handle on the operation it started.
+pub fn launch<R: AsyncJob>(
+    client: &Client,
+    request: &R,
+) -> BoxFuture<'static, Result<JobHandle<R::Output>>> {
+    let launched = request.launch(client);
+    let client = client.clone();
+    Box::pin(async move { Ok(JobHandle::new::<R>(client, launched.await?)) })
+}
+
+/// Blocking variant of [`launch`].
+pub fn launch_sync<R: AsyncJob>(client: &Client, request: &R) -> Result<JobHandle<R::Output>> {
+    let launched = request.launch_sync(client)?;
+    Ok(JobHandle::new::<R>(client.clone(), launched))
+}
+
+/// A handle on a job launched earlier, e.g. by another process or before a
+/// [`JobTimedOut`]. `R` is the launch request type.
+pub fn resume<R: AsyncJob>(
+    client: &Client,
+    async_job_id: impl Into<String>,
+) -> JobHandle<R::Output> {
+    JobHandle::new::<R>(client.clone(), Launch::AsyncJobId(async_job_id.into()))
+}
+
+type Check<T> = fn(&Client, String) -> BoxFuture<'static, Result<JobState<T>>>;
+type CheckSync<T> = fn(&Client, String) -> Result<JobState<T>>;
+
+/// An operation that has either completed or is running as an async job.
+pub struct JobHandle<T> {
+    client: Client,
+    launch: Launch<T>,
+    check: Check<T>,
+    check_sync: CheckSync<T>,
+    first_delay: Duration,
+    max_delay: Duration,
+    deadline: Option<Duration>,
+}
+
+impl<T> std::fmt::Debug for JobHandle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobHandle")
+            .field("async_job_id", &self.async_job_id())
+            .field("first_delay", &self.first_delay)
+            .field("max_delay", &self.max_delay)
+            .field("deadline", &self.deadline)
+            .finish()
+    }
+}
+
+impl<T> JobHandle<T> {
+    fn new<R: AsyncJob<Output = T>>(client: Client, launch: Launch<T>) -> Self {
+        Self {
+            client,
+            launch,
+            check: R::check,
+            check_sync: R::check_sync,
+            first_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(5),
+            deadline: Some(Duration::from_secs(600)),
+        }
+    }
+
+    /// The id to poll, or `None` if the operation finished at launch.
+    pub fn async_job_id(&self) -> Option<&str> {
+        match &self.launch {
+            Launch::Complete(_) => None,
+            Launch::AsyncJobId(id) => Some(id),
+        }
+    }
+
+    /// Wait `first` before the first poll, doubling up to `max` between
+    /// later ones. Defaults to 500 ms and 5 s.
+    pub fn with_backoff(self, first: Duration, max: Duration) -> Self {
+        Self {
+            first_delay: first,
+            max_delay: max.max(first),
+            ..self
+        }
+    }
+
+    /// Give up with [`JobTimedOut`] once the job has been polled for this
+    /// long; `None` waits indefinitely. Defaults to 10 minutes.
+    pub fn with_deadline(self, deadline: Option<Duration>) -> Self {
+        Self { deadline, ..self }
+    }
+
+    /// Poll until the job completes and return its result.
+    pub async fn wait(self) -> Result<T> {
+        let id = match self.launch {
+            Launch::Complete(output) => return Ok(output),
+            Launch::AsyncJobId(id) => id,
+        };
+        let mut schedule = Schedule::new(self.first_delay, self.max_delay, self.deadline);
+        loop {
+            let delay = schedule.next_delay(&id)?;
+            tokio::time::sleep(delay).await;
+            match (self.check)(&self.client, id.clone()).await? {
+                JobState::InProgress => continue,
+                JobState::Complete(output) => return Ok(output),
+                JobState::Failed(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Blocking variant of [`JobHandle::wait`].
+    pub fn wait_sync(self) -> Result<T> {
+        let id = match self.launch {
+            Launch::Complete(output) => return Ok(output),
+            Launch::AsyncJobId(id) => id,
+        };
+        let mut schedule = Schedule::new(self.first_delay, self.max_delay, self.deadline);
+        loop {
+            std::thread::sleep(schedule.next_delay(&id)?);
+            match (self.check_sync)(&self.client, id.clone())? {
+                JobState::InProgress => continue,
+                JobState::Complete(output) => return Ok(output),
+                JobState::Failed(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/// Delays between polls: doubling, capped, and cut short by the deadline.
+struct Schedule {
+    started: Instant,
+    delay: Duration,
+    max_delay: Duration,
+    deadline: Option<Duration>,
+}
+
+impl Schedule {
+    fn new(first: Duration, max: Duration, deadline: Option<Duration>) -> Self {
+        Self {
+            started: Instant::now(),
+            delay: first,
+            max_delay: max,
+            deadline,
+        }
+    }
+
+    /// How long to sleep before the next poll, or [`JobTimedOut`] if the
+    /// deadline has passed.
+    fn next_delay(&mut self, id: &str) -> Result<Duration> {
+        let mut delay = self.delay;
+        self.delay = (self.delay * 2).min(self.max_delay);
+        if let Some(deadline) = self.deadline {
+            let waited = self.started.elapsed();
+            if waited >= deadline {
+                return Err(JobTimedOut {
+                    async_job_id: id.to_string(),
+                    waited,
+                }
+                .into());
+            }
+            delay = delay.min(deadline - waited);
+        }
+        Ok(delay)
+    }
+}
+
+/// A launch response in [`Launch`] terms.
+trait IntoLaunch<T> {
+    fn into_launch(self, route: &'static str) -> Result<Launch<T>>;
+}
+
+/// A job-status response in [`JobState`] terms.
+trait IntoJobState<T> {
+    fn into_job_state(self, route: &'static str) -> JobState<T>;
+}
+
+/// A whole-job failure as an error carrying the typed reason.
+fn job_failed<E>(route: &'static str, failure: E) -> anyhow::Error
+where
+    E: std::fmt::Debug + Send + Sync + 'static,
+{
+    anyhow::Error::new(TypedError(failure)).context(format!("{route}: job failed"))
+}
+
+/// A response tag this SDK doesn't know, which can't be waited on.
+fn unknown_tag(route: &'static str) -> anyhow::Error {
+    anyhow::anyhow!("{route}: unrecognised response tag")
+}
+
+/// Implement [`AsyncJob`] for `$launch`, polling with the request `$check`
+/// builds from `$id`.
+macro_rules! impl_async_job {
+    ($launch:ty, $output:ty, $launch_route:literal, $check_route:literal, |$id:ident| $check:expr) => {
+        impl AsyncJob for $launch {
+            type Output = $output;
+
+            fn launch(&self, client: &Client) -> BoxFuture<'static, Result<Launch<$output>>> {
+                let call = client.execute(self);
+                Box::pin(async move {
+                    let response = call
+                        .await?
+                        .context(concat!($launch_route, " returned empty"))?;
+                    response.payload.into_launch($launch_route)
+                })
+            }
+
+            fn launch_sync(&self, client: &Client) -> Result<Launch<$output>> {
+                let response = client
+                    .execute_sync(self)?
+                    .context(concat!($launch_route, " returned empty"))?;
+                response.payload.into_launch($launch_route)
+            }
+
+            fn check(
+                client: &Client,
+                $id: String,
+            ) -> BoxFuture<'static, Result<JobState<$output>>> {
+                let call = client.execute(&$check);
+                Box::pin(async move {
+                    let response = call
+                        .await?
+                        .context(concat!($check_route, " returned empty"))?;
+                    Ok(response.payload.into_job_state($check_route))
+                })
+            }
+
+            fn check_sync(client: &Client, $id: String) -> Result<JobState<$output>> {
+                let response = client
+                    .execute_sync(&$check)?
+                    .context(concat!($check_route, " returned empty"))?;
+                Ok(response.payload.into_job_state($check_route))
+            }
+        }
+    };
+}
+
+/// `IntoLaunch` for `async_job_id` / `complete(T)` unions.
+macro_rules! impl_into_launch {
+    ($launch:ty, $output:ty) => {
+        impl IntoLaunch<$output> for $launch {
+            fn into_launch(self, _route: &'static str) -> Result<Launch<$output>> {
+                Ok(match self {
+                    Self::AsyncJobId { async_job_id } => Launch::AsyncJobId(async_job_id),
+                    Self::Complete(output) => Launch::Complete(output),
+                })
+            }
+        }
+    };
+}
+
+/// `IntoJobState` for `in_progress` / `complete(T)` unions, with or
+/// without a `failed` member.
+macro_rules! impl_into_job_state {
+    ($status:ty, $output:ty) => {
+        impl IntoJobState<$output> for $status {
+            fn into_job_state(self, _route: &'static str) -> JobState<$output> {
+                match self {
+                    Self::InProgress => JobState::InProgress,
+                    Self::Complete(output) => JobState::Complete(output),
+                }
+            }
+        }
+    };
+    ($status:ty, $output:ty, failed) => {
+        impl IntoJobState<$output> for $status {
+            fn into_job_state(self, route: &'static str) -> JobState<$output> {
+                match self {
+                    Self::InProgress => JobState::InProgress,
+                    Self::Complete(output) => JobState::Complete(output),
+                    Self::Failed { failed } => JobState::Failed(job_failed(route, failed)),
+                }
+            }
+        }
+    };
+}
+
+// ---- files ----
+
+impl_into_launch!(
+    files::RelocationBatchV2Launch,
+    files::RelocationBatchV2Result
+);
+impl_into_job_state!(files::CopyBatchCheckResult, files::RelocationBatchV2Result);
+impl_async_job!(
+    files::copy_batch::CopyBatchRequest<'_>,
+    files::RelocationBatchV2Result,
+    "files/copy_batch_v2",
+    "files/copy_batch/check_v2",
+    |async_job_id| files::copy_batch_check::CopyBatchRequest {
+        payload: Some(files::AsyncJobCheckArgs { async_job_id }),
+        ..Default::default()
+    }
+);
+
+impl_into_job_state!(files::MoveBatchJobStatus, files::RelocationBatchV2Result);
+impl_async_job!(
+    files::move_batch::MoveBatchRequest<'_>,
+    files::RelocationBatchV2Result,
+    "files/move_batch_v2",
+    "files/move_batch/check_v2",
+    |async_job_id| files::move_batch_check::MoveBatchCheckRequest {
+        payload: Some(files::AsyncJobCheckArgs { async_job_id }),
+        ..Default::default()
+    }
+);
+
+impl_into_launch!(files::DeleteBatchLaunch, files::DeleteBatchResult);
+impl_into_job_state!(
+    files::DeleteBatchJobStatus,
+    files::DeleteBatchResult,
+    failed
+);
+impl_async_job!(
+    files::delete_batch::DeleteBatchRequest<'_>,
+    files::DeleteBatchResult,
+    "files/delete_batch",
+    "files/delete_batch/check",
+    |async_job_id| files::delete_batch_check::DeleteBatchCheckRequest {
+        payload: Some(files::AsyncJobCheckArgs { async_job_id }),
+        ..Default::default()
+    }
+);
+
+impl_into_launch!(
+    files::CreateFolderBatchLaunch,
+    files::CreateFolderBatchResult
+);
+impl_into_job_state!(
+    files::CreateFolderBatchCheckResult,
+    files::CreateFolderBatchResult,
+    failed
+);
+impl_async_job!(
+    files::create_folder_batch::CreateFolderBatchRequest<'_>,
+    files::CreateFolderBatchResult,
+    "files/create_folder_batch",
+    "files/create_folder_batch/check",
+    |async_job_id| files::create_folder_batch_check::CreateFolderBatchCheckRequest {
+        payload: Some(files::AsyncJobCheckArgs { async_job_id }),
+        ..Default::default()
+    }
+);
+
+impl_into_launch!(files::SaveUrlResult, files::FileMetadata);
+impl_into_job_state!(files::SaveUrlJobStatus, files::FileMetadata, failed);
+impl_async_job!(
+    files::save_url::SaveUrlRequest<'_>,
+    files::FileMetadata,
+    "files/save_url",
+    "files/save_url/check_job_status",
+    |async_job_id| files::save_url_check_job_status::SaveUrlCheckJobStatusRequest {
+        payload: Some(files::AsyncJobCheckArgs { async_job_id }),
+        ..Default::default()
+    }
+);
+
+impl_into_launch!(
+    files::UploadSessionFinishBatchLaunch,
+    files::UploadSessionFinishBatchResult
+);
+impl_into_job_state!(
+    files::UploadSessionFinishBatchJobStatus,
+    files::UploadSessionFinishBatchResult
+);
+impl_async_job!(
+    files::upload_session_finish_batch::UploadSessionFinishBatchRequest<'_>,
+    files::UploadSessionFinishBatchResult,
+    "files/upload_session/finish_batch",
+    "files/upload_session/finish_batch/check",
+    |async_job_id| files::upload_session_finish_batch_check::UploadSessionFinishBatchCheckRequest {
+        payload: Some(files::PollArg { async_job_id }),
+        ..Default::default()
+    }
+);
+
+// ---- sharing ----
+
+impl_into_launch!(sharing::ShareFolderLaunch, sharing::SharedFolderMetadata);
+impl_into_job_state!(
+    sharing::ShareFolderJobStatus,
+    sharing::SharedFolderMetadata,
+    failed
+);
+impl_async_job!(
+    sharing::share_folder::ShareFolderRequest<'_>,
+    sharing::SharedFolderMetadata,
+    "sharing/share_folder",
+    "sharing/check_share_job_status",
+    |async_job_id| sharing::check_share_job_status::CheckShareJobStatusRequest {
+        payload: Some(sharing::PollArg { async_job_id }),
+        ..Default::default()
+    }
+);
+
+impl IntoLaunch<sharing::MemberAccessLevelResult> for sharing::LaunchResultBase {
+    fn into_launch(self, _route: &'static str) -> Result<Launch<sharing::MemberAccessLevelResult>> {
+        match self {
+            Self::AsyncJobId { async_job_id } => Ok(Launch::AsyncJobId(async_job_id)),
+        }
+    }
+}
+impl_into_job_state!(
+    sharing::RemoveMemberJobStatus,
+    sharing::MemberAccessLevelResult,
+    failed
+);
+impl_async_job!(
+    sharing::remove_folder_member::RemoveFolderMemberRequest<'_>,
+    sharing::MemberAccessLevelResult,
+    "sharing/remove_folder_member",
+    "sharing/check_remove_member_job_status",
+    |async_job_id| sharing::check_remove_member_job_status::CheckRemoveMemberJobStatusRequest {
+        payload: Some(sharing::PollArg { async_job_id }),
+        ..Default::default()
+    }
+);
+
+impl IntoLaunch<()> for sharing::LaunchEmptyResult {
+    fn into_launch(self, _route: &'static str) -> Result<Launch<()>> {
+        Ok(match self {
+            Self::AsyncJobId { async_job_id } => Launch::AsyncJobId(async_job_id),
+            Self::Complete => Launch::Complete(()),
+        })
+    }
+}
+impl IntoJobState<()> for sharing::JobStatus {
+    fn into_job_state(self, route: &'static str) -> JobState<()> {
+        match self {
+            Self::InProgress => JobState::InProgress,
+            Self::Complete => JobState::Complete(()),
+            Self::Failed { failed } => JobState::Failed(job_failed(route, failed)),
+        }
+    }
+}
+impl_async_job!(
+    sharing::unshare_folder::UnshareFolderRequest<'_>,
+    (),
+    "sharing/unshare_folder",
+    "sharing/check_job_status",
+    |async_job_id| sharing::check_job_status::CheckJobStatusRequest {
+        payload: Some(sharing::PollArg { async_job_id }),
+        ..Default::default()
+    }
+);
+
+// ---- team ----
+
+impl IntoLaunch<Vec<team::MemberAddV2Result>> for team::MembersAddLaunchV2Result {
+    fn into_launch(self, route: &'static str) -> Result<Launch<Vec<team::MemberAddV2Result>>> {
+        match self {
+            Self::AsyncJobId { async_job_id } => Ok(Launch::AsyncJobId(async_job_id)),
+            Self::Complete { complete } => Ok(Launch::Complete(complete)),
+            Self::Other => Err(unknown_tag(route)),
+        }
+    }
+}
+impl IntoJobState<Vec<team::MemberAddV2Result>> for team::MembersAddJobStatusV2Result {
+    fn into_job_state(self, route: &'static str) -> JobState<Vec<team::MemberAddV2Result>> {
+        match self {
+            Self::InProgress => JobState::InProgress,
+            Self::Complete { complete } => JobState::Complete(complete),
+            Self::Failed { failed } => JobState::Failed(job_failed(route, failed)),
+            Self::Other => JobState::Failed(unknown_tag(route)),
+        }
+    }
+}
+impl_async_job!(
+    team::members_add::MembersAddRequest<'_>,
+    Vec<team::MemberAddV2Result>,
+    "team/members/add_v2",
+    "team/members/add/job_status/get_v2",
+    |async_job_id| team::members_add_job_status_get::MembersAddJobStatusGetRequest {
+        payload: Some(sharing::PollArg { async_job_id }),
+        ..Default::default()
+    }
+);
+
+impl IntoLaunch<()> for team::LaunchEmptyResult {
+    fn into_launch(self, route: &'static str) -> Result<Launch<()>> {
+        match self {
+            Self::AsyncJobId { async_job_id } => Ok(Launch::AsyncJobId(async_job_id)),
+            Self::Complete => Ok(Launch::Complete(())),
+            Self::Other => Err(unknown_tag(route)),
+        }
+    }
+}
+impl IntoJobState<()> for team::PollEmptyResult {
+    fn into_job_state(self, route: &'static str) -> JobState<()> {
+        match self {
+            Self::InProgress => JobState::InProgress,
+            Self::Complete => JobState::Complete(()),
+            Self::Other => JobState::Failed(unknown_tag(route)),
+        }
+    }
+}
+impl_async_job!(
+    team::members_remove::MembersRemoveRequest<'_>,
+    (),
+    "team/members/remove",
+    "team/members/remove/job_status/get",
+    |async_job_id| team::members_remove_job_status_get::MembersRemoveJobStatusGetRequest {
+        payload: Some(sharing::PollArg { async_job_id }),
+        ..Default::default()
+    }
+);
+
+impl_async_job!(
+    team::groups_delete::GroupsDeleteRequest<'_>,
+    (),
+    "team/groups/delete",
+    "team/groups/job_status/get",
+    |async_job_id| team::groups_job_status_get::GroupsJobStatusGetRequest {
+        payload: Some(sharing::PollArg { async_job_id }),
+        ..Default::default()
+    }
+);
+
+/// Member changes always run in the background. The `group_info` in the
+/// launch response is a snapshot from before the job finishes, so it is
+/// dropped; read the group again once the handle completes.
+impl IntoLaunch<()> for team::GroupMembersChangeResult {
+    fn into_launch(self, _route: &'static str) -> Result<Launch<()>> {
+        Ok(Launch::AsyncJobId(self.async_job_id))
+    }
+}
+impl_async_job!(
+    team::groups_members_add::GroupsMembersAddRequest<'_>,
+    (),
+    "team/groups/members/add",
+    "team/groups/job_status/get",
+    |async_job_id| team::groups_job_status_get::GroupsJobStatusGetRequest {
+        payload: Some(sharing::PollArg { async_job_id }),
+        ..Default::default()
+    }
+);
+impl_async_job!(
+    team::groups_members_remove::GroupsMembersRemoveRequest<'_>,
+    (),
+    "team/groups/members/remove",
+    "team/groups/job_status/get",
+    |async_job_id| team::groups_job_status_get::GroupsJobStatusGetRequest {
+        payload: Some(sharing::PollArg { async_job_id }),
+        ..Default::default()
+    }
+);
+
+impl IntoLaunch<team::TeamFolderMetadata> for team::TeamFolderArchiveLaunch {
+    fn into_launch(self, route: &'static str) -> Result<Launch<team::TeamFolderMetadata>> {
+        match self {
+            Self::AsyncJobId { async_job_id } => Ok(Launch::AsyncJobId(async_job_id)),
+            Self::Complete(folder) => Ok(Launch::Complete(folder)),
+            Self::Other => Err(unknown_tag(route)),
+        }
+    }
+}
+impl IntoJobState<team::TeamFolderMetadata> for team::TeamFolderArchiveJobStatus {
+    fn into_job_state(self, route: &'static str) -> JobState<team::TeamFolderMetadata> {
+        match self {
+            Self::InProgress => JobState::InProgress,
+            Self::Complete(folder) => JobState::Complete(folder),
+            Self::Failed { failed } => JobState::Failed(job_failed(route, failed)),
+            Self::Other => JobState::Failed(unknown_tag(route)),
+        }
+    }
+}
+impl_async_job!(
+    team::team_folder_archive::TeamFolderArchiveRequest<'_>,
+    team::TeamFolderMetadata,
+    "team/team_folder/archive",
+    "team/team_folder/archive/check",
+    |async_job_id| team::team_folder_archive_check::TeamFolderArchiveCheckRequest {
+        payload: Some(sharing::PollArg { async_job_id }),
+        ..Default::default()
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests_utils::ScriptedTransport;
+
+    fn delete_batch() -> files::delete_batch::DeleteBatchRequest<'static> {
+        files::delete_batch::DeleteBatchRequest {
+            payload: Some(files::DeleteBatchArgs {
+                entries: vec![files::DeleteArg {
+                    path: "/old".into(),
+                    parent_rev: None,
+                }],
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn unshare_folder() -> sharing::unshare_folder::UnshareFolderRequest<'static> {
+        sharing::unshare_folder::UnshareFolderRequest {
+            payload: Some(sharing::UnshareFolderArg {
+                shared_folder_id: "84528192421".into(),
+                leave_a_copy: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    const DELETED: &str = r#"{".tag":"complete","entries":[{".tag":"success","metadata":{".tag":"folder","name":"old","id":"id:old"}}]}"#;
+
+    fn fast<T>(handle: JobHandle<T>) -> JobHandle<T> {
+        handle.with_backoff(Duration::from_millis(1), Duration::from_millis(2))
+    }
+
+    #[tokio::test]
+    async fn complete_at_launch_needs_no_poll() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], DELETED);
+        let client = Client::new("t").with_transport(fake.clone());
+
+        let handle = launch(&client, &delete_batch()).await.unwrap();
+        assert_eq!(handle.async_job_id(), None);
+        let result = handle.wait().await.unwrap();
+        assert!(matches!(
+            result.entries[..],
+            [files::DeleteBatchResultEntry::Success(_)]
+        ));
+        assert_eq!(fake.calls(), 1);
+    }
+
+    #[tokio::test]
+    async fn polls_the_check_route_until_complete() {
+        let fake = ScriptedTransport::new();
+        fake.reply(
+            200,
+            &[],
+            r#"{".tag":"async_job_id","async_job_id":"dbjid:1"}"#,
+        )
+        .reply(200, &[], r#"{".tag":"in_progress"}"#)
+        .reply(200, &[], DELETED);
+        let client = Client::new("t").with_transport(fake.clone());
+
+        let handle = fast(launch(&client, &delete_batch()).await.unwrap());
+        assert_eq!(handle.async_job_id(), Some("dbjid:1"));
+        assert_eq!(handle.wait().await.unwrap().entries.len(), 1);
+
+        let seen = fake.seen.lock().unwrap();
+        assert!(seen[0].url.ends_with("/2/files/delete_batch"));
+        assert!(seen[1].url.ends_with("/2/files/delete_batch/check"));
+        let body = String::from_utf8_lossy(seen[2].body.as_deref().unwrap()).into_owned();
+        assert_eq!(body, r#"{"async_job_id":"dbjid:1"}"#);
+    }
+
+    #[tokio::test]
+    async fn a_failed_job_carries_its_typed_reason() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], r#"{".tag":"async_job_id","async_job_id":"j"}"#)
+            .reply(
+                200,
+                &[],
+                r#"{".tag":"failed","failed":{".tag":"unshare_folder_error","unshare_folder_error":{".tag":"team_folder"}}}"#,
+            );
+        let client = Client::new("t").with_transport(fake);
+
+        let err = fast(launch(&client, &unshare_folder()).await.unwrap())
+            .wait()
+            .await
+            .unwrap_err();
+        let TypedError(reason) = err.downcast_ref::<TypedError<sharing::JobError>>().unwrap();
+        assert!(matches!(
+            reason,
+            sharing::JobError::UnshareFolderError {
+                unshare_folder_error: sharing::UnshareFolderError::TeamFolder
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn gives_up_at_the_deadline_with_a_resumable_id() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], r#"{".tag":"async_job_id","async_job_id":"j"}"#);
+        for _ in 0..50 {
+            fake.reply(200, &[], r#"{".tag":"in_progress"}"#);
+        }
+        let client = Client::new("t").with_transport(fake);
+
+        let err = fast(launch(&client, &unshare_folder()).await.unwrap())
+            .with_deadline(Some(Duration::from_millis(20)))
+            .wait()
+            .await
+            .unwrap_err();
+        let timed_out = err.downcast_ref::<JobTimedOut>().unwrap();
+        assert_eq!(timed_out.async_job_id, "j");
+        assert!(timed_out.waited >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn resume_sync_polls_an_existing_job() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], r#"{".tag":"in_progress"}"#)
+            .reply(200, &[], r#"{".tag":"complete"}"#);
+        let client = Client::new("t").with_transport(fake.clone());
+
+        let handle = resume::<sharing::unshare_folder::UnshareFolderRequest>(&client, "j");
+        fast(handle).wait_sync().unwrap();
+        let seen = fake.seen.lock().unwrap();
+        assert!(seen[0].url.ends_with("/2/sharing/check_job_status"));
+        assert_eq!(seen.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn polls_members_add_until_complete() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], r#"{".tag":"async_job_id","async_job_id":"mjid"}"#)
+            .reply(200, &[], r#"{".tag":"in_progress"}"#)
+            .reply(
+                200,
+                &[],
+                r#"{".tag":"complete","complete":[{".tag":"team_license_limit","team_license_limit":"a@example.com"}]}"#,
+            );
+        let client = Client::new("t").with_transport(fake.clone());
+
+        let request = team::members_add::MembersAddRequest {
+            payload: Some(team::MembersAddV2Arg {
+                new_members: vec![team::MemberAddV2Arg {
+                    member_email: "a@example.com".into(),
+                    ..Default::default()
+                }],
+                force_async: Some(true),
+            }),
+            ..Default::default()
+        };
+        let handle = fast(launch(&client, &request).await.unwrap());
+        assert_eq!(handle.async_job_id(), Some("mjid"));
+        let results = handle.wait().await.unwrap();
+        assert!(matches!(
+            &results[..],
+            [team::MemberAddV2Result::TeamLicenseLimit { team_license_limit }]
+                if team_license_limit == "a@example.com"
+        ));
+
+        let seen = fake.seen.lock().unwrap();
+        assert!(seen[0].url.ends_with("/2/team/members/add_v2"));
+        assert!(seen[1]
+            .url
+            .ends_with("/2/team/members/add/job_status/get_v2"));
+        let body = String::from_utf8_lossy(seen[2].body.as_deref().unwrap()).into_owned();
+        assert_eq!(body, r#"{"async_job_id":"mjid"}"#);
+    }
+
+    #[tokio::test]
+    async fn polls_group_member_changes_and_deletes_until_complete() {
+        const GROUP_INFO: &str = r#"{"group_name":"Eng","group_id":"g:1","group_management_type":{".tag":"user_managed"},"created":1,"members":[]}"#;
+        let fake = ScriptedTransport::new();
+        fake.reply(
+            200,
+            &[],
+            &format!(r#"{{"group_info":{GROUP_INFO},"async_job_id":"gjid"}}"#),
+        )
+        .reply(200, &[], r#"{".tag":"in_progress"}"#)
+        .reply(200, &[], r#"{".tag":"complete"}"#)
+        .reply(200, &[], r#"{".tag":"async_job_id","async_job_id":"djid"}"#)
+        .reply(200, &[], r#"{".tag":"complete"}"#);
+        let client = Client::new("t").with_transport(fake.clone());
+        let group = || team::GroupSelector::GroupId {
+            group_id: "g:1".into(),
+        };
+
+        let add = team::groups_members_add::GroupsMembersAddRequest {
+            payload: Some(team::GroupMembersAddArg {
+                group: group(),
+                members: vec![team::MemberAccess {
+                    user: team::UserSelectorArg::Email {
+                        email: "a@example.com".into(),
+                    },
+                    access_type: team::GroupAccessType::Member,
+                }],
+                return_members: None,
+            }),
+            ..Default::default()
+        };
+        let handle = fast(launch(&client, &add).await.unwrap());
+        assert_eq!(handle.async_job_id(), Some("gjid"));
+        handle.wait().await.unwrap();
+
+        let delete = team::groups_delete::GroupsDeleteRequest {
+            payload: Some(group()),
+            ..Default::default()
+        };
+        fast(launch(&client, &delete).await.unwrap())
+            .wait()
+            .await
+            .unwrap();
+
+        let seen = fake.seen.lock().unwrap();
+        assert!(seen[0].url.ends_with("/2/team/groups/members/add"));
+        assert!(seen[1].url.ends_with("/2/team/groups/job_status/get"));
+        let body = String::from_utf8_lossy(seen[2].body.as_deref().unwrap()).into_owned();
+        assert_eq!(body, r#"{"async_job_id":"gjid"}"#);
+        assert!(seen[3].url.ends_with("/2/team/groups/delete"));
+        let body = String::from_utf8_lossy(seen[4].body.as_deref().unwrap()).into_owned();
+        assert_eq!(body, r#"{"async_job_id":"djid"}"#);
+    }
+
+    #[test]
+    fn polls_team_folder_archive_until_complete_or_failed() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], r#"{".tag":"async_job_id","async_job_id":"ajid"}"#)
+            .reply(200, &[], r#"{".tag":"in_progress"}"#)
+            .reply(
+                200,
+                &[],
+                r#"{".tag":"complete","team_folder_id":"123","name":"Old","status":{".tag":"archived"},"is_team_shared_dropbox":false,"sync_setting":{".tag":"default"},"content_sync_settings":[]}"#,
+            )
+            .reply(200, &[], r#"{".tag":"async_job_id","async_job_id":"bjid"}"#)
+            .reply(
+                200,
+                &[],
+                r#"{".tag":"failed","failed":{".tag":"status_error","status_error":{".tag":"archived"}}}"#,
+            );
+        let client = Client::new("t").with_transport(fake.clone());
+        let request = team::team_folder_archive::TeamFolderArchiveRequest {
+            payload: Some(team::TeamFolderArchiveArg {
+                team_folder_id: "123".into(),
+                force_async_off: None,
+            }),
+            ..Default::default()
+        };
+
+        let folder = fast(launch_sync(&client, &request).unwrap())
+            .wait_sync()
+            .unwrap();
+        assert!(matches!(folder.status, team::TeamFolderStatus::Archived));
+
+        let err = fast(launch_sync(&client, &request).unwrap())
+            .wait_sync()
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<TypedError<team::BaseTeamFolderError>>()
+                .map(TypedError::get),
+            Some(team::BaseTeamFolderError::StatusError { .. })
+        ));
+
+        let seen = fake.seen.lock().unwrap();
+        assert!(seen[0].url.ends_with("/2/team/team_folder/archive"));
+        assert!(seen[1].url.ends_with("/2/team/team_folder/archive/check"));
+        let body = String::from_utf8_lossy(seen[4].body.as_deref().unwrap()).into_owned();
+        assert_eq!(body, r#"{"async_job_id":"bjid"}"#);
+    }
+
+    #[tokio::test]
+    async fn members_add_failure_and_unknown_status_are_errors() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], r#"{".tag":"failed","failed":"quota"}"#)
+            .reply(200, &[], r#"{".tag":"something_new"}"#);
+        let client = Client::new("t").with_transport(fake);
+
+        let err = fast(resume::<team::members_add::MembersAddRequest>(&client, "j"))
+            .wait()
+            .await
+            .unwrap_err();
+        let TypedError(reason) = err.downcast_ref::<TypedError<String>>().unwrap();
+        assert_eq!(reason, "quota");
+
+        let err = fast(resume::<team::members_add::MembersAddRequest>(&client, "j"))
+            .wait()
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("unrecognised"), "{err}");
+    }
+
+    #[test]
+    fn polls_members_remove_until_complete() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], r#"{".tag":"async_job_id","async_job_id":"rjid"}"#)
+            .reply(200, &[], r#"{".tag":"in_progress"}"#)
+            .reply(200, &[], r#"{".tag":"complete"}"#);
+        let client = Client::new("t").with_transport(fake.clone());
+
+        let request = team::members_remove::MembersRemoveRequest {
+            payload: Some(team::MembersRemoveArg {
+                user: team::UserSelectorArg::Email {
+                    email: "a@example.com".into(),
+                },
+                wipe_data: None,
+                transfer_dest_id: None,
+                transfer_admin_id: None,
+                keep_account: None,
+                retain_team_shares: None,
+            }),
+            ..Default::default()
+        };
+        fast(launch_sync(&client, &request).unwrap())
+            .wait_sync()
+            .unwrap();
+        let seen = fake.seen.lock().unwrap();
+        assert!(seen[1]
+            .url
+            .ends_with("/2/team/members/remove/job_status/get"));
+        assert_eq!(seen.len(), 3);
+    }
+}
@@ -6,4 +6,8 @@
 
 pub mod chunked_upload;
 pub mod download_stream;
+pub mod jobs;
+pub mod pagination;
+pub mod team_log;
 pub mod upload_stream;
+pub mod watch;
@@ -0,0 +1,835 @@
+//! Follow listing cursors without writing the loop.
+//!
+//! Every listing that continues through a cursor implements [`Paginate`]
+//! on its first request. [`pages`] turns that request into a `Stream` of
+//! [`Page`]s, calling the matching `*/continue` route until the server says
+//! there is no more, and [`Pages::items`] flattens it to the entries
+//! themselves. [`pages_sync`] is the blocking `Iterator` equivalent. Every
+//! call goes through [`Client::execute`], so refreshes and retries apply.
+//!
+//! ```ignore
+//! use futures::TryStreamExt;
+//!
+//! let request = ListFolderRequest {
+//!     payload: Some(args),
+//!     ..Default::default()
+//! };
+//! let mut entries = pagination::pages(&client, &request).items();
+//! while let Some(entry) = entries.try_next().await? {
+//!     index.add(entry)?;
+//! }
+//! // Later, pick up where this left off:
+//! let cursor = entries.cursor().map(str::to_owned);
+//! let more = pagination::pages_from::<ListFolderRequest>(&client, cursor.unwrap());
+//! ```
+//!
+//! `cursor()` only moves past a page once all of its items have been
+//! yielded, so resuming from it may repeat items but never skips any.
+
+use crate::api::{file_properties, file_requests, files, sharing, team, team_log};
+use crate::Client;
+use anyhow::{Context, Result};
+use futures::future::BoxFuture;
+use futures::stream::Stream;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{self, Poll};
+
+/// One response of a paginated listing.
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Continues the listing after this page. `files/list_folder` keeps
+    /// returning one on the last page, for picking up later changes.
+    pub cursor: Option<String>,
+    pub has_more: bool,
+}
+
+/// A first request whose listing continues through a cursor.
+pub trait Paginate {
+    type Item: Send + 'static;
+
+    /// Fetch the first page.
+    fn first_page(&self, client: &Client) -> BoxFuture<'static, Result<Page<Self::Item>>>;
+
+    /// Blocking variant of [`Paginate::first_page`].
+    fn first_page_sync(&self, client: &Client) -> Result<Page<Self::Item>>;
+
+    /// Fetch the page after `cursor` from the matching `*/continue` route.
+    fn next_page(client: &Client, cursor: String) -> BoxFuture<'static, Result<Page<Self::Item>>>;
+
+    /// Blocking variant of [`Paginate::next_page`].
+    fn next_page_sync(client: &Client, cursor: String) -> Result<Page<Self::Item>>;
+}
+
+/// Every page of the listing `first` starts.
+pub fn pages<R: Paginate>(client: &Client, first: &R) -> Pages<R::Item> {
+    Pages {
+        client: client.clone(),
+        next_page: R::next_page,
+        pending: Some(first.first_page(client)),
+        progress: Progress::default(),
+    }
+}
+
+/// The pages after `cursor`, as saved from an earlier run.
+pub fn pages_from<R: Paginate>(client: &Client, cursor: impl Into<String>) -> Pages<R::Item> {
+    Pages {
+        client: client.clone(),
+        next_page: R::next_page,
+        pending: None,
+        progress: Progress::resuming(cursor.into()),
+    }
+}
+
+/// Blocking variant of [`pages`]. The first call is made right away; its
+/// error, if any, comes out of the first `next()`.
+pub fn pages_sync<R: Paginate>(client: &Client, first: &R) -> PagesIter<R::Item> {
+    PagesIter {
+        client: client.clone(),
+        next_page: R::next_page_sync,
+        first: Some(first.first_page_sync(client)),
+        progress: Progress::default(),
+    }
+}
+
+/// Blocking variant of [`pages_from`].
+pub fn pages_from_sync<R: Paginate>(
+    client: &Client,
+    cursor: impl Into<String>,
+) -> PagesIter<R::Item> {
+    PagesIter {
+        client: client.clone(),
+        next_page: R::next_page_sync,
+        first: None,
+        progress: Progress::resuming(cursor.into()),
+    }
+}
+
+/// Where the listing stands between pages.
+#[derive(Debug, Default)]
+struct Progress {
+    cursor: Option<String>,
+    done: bool,
+}
+
+impl Progress {
+    fn resuming(cursor: String) -> Self {
+        Self {
+            cursor: Some(cursor),
+            done: false,
+        }
+    }
+
+    /// Cursor for the next `*/continue` call, if one is due.
+    fn next_cursor(&self) -> Option<String> {
+        match self.done {
+            true => None,
+            false => self.cursor.clone(),
+        }
+    }
+
+    /// Take in a page, or stop after an error. The cursor survives an
+    /// error, so the caller can resume from it.
+    fn record<T>(&mut self, outcome: &Result<Page<T>>) {
+        match outcome {
+            Ok(page) => {
+                self.cursor.clone_from(&page.cursor);
+                self.done = !page.has_more || page.cursor.is_none();
+            }
+            Err(_) => self.done = true,
+        }
+    }
+}
+
+type NextPage<T> = fn(&Client, String) -> BoxFuture<'static, Result<Page<T>>>;
+
+/// Stream of [`Page`]s. Ends after the last page or the first error.
+pub struct Pages<T> {
+    client: Client,
+    next_page: NextPage<T>,
+    pending: Option<BoxFuture<'static, Result<Page<T>>>>,
+    progress: Progress,
+}
+
+impl<T> Pages<T> {
+    /// The cursor of the last page received, or the one resumed from.
+    pub fn cursor(&self) -> Option<&str> {
+        self.progress.cursor.as_deref()
+    }
+
+    /// Stream the pages' items instead.
+    pub fn items(self) -> Items<T> {
+        Items {
+            pages: self,
+            buffered: VecDeque::new(),
+            resume: None,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for Pages<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pages")
+            .field("progress", &self.progress)
+            .field("pending", &self.pending.is_some())
+            .finish()
+    }
+}
+
+impl<T> Stream for Pages<T> {
+    type Item = Result<Page<T>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let pending = match &mut this.pending {
+            Some(pending) => pending,
+            None => match this.progress.next_cursor() {
+                Some(cursor) => this.pending.insert((this.next_page)(&this.client, cursor)),
+                None => return Poll::Ready(None),
+            },
+        };
+        let outcome = futures::ready!(pending.as_mut().poll(cx));
+        this.pending = None;
+        this.progress.record(&outcome);
+        Poll::Ready(Some(outcome))
+    }
+}
+
+/// Stream of the items of every page.
+pub struct Items<T> {
+    pages: Pages<T>,
+    buffered: VecDeque<T>,
+    /// Cursor from before the buffered page.
+    resume: Option<String>,
+}
+
+// Items are moved out of the buffer, never pinned in place.
+impl<T> Unpin for Items<T> {}
+
+impl<T> Items<T> {
+    /// Resume point covering every item yielded so far. While a page is
+    /// still being yielded this is the cursor from before it.
+    pub fn cursor(&self) -> Option<&str> {
+        match self.buffered.is_empty() {
+            true => self.pages.cursor(),
+            false => self.resume.as_deref(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for Items<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Items")
+            .field("pages", &self.pages)
+            .field("buffered", &self.buffered.len())
+            .finish()
+    }
+}
+
+impl<T> Stream for Items<T> {
+    type Item = Result<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(item) = this.buffered.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+            let before = this.pages.progress.cursor.clone();
+            match futures::ready!(Pin::new(&mut this.pages).poll_next(cx)) {
+                Some(Ok(page)) => {
+                    this.resume = before;
+                    this.buffered.extend(page.items);
+                }
+                Some(Err(err)) => return Poll::Ready(Some(Err(err))),
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+type NextPageSync<T> = fn(&Client, String) -> Result<Page<T>>;
+
+/// Blocking iterator of [`Page`]s. Ends after the last page or the first
+/// error.
+pub struct PagesIter<T> {
+    client: Client,
+    next_page: NextPageSync<T>,
+    first: Option<Result<Page<T>>>,
+    progress: Progress,
+}
+
+impl<T> PagesIter<T> {
+    /// The cursor of the last page received, or the one resumed from.
+    pub fn cursor(&self) -> Option<&str> {
+        self.progress.cursor.as_deref()
+    }
+
+    /// Iterate over the pages' items instead.
+    pub fn items(self) -> ItemsIter<T> {
+        ItemsIter {
+            pages: self,
+            buffered: VecDeque::new(),
+            resume: None,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for PagesIter<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PagesIter")
+            .field("progress", &self.progress)
+            .finish()
+    }
+}
+
+impl<T> Iterator for PagesIter<T> {
+    type Item = Result<Page<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let outcome = match self.first.take() {
+            Some(first) => first,
+            None => (self.next_page)(&self.client, self.progress.next_cursor()?),
+        };
+        self.progress.record(&outcome);
+        Some(outcome)
+    }
+}
+
+/// Blocking iterator over the items of every page.
+pub struct ItemsIter<T> {
+    pages: PagesIter<T>,
+    buffered: VecDeque<T>,
+    resume: Option<String>,
+}
+
+impl<T> ItemsIter<T> {
+    /// Resume point covering every item yielded so far; see
+    /// [`Items::cursor`].
+    pub fn cursor(&self) -> Option<&str> {
+        match self.buffered.is_empty() {
+            true => self.pages.cursor(),
+            false => self.resume.as_deref(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for ItemsIter<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ItemsIter")
+            .field("pages", &self.pages)
+            .field("buffered", &self.buffered.len())
+            .finish()
+    }
+}
+
+impl<T> Iterator for ItemsIter<T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.buffered.pop_front() {
+                return Some(Ok(item));
+            }
+            let before = self.pages.progress.cursor.clone();
+            match self.pages.next()? {
+                Ok(page) => {
+                    self.resume = before;
+                    self.buffered.extend(page.items);
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+/// One entry of `sharing/list_folder_members` or
+/// `sharing/list_file_members`, which list users, groups and invitees
+/// separately on each page.
+#[derive(Debug)]
+pub enum Member {
+    User(sharing::UserMembershipInfo),
+    Group(sharing::GroupMembershipInfo),
+    Invitee(sharing::InviteeMembershipInfo),
+}
+
+/// A listing response, split into its entries and where to go next.
+trait IntoPage<T> {
+    fn into_page(self) -> Page<T>;
+}
+
+/// Implement [`Paginate`] for `$first`, continuing with the request `$next`
+/// builds from `$cursor`.
+macro_rules! impl_paginate {
+    ($first:ty, $item:ty, $first_route:literal, $next_route:literal, |$cursor:ident| $next:expr) => {
+        impl Paginate for $first {
+            type Item = $item;
+
+            fn first_page(&self, client: &Client) -> BoxFuture<'static, Result<Page<$item>>> {
+                let call = client.execute(self);
+                Box::pin(async move {
+                    let response = call
+                        .await?
+                        .context(concat!($first_route, " returned empty"))?;
+                    Ok(response.payload.into_page())
+                })
+            }
+
+            fn first_page_sync(&self, client: &Client) -> Result<Page<$item>> {
+                let response = client
+                    .execute_sync(self)?
+                    .context(concat!($first_route, " returned empty"))?;
+                Ok(response.payload.into_page())
+            }
+
+            fn next_page(
+                client: &Client,
+                $cursor: String,
+            ) -> BoxFuture<'static, Result<Page<$item>>> {
+                let call = client.execute(&$next);
+                Box::pin(async move {
+                    let response = call
+                        .await?
+                        .context(concat!($next_route, " returned empty"))?;
+                    Ok(response.payload.into_page())
+                })
+            }
+
+            fn next_page_sync(client: &Client, $cursor: String) -> Result<Page<$item>> {
+                let response = client
+                    .execute_sync(&$next)?
+                    .context(concat!($next_route, " returned empty"))?;
+                Ok(response.payload.into_page())
+            }
+        }
+    };
+}
+
+/// `IntoPage` for results with `cursor: String` and `has_more`.
+macro_rules! impl_into_page {
+    ($result:ty, $field:ident, $item:ty) => {
+        impl IntoPage<$item> for $result {
+            fn into_page(self) -> Page<$item> {
+                Page {
+                    items: self.$field,
+                    cursor: Some(self.cursor),
+                    has_more: self.has_more,
+                }
+            }
+        }
+    };
+}
+
+/// `IntoPage` for results whose optional cursor is present only when there
+/// is more to fetch.
+macro_rules! impl_into_page_until_no_cursor {
+    ($result:ty, $field:ident, $item:ty) => {
+        impl IntoPage<$item> for $result {
+            fn into_page(self) -> Page<$item> {
+                Page {
+                    has_more: self.cursor.is_some(),
+                    items: self.$field,
+                    cursor: self.cursor,
+                }
+            }
+        }
+    };
+}
+
+// ---- files ----
+
+impl_into_page!(files::ListFolderResult, entries, files::Metadata);
+impl_into_page!(files::ListFolderContinueResult, entries, files::Metadata);
+impl_paginate!(
+    files::list_folder::ListFolderRequest<'_>,
+    files::Metadata,
+    "files/list_folder",
+    "files/list_folder/continue",
+    |cursor| files::list_folders_continue::ListFolderContinueRequest {
+        payload: Some(files::ListFolderContinueArgs { cursor }),
+        ..Default::default()
+    }
+);
+
+impl IntoPage<files::SearchMatchV2> for files::SearchV2Result {
+    fn into_page(self) -> Page<files::SearchMatchV2> {
+        Page {
+            items: self.matches,
+            cursor: self.cursor,
+            has_more: self.has_more,
+        }
+    }
+}
+impl_paginate!(
+    files::search::SearchRequest<'_>,
+    files::SearchMatchV2,
+    "files/search_v2",
+    "files/search/continue_v2",
+    |cursor| files::search_continue::SearchContinueRequest {
+        payload: Some(files::SearchV2ContinueArg { cursor }),
+        ..Default::default()
+    }
+);
+
+// ---- file_requests ----
+
+impl_into_page!(
+    file_requests::ListFileRequestsResult,
+    file_requests,
+    file_requests::FileRequest
+);
+impl_into_page!(
+    file_requests::ListFileRequestsContinueResult,
+    file_requests,
+    file_requests::FileRequest
+);
+impl_paginate!(
+    file_requests::list::ListRequest<'_>,
+    file_requests::FileRequest,
+    "file_requests/list_v2",
+    "file_requests/list/continue",
+    |cursor| file_requests::list_continue::ListContinueRequest {
+        payload: Some(file_requests::ListFileRequestsContinueArgs { cursor }),
+        ..Default::default()
+    }
+);
+
+// ---- file_properties ----
+
+impl_into_page_until_no_cursor!(
+    file_properties::PropertiesSearchResult,
+    matches,
+    file_properties::PropertiesSearchMatch
+);
+impl_paginate!(
+    file_properties::properties_search::PropertiesSearchRequest<'_>,
+    file_properties::PropertiesSearchMatch,
+    "file_properties/properties/search",
+    "file_properties/properties/search/continue",
+    |cursor| file_properties::properties_search_continue::PropertiesSearchContinueRequest {
+        payload: Some(file_properties::PropertiesSearchContinueArg { cursor }),
+        ..Default::default()
+    }
+);
+
+// ---- sharing ----
+
+impl_into_page_until_no_cursor!(
+    sharing::ListFoldersResult,
+    entries,
+    sharing::SharedFolderMetadata
+);
+impl_paginate!(
+    sharing::list_folders::ListFoldersRequest<'_>,
+    sharing::SharedFolderMetadata,
+    "sharing/list_folders",
+    "sharing/list_folders/continue",
+    |cursor| sharing::list_folders_continue::ListFoldersContinueRequest {
+        payload: Some(sharing::ListFoldersContinueArg { cursor }),
+        ..Default::default()
+    }
+);
+impl_paginate!(
+    sharing::list_mountable_folders::ListMountableFoldersRequest<'_>,
+    sharing::SharedFolderMetadata,
+    "sharing/list_mountable_folders",
+    "sharing/list_mountable_folders/continue",
+    |cursor| sharing::list_mountable_folders_continue::ListMountableFoldersContinueRequest {
+        payload: Some(sharing::ListFoldersContinueArg { cursor }),
+        ..Default::default()
+    }
+);
+
+impl_into_page_until_no_cursor!(
+    sharing::ListReceivedFilesResult,
+    entries,
+    sharing::SharedFileMetadata
+);
+impl_paginate!(
+    sharing::list_received_files::ListReceivedFilesRequest<'_>,
+    sharing::SharedFileMetadata,
+    "sharing/list_received_files",
+    "sharing/list_received_files/continue",
+    |cursor| sharing::list_received_files_continue::ListReceivedFilesContinueRequest {
+        payload: Some(sharing::ListFoldersContinueArg { cursor }),
+        ..Default::default()
+    }
+);
+
+/// Users, then groups, then invitees.
+fn members(
+    users: Vec<sharing::UserMembershipInfo>,
+    groups: Vec<sharing::GroupMembershipInfo>,
+    invitees: Vec<sharing::InviteeMembershipInfo>,
+    cursor: Option<String>,
+) -> Page<Member> {
+    let items = users
+        .into_iter()
+        .map(Member::User)
+        .chain(groups.into_iter().map(Member::Group))
+        .chain(invitees.into_iter().map(Member::Invitee))
+        .collect();
+    Page {
+        items,
+        has_more: cursor.is_some(),
+        cursor,
+    }
+}
+
+impl IntoPage<Member> for sharing::ListFolderMembersResult {
+    fn into_page(self) -> Page<Member> {
+        members(self.users, self.groups, self.invitees, self.cursor)
+    }
+}
+impl_paginate!(
+    sharing::list_folder_members::ListFolderMembersRequest<'_>,
+    Member,
+    "sharing/list_folder_members",
+    "sharing/list_folder_members/continue",
+    |cursor| sharing::list_folder_members_continue::ListFolderMembersContinueRequest {
+        payload: Some(sharing::ListFolderMembersContinueArg { cursor }),
+        ..Default::default()
+    }
+);
+
+impl IntoPage<Member> for sharing::ListFileMembersResult {
+    fn into_page(self) -> Page<Member> {
+        members(self.users, self.groups, self.invitees, self.cursor)
+    }
+}
+impl_paginate!(
+    sharing::list_file_members::ListFileMembersRequest<'_>,
+    Member,
+    "sharing/list_file_members",
+    "sharing/list_file_members/continue",
+    |cursor| sharing::list_file_members_continue::ListFileMembersContinueRequest {
+        payload: Some(sharing::ListFileMembersContinueArg { cursor }),
+        ..Default::default()
+    }
+);
+
+impl IntoPage<sharing::SharedLinkMetadata> for sharing::ListSharedLinksResult {
+    fn into_page(self) -> Page<sharing::SharedLinkMetadata> {
+        Page {
+            items: self.links,
+            cursor: self.cursor,
+            has_more: self.has_more,
+        }
+    }
+}
+// `list_shared_links` has no `/continue` route; the cursor goes back to
+// the same one.
+impl_paginate!(
+    sharing::list_shared_links::ListSharedLinksRequest<'_>,
+    sharing::SharedLinkMetadata,
+    "sharing/list_shared_links",
+    "sharing/list_shared_links",
+    |cursor| sharing::list_shared_links::ListSharedLinksRequest {
+        payload: Some(sharing::ListSharedLinksArg {
+            path: None,
+            cursor: Some(cursor),
+            direct_only: None,
+        }),
+        ..Default::default()
+    }
+);
+
+// ---- team ----
+
+impl_into_page!(team::MembersListV2Result, members, team::TeamMemberInfoV2);
+impl_paginate!(
+    team::members_list::MembersListRequest<'_>,
+    team::TeamMemberInfoV2,
+    "team/members/list_v2",
+    "team/members/list/continue_v2",
+    |cursor| team::members_list_continue::MembersListContinueRequest {
+        payload: Some(team::MembersListContinueArg { cursor }),
+        ..Default::default()
+    }
+);
+
+impl_into_page!(team::GroupsListResult, groups, team::GroupSummary);
+impl_paginate!(
+    team::groups_list::GroupsListRequest<'_>,
+    team::GroupSummary,
+    "team/groups/list",
+    "team/groups/list/continue",
+    |cursor| team::groups_list_continue::GroupsListContinueRequest {
+        payload: Some(team::GroupsListContinueArg { cursor }),
+        ..Default::default()
+    }
+);
+
+impl_into_page!(
+    team::GroupsMembersListResult,
+    members,
+    team::GroupMemberInfo
+);
+impl_paginate!(
+    team::groups_members_list::GroupsMembersListRequest<'_>,
+    team::GroupMemberInfo,
+    "team/groups/members/list",
+    "team/groups/members/list/continue",
+    |cursor| team::groups_members_list_continue::GroupsMembersListContinueRequest {
+        payload: Some(team::GroupsMembersListContinueArg { cursor }),
+        ..Default::default()
+    }
+);
+
+impl_into_page!(
+    team::TeamFolderListResult,
+    team_folders,
+    team::TeamFolderMetadata
+);
+impl_paginate!(
+    team::team_folder_list::TeamFolderListRequest<'_>,
+    team::TeamFolderMetadata,
+    "team/team_folder/list",
+    "team/team_folder/list/continue",
+    |cursor| team::team_folder_list_continue::TeamFolderListContinueRequest {
+        payload: Some(team::TeamFolderListContinueArg { cursor }),
+        ..Default::default()
+    }
+);
+
+impl_into_page!(
+    team::TeamNamespacesListResult,
+    namespaces,
+    team::NamespaceMetadata
+);
+impl_paginate!(
+    team::namespaces_list::NamespacesListRequest<'_>,
+    team::NamespaceMetadata,
+    "team/namespaces/list",
+    "team/namespaces/list/continue",
+    |cursor| team::namespaces_list_continue::NamespacesListContinueRequest {
+        payload: Some(team::TeamNamespacesListContinueArg { cursor }),
+        ..Default::default()
+    }
+);
+
+// ---- team_log ----
+
+impl_into_page!(team_log::GetTeamEventsResult, events, team_log::TeamEvent);
+impl_paginate!(
+    team_log::get_events::GetEventsRequest<'_>,
+    team_log::TeamEvent,
+    "team_log/get_events",
+    "team_log/get_events/continue",
+    |cursor| team_log::get_events_continue::GetEventsContinueRequest {
+        payload: Some(team_log::GetTeamEventsContinueArg { cursor }),
+        ..Default::default()
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests_utils::ScriptedTransport;
+    use futures::TryStreamExt;
+
+    fn list_folder() -> files::list_folder::ListFolderRequest<'static> {
+        files::list_folder::ListFolderRequest {
+            payload: Some(files::ListFolderArgs {
+                path: "/Photos".into(),
+                recursive: None,
+                include_media_info: None,
+                include_deleted: None,
+                include_has_explicit_shared_members: None,
+                include_mounted_folders: None,
+                limit: None,
+                shared_link: None,
+                include_property_groups: None,
+                include_non_downloadable_files: None,
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn folders(names: &[&str], cursor: &str, has_more: bool) -> String {
+        let entries: Vec<String> = names
+            .iter()
+            .map(|name| format!(r#"{{".tag":"folder","name":"{name}","id":"id:{name}"}}"#))
+            .collect();
+        format!(
+            r#"{{"entries":[{}],"cursor":"{cursor}","has_more":{has_more}}}"#,
+            entries.join(",")
+        )
+    }
+
+    fn name(entry: &files::Metadata) -> &str {
+        match entry {
+            files::Metadata::Folder(folder) => &folder.name,
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn items_follow_the_cursor_and_expose_a_safe_resume_point() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], &folders(&["a", "b"], "c1", true))
+            .reply(200, &[], &folders(&["c"], "c2", false));
+        let client = Client::new("t").with_transport(fake.clone());
+
+        let mut items = pages(&client, &list_folder()).items();
+        assert_eq!(name(&items.try_next().await.unwrap().unwrap()), "a");
+        // Resuming here would have to start over, or "b" is lost.
+        assert_eq!(items.cursor(), None);
+        assert_eq!(name(&items.try_next().await.unwrap().unwrap()), "b");
+        assert_eq!(items.cursor(), Some("c1"));
+        assert_eq!(name(&items.try_next().await.unwrap().unwrap()), "c");
+        assert!(items.try_next().await.unwrap().is_none());
+        assert_eq!(items.cursor(), Some("c2"));
+
+        let seen = fake.seen.lock().unwrap();
+        assert!(seen[0].url.ends_with("/2/files/list_folder"));
+        assert!(seen[1].url.ends_with("/2/files/list_folder/continue"));
+        assert_eq!(seen[1].body.as_deref(), Some(&br#"{"cursor":"c1"}"#[..]));
+    }
+
+    #[test]
+    fn listings_without_has_more_stop_when_the_cursor_does() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], r#"{"entries":[],"cursor":"s1"}"#)
+            .reply(200, &[], r#"{"entries":[]}"#);
+        let client = Client::new("t").with_transport(fake.clone());
+
+        let request = sharing::list_folders::ListFoldersRequest {
+            payload: Some(sharing::ListFoldersArgs {
+                limit: Some(1),
+                actions: None,
+            }),
+            ..Default::default()
+        };
+        let pages: Vec<_> = pages_sync(&client, &request)
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(pages.len(), 2);
+        assert!(pages[0].has_more);
+        assert!(!pages[1].has_more);
+        assert_eq!(fake.calls(), 2);
+        assert!(fake.seen.lock().unwrap()[1]
+            .url
+            .ends_with("/2/sharing/list_folders/continue"));
+    }
+
+    #[test]
+    fn an_error_ends_the_iterator_and_keeps_the_cursor() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], &folders(&["a"], "c1", true)).reply(
+            409,
+            &[],
+            r#"{"error_summary":"reset/..","error":{".tag":"reset"}}"#,
+        );
+        let client = Client::new("t").with_transport(fake.clone());
+
+        let mut items =
+            pages_from_sync::<files::list_folder::ListFolderRequest>(&client, "c0").items();
+        assert_eq!(name(&items.next().unwrap().unwrap()), "a");
+        assert!(items.next().unwrap().is_err());
+        assert!(items.next().is_none());
+        assert_eq!(items.cursor(), Some("c1"));
+        assert_eq!(fake.calls(), 2);
+    }
+}
@@ -0,0 +1,115 @@
+//! Follow the team audit log across pages.
+//!
+//! `team_log/get_events` returns at most 1000 events per call and a cursor
+//! for the rest. These streams chain `get_events` and
+//! `get_events/continue` until `has_more` is false. Every page goes through
+//! [`Client::execute`], so an export that runs for hours keeps refreshing
+//! its token and retrying like any other call.
+//!
+//! ```ignore
+//! use futures::TryStreamExt;
+//!
+//! let arg = GetTeamEventsArg {
+//!     category: Some(EventCategory::Logins),
+//!     ..Default::default()
+//! };
+//! let mut events = team_log::events(&client, arg);
+//! while let Some(event) = events.try_next().await? {
+//!     siem.send(&event)?;
+//! }
+//! ```
+//!
+//! To checkpoint a long export, store the stream's `cursor()` now and then;
+//! [`pages_from`] resumes from it. These are [`pagination`] streams for
+//! `team_log/get_events`.
+
+use super::pagination::{self, Items, Pages};
+use crate::api::team_log::get_events::GetEventsRequest;
+use crate::api::team_log::{GetTeamEventsArg, TeamEvent};
+use crate::Client;
+
+/// Every event matching `arg`, oldest first.
+pub fn events(client: &Client, arg: GetTeamEventsArg) -> Items<TeamEvent> {
+    pages(client, arg).items()
+}
+
+/// Pages of events matching `arg`. The stream ends after the first page
+/// with `has_more == false`, or after the first error.
+pub fn pages(client: &Client, arg: GetTeamEventsArg) -> Pages<TeamEvent> {
+    let request = GetEventsRequest {
+        payload: Some(arg),
+        ..Default::default()
+    };
+    pagination::pages(client, &request)
+}
+
+/// Pages after `cursor`, as saved from an earlier [`pages`] run.
+pub fn pages_from(client: &Client, cursor: impl Into<String>) -> Pages<TeamEvent> {
+    pagination::pages_from::<GetEventsRequest>(client, cursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::team_log::{EventCategory, EventDetails};
+    use crate::tests_utils::ScriptedTransport;
+    use futures::TryStreamExt;
+
+    fn page(events: &[&str], cursor: &str, has_more: bool) -> String {
+        let events: Vec<String> = events
+            .iter()
+            .map(|tag| {
+                format!(
+                    r#"{{"timestamp":"2024-01-01T00:00:00Z","event_category":{{".tag":"file_operations"}},"event_type":{{".tag":"{tag}","description":"-"}},"details":{{".tag":"{tag}_details"}}}}"#
+                )
+            })
+            .collect();
+        format!(
+            r#"{{"events":[{}],"cursor":"{cursor}","has_more":{has_more}}}"#,
+            events.join(",")
+        )
+    }
+
+    #[tokio::test]
+    async fn events_follow_the_cursor() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], &page(&["file_add", "file_delete"], "c1", true))
+            .reply(200, &[], &page(&["file_rename_later"], "c2", false));
+        let client = Client::new("t").with_transport(fake.clone());
+
+        let arg = GetTeamEventsArg {
+            category: Some(EventCategory::FileOperations),
+            ..Default::default()
+        };
+        let events: Vec<TeamEvent> = events(&client, arg).try_collect().await.unwrap();
+
+        let tags: Vec<_> = events.iter().map(|e| e.event_type.tag.as_str()).collect();
+        assert_eq!(tags, ["file_add", "file_delete", "file_rename_later"]);
+        assert!(matches!(events[0].details, EventDetails::Known(_)));
+        assert!(matches!(events[2].details, EventDetails::Other(_)));
+
+        let seen = fake.seen.lock().unwrap();
+        assert!(seen[0].url.ends_with("/2/team_log/get_events"));
+        assert!(seen[1].url.ends_with("/2/team_log/get_events/continue"));
+        assert_eq!(seen[1].body.as_deref(), Some(&br#"{"cursor":"c1"}"#[..]));
+    }
+
+    #[tokio::test]
+    async fn stream_stops_at_the_first_error() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], &page(&["file_add"], "c1", true)).reply(
+            409,
+            &[],
+            r#"{"error_summary":"reset/..","error":{".tag":"reset","reset":"2024-01-01T00:00:00Z"}}"#,
+        );
+        let client = Client::new("t").with_transport(fake.clone());
+
+        let mut pages = pages_from(&client, "c0");
+        let first = pages.try_next().await.unwrap().unwrap();
+        assert_eq!(first.cursor.as_deref(), Some("c1"));
+        assert!(pages.try_next().await.is_err());
+        assert!(pages.try_next().await.unwrap().is_none());
+        assert_eq!(pages.cursor(), Some("c1"));
+        assert_eq!(fake.calls(), 2);
+    }
+}
@@ -6,11 +6,15 @@
 //! don't want to buffer the whole thing into a `Vec<u8>` to hand to
 //! `UploadRequest.data`.
 
-use crate::api::files::{FileMetadata, UploadArg, WriteMode};
+use crate::api::files::{FileMetadata, UploadArg, UploadError, WriteMode};
+use crate::content_hash::{self, ContentHasher};
 use crate::endpoints::{get_endpoint_url, Endpoint};
+use crate::transport::HttpRequest;
+use crate::Client;
 use anyhow::{Context, Result};
 use bytes::Bytes;
-use futures::stream;
+use futures::stream::{self, StreamExt};
+use std::sync::{Arc, Mutex};
 use tokio::io::{AsyncRead, AsyncReadExt};
 
 const CHUNK_SIZE: usize = 64 * 1024;
@@ -28,6 +32,52 @@ pub async fn upload_stream<R>(
 where
     R: AsyncRead + Send + Sync + Unpin + 'static,
 {
+    upload_stream_with(&Client::new(token), path, reader, mode).await
+}
+
+/// [`upload_stream`] through a [`Client`]: uses its transport settings and
+/// refreshes the token first if it has expired. The body is streamed once,
+/// so a 401 mid-upload is returned rather than replayed.
+pub async fn upload_stream_with<R>(
+    client: &Client,
+    path: &str,
+    reader: R,
+    mode: WriteMode,
+) -> Result<FileMetadata>
+where
+    R: AsyncRead + Send + Sync + Unpin + 'static,
+{
+    upload(client, path, reader, mode, false).await
+}
+
+/// [`upload_stream_with`], hashing the body as it is sent and comparing it
+/// with the `content_hash` Dropbox reports for the new file. The hash is
+/// only known once the body has gone, so on a mismatch the file has already
+/// been committed: the [`ContentHashMismatch`](content_hash::ContentHashMismatch)
+/// error means it must be uploaded again.
+pub async fn upload_stream_verified<R>(
+    client: &Client,
+    path: &str,
+    reader: R,
+    mode: WriteMode,
+) -> Result<FileMetadata>
+where
+    R: AsyncRead + Send + Sync + Unpin + 'static,
+{
+    upload(client, path, reader, mode, true).await
+}
+
+async fn upload<R>(
+    client: &Client,
+    path: &str,
+    reader: R,
+    mode: WriteMode,
+    verify: bool,
+) -> Result<FileMetadata>
+where
+    R: AsyncRead + Send + Sync + Unpin + 'static,
+{
+    client.ensure_fresh().await?;
     let arg = UploadArg {
         path: path.to_string(),
         mode,
@@ -42,44 +92,72 @@ where
 
     // Adapter: AsyncRead -> Stream<Item = Result<Bytes, io::Error>>. No
     // tokio-util dep needed; each poll reads one chunk into a fresh Vec.
-    let body_stream = stream::unfold(reader, |mut reader| async move {
+    let hasher = Arc::new(Mutex::new(ContentHasher::new()));
+    let tap = verify.then(|| Arc::clone(&hasher));
+    let body_stream = stream::unfold((reader, tap), |(mut reader, tap)| async move {
         let mut buf = vec![0u8; CHUNK_SIZE];
         match reader.read(&mut buf).await {
             Ok(0) => None,
             Ok(n) => {
                 buf.truncate(n);
-                Some((Ok::<Bytes, std::io::Error>(Bytes::from(buf)), reader))
+                if let Some(hasher) = &tap {
+                    hasher.lock().unwrap().update(&buf);
+                }
+                Some((Ok::<Bytes, std::io::Error>(Bytes::from(buf)), (reader, tap)))
             }
-            Err(e) => Some((Err(e), reader)),
+            Err(e) => Some((Err(e), (reader, tap))),
         }
     });
-    let body = reqwest::Body::wrap_stream(body_stream);
 
     let url = get_endpoint_url(Endpoint::FilesUploadPost)
         .2
         .unwrap_or_else(|| get_endpoint_url(Endpoint::FilesUploadPost).0);
 
-    let resp = crate::AsyncClient
-        .post(url)
-        .bearer_auth(token)
-        .header("Content-Type", "application/octet-stream")
-        .header("Dropbox-API-Arg", arg_json)
-        .body(body)
-        .send()
+    let mut request = HttpRequest {
+        url,
+        headers: vec![
+            (
+                "Authorization".to_string(),
+                format!("Bearer {}", client.token()),
+            ),
+            (
+                "Content-Type".to_string(),
+                "application/octet-stream".to_string(),
+            ),
+            ("Dropbox-API-Arg".to_string(), arg_json),
+        ],
+        body: None,
+    };
+    request.headers.extend(client.context_headers());
+    let resp = client
+        .send_http_streaming(
+            Endpoint::FilesUploadPost,
+            request,
+            Some(body_stream.boxed()),
+        )
         .await
         .context("upload request failed")?
-        .error_for_status()
+        .collect()
+        .await
+        .context("upload request failed")?;
+    resp.error_for_status::<UploadError>(Endpoint::FilesUploadPost)
         .context("upload returned non-2xx")?;
 
-    let meta: FileMetadata = resp.json().await.context("parse upload response")?;
+    let meta: FileMetadata = serde_json::from_slice(&resp.body).context("parse upload response")?;
+    if verify {
+        let actual = hasher.lock().unwrap().clone().finish();
+        content_hash::verify(path, meta.content_hash.as_deref(), actual)?;
+    }
     Ok(meta)
 }
 
 #[cfg(all(test, feature = "test-utils"))]
 mod tests {
-    use super::upload_stream;
+    use super::{upload_stream, upload_stream_verified};
     use crate::api::files::WriteMode;
+    use crate::content_hash::{content_hash, ContentHashMismatch};
     use crate::tests_utils::with_test_server_async;
+    use crate::Client;
     use std::io::Cursor;
 
     #[tokio::test]
@@ -105,4 +183,32 @@ mod tests {
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn verified_upload_reports_a_hash_mismatch() {
+        let meta_json = format!(
+            r#"{{"name":"f.txt","id":"id:abc","client_modified":"2025-01-01T00:00:00Z","server_modified":"2025-01-01T00:00:00Z","rev":"r1","size":5,"path_lower":"/f.txt","path_display":"/f.txt","is_downloadable":true,"content_hash":"{}"}}"#,
+            content_hash(b"hellp")
+        );
+
+        with_test_server_async(|mut server| async move {
+            let _mock = server
+                .mock("POST", "/2/files/upload")
+                .with_status(200)
+                .with_header("Content-Type", "application/json")
+                .with_body(meta_json)
+                .create_async()
+                .await;
+
+            let client = Client::new("test");
+            let reader = Cursor::new(b"hello".to_vec());
+            let err = upload_stream_verified(&client, "/f.txt", reader, WriteMode::Add)
+                .await
+                .unwrap_err();
+            let mismatch = err.downcast_ref::<ContentHashMismatch>().unwrap();
+            assert_eq!(mismatch.actual, content_hash(b"hello"));
+            assert_eq!(mismatch.path, "/f.txt");
+        })
+        .await;
+    }
 }
@@ -0,0 +1,505 @@
+//! Live changes under one or more folders, without polling `list_folder`.
+//!
+//! [`watch`] (or [`Watcher`] for several roots) returns a `Stream` of
+//! [`ChangeBatch`]es. Each root waits on `files/list_folder/longpoll` and,
+//! when it reports changes, reads them from `files/list_folder/continue`.
+//! The longpoll `backoff` is honoured. When Dropbox resets a cursor, the
+//! root is listed again from scratch and the first batch is marked
+//! [`ChangeBatch::reset`].
+//!
+//! ```ignore
+//! let mut changes = Watcher::new(&client, WatchOptions::default())
+//!     .root("/Photos")
+//!     .resume("/Documents", saved_cursor)
+//!     .changes();
+//! while let Some(batch) = changes.try_next().await? {
+//!     apply(&batch.root, batch.reset, &batch.entries);
+//!     save_cursor(&batch.root, &batch.cursor);
+//! }
+//! ```
+//!
+//! A longpoll holds its request open for [`WatchOptions::longpoll_timeout`]
+//! plus up to 90 seconds of jitter, so give the client a transport timeout
+//! above that, e.g. through [`Client::with_http_config`].
+
+use super::pagination::{Page, Paginate};
+use crate::api::files;
+use crate::errors::TypedError;
+use crate::Client;
+use anyhow::{Context, Result};
+use futures::stream::{self, BoxStream, SelectAll, Stream, StreamExt};
+use std::pin::Pin;
+use std::task::{self, Poll};
+use std::time::Duration;
+
+/// The range of `timeout` values `files/list_folder/longpoll` accepts.
+const LONGPOLL_MIN_SECS: u64 = 30;
+const LONGPOLL_MAX_SECS: u64 = 480;
+
+/// What to watch under each root, and how.
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    /// Include changes in subfolders. Defaults to `true`.
+    pub recursive: bool,
+    /// Start a root without a saved cursor by listing what is already
+    /// there, as a first batch. Defaults to `false`: only changes made after
+    /// the watch starts are reported.
+    pub initial_listing: bool,
+    /// How long each longpoll waits for a change. Dropbox accepts 30 s to
+    /// 480 s; values outside that range are clamped to it. Defaults to 30 s.
+    pub longpoll_timeout: Duration,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            recursive: true,
+            initial_listing: false,
+            longpoll_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Entries that changed under one root.
+#[derive(Debug)]
+pub struct ChangeBatch {
+    /// The watched path these entries are under.
+    pub root: String,
+    /// Added or modified files and folders, and deleted entries.
+    pub entries: Vec<files::Metadata>,
+    /// Save this to resume after the batch with [`Watcher::resume`].
+    pub cursor: String,
+    /// The root is being listed again from scratch, starting with this
+    /// batch; drop what was known about it. Set on the first batch of an
+    /// initial listing too.
+    pub reset: bool,
+}
+
+/// Several roots watched as one stream.
+#[derive(Debug)]
+pub struct Watcher {
+    client: Client,
+    options: WatchOptions,
+    roots: Vec<(String, Option<String>)>,
+}
+
+impl Watcher {
+    pub fn new(client: &Client, options: WatchOptions) -> Self {
+        Self {
+            client: client.clone(),
+            options,
+            roots: Vec::new(),
+        }
+    }
+
+    /// Watch `path` from now on (or from a listing of it, with
+    /// [`WatchOptions::initial_listing`]). `""` is the Dropbox root.
+    pub fn root(mut self, path: impl Into<String>) -> Self {
+        self.roots.push((path.into(), None));
+        self
+    }
+
+    /// Watch `path` from a cursor saved from an earlier [`ChangeBatch`].
+    pub fn resume(mut self, path: impl Into<String>, cursor: impl Into<String>) -> Self {
+        self.roots.push((path.into(), Some(cursor.into())));
+        self
+    }
+
+    /// Start watching. An error ends the stream for its root only; resume
+    /// that root from its last cursor to carry on.
+    pub fn changes(self) -> Changes {
+        let roots = self.roots.into_iter().map(|(path, cursor)| {
+            let root = Root {
+                client: self.client.clone(),
+                options: self.options.clone(),
+                next: match cursor {
+                    Some(cursor) => Next::Poll { cursor },
+                    None => Next::Start,
+                },
+                path,
+                backoff: None,
+            };
+            stream::unfold(root, Root::step).boxed()
+        });
+        Changes {
+            roots: stream::select_all(roots),
+        }
+    }
+}
+
+/// Watch a single root; see [`Watcher`] for more than one.
+pub fn watch(client: &Client, path: impl Into<String>, options: WatchOptions) -> Changes {
+    Watcher::new(client, options).root(path).changes()
+}
+
+/// The batches of every watched root, as they arrive.
+pub struct Changes {
+    roots: SelectAll<BoxStream<'static, Result<ChangeBatch>>>,
+}
+
+impl std::fmt::Debug for Changes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Changes")
+            .field("roots", &self.roots.len())
+            .finish()
+    }
+}
+
+impl Stream for Changes {
+    type Item = Result<ChangeBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.roots).poll_next(cx)
+    }
+}
+
+/// What a root does next.
+enum Next {
+    /// No cursor yet.
+    Start,
+    /// List from scratch; `reset` marks the first batch.
+    List {
+        reset: bool,
+    },
+    /// Read the page after `cursor`.
+    Continue {
+        cursor: String,
+    },
+    /// Wait for changes after `cursor`.
+    Poll {
+        cursor: String,
+    },
+    Done,
+}
+
+struct Root {
+    client: Client,
+    options: WatchOptions,
+    path: String,
+    next: Next,
+    /// Wait this long before the next longpoll, as the last one asked.
+    backoff: Option<Duration>,
+}
+
+impl Root {
+    /// Run until there is a batch to yield, or an error.
+    async fn step(mut self) -> Option<(Result<ChangeBatch>, Self)> {
+        loop {
+            let outcome = match std::mem::replace(&mut self.next, Next::Done) {
+                Next::Done => return None,
+                Next::Start if self.options.initial_listing => self.list(true).await,
+                Next::Start => self.latest_cursor().await.map(|_| None),
+                Next::List { reset } => self.list(reset).await,
+                Next::Continue { cursor } => self.read(cursor).await,
+                Next::Poll { cursor } => self.poll(cursor).await.map(|_| None),
+            };
+            match outcome {
+                Ok(Some(batch)) => return Some((Ok(batch), self)),
+                Ok(None) => continue,
+                Err(err) => {
+                    self.next = Next::Done;
+                    let err = err.context(format!("watch {:?}", self.path));
+                    return Some((Err(err), self));
+                }
+            }
+        }
+    }
+
+    fn args(&self) -> files::ListFolderArgs {
+        files::ListFolderArgs {
+            path: self.path.clone(),
+            recursive: Some(self.options.recursive),
+            include_media_info: None,
+            include_deleted: None,
+            include_has_explicit_shared_members: None,
+            include_mounted_folders: None,
+            limit: None,
+            shared_link: None,
+            include_property_groups: None,
+            include_non_downloadable_files: None,
+        }
+    }
+
+    async fn latest_cursor(&mut self) -> Result<()> {
+        let request = files::list_folder_get_latest_cursor::ListFolderGetLatestCursorRequest {
+            payload: Some(files::GetLatestCursorArgs {
+                path: self.path.clone(),
+                recursive: Some(self.options.recursive),
+                include_media_info: None,
+                include_deleted: None,
+                include_has_explicit_shared_members: None,
+                include_mounted_folders: None,
+                limit: None,
+                shared_link: None,
+                include_property_groups: None,
+                include_non_downloadable_files: None,
+            }),
+            ..Default::default()
+        };
+        let response = self
+            .client
+            .execute(&request)
+            .await?
+            .context("files/list_folder/get_latest_cursor returned empty")?;
+        self.next = Next::Poll {
+            cursor: response.payload.cursor,
+        };
+        Ok(())
+    }
+
+    async fn list(&mut self, reset: bool) -> Result<Option<ChangeBatch>> {
+        let request = files::list_folder::ListFolderRequest {
+            payload: Some(self.args()),
+            ..Default::default()
+        };
+        let page = request.first_page(&self.client).await?;
+        Ok(self.take(page, reset))
+    }
+
+    async fn read(&mut self, cursor: String) -> Result<Option<ChangeBatch>> {
+        type ListFolder = files::list_folder::ListFolderRequest<'static>;
+        match ListFolder::next_page(&self.client, cursor).await {
+            Ok(page) => Ok(self.take(page, false)),
+            Err(err) if is_continue_reset(&err) => {
+                self.next = Next::List { reset: true };
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn poll(&mut self, cursor: String) -> Result<()> {
+        if let Some(backoff) = self.backoff.take() {
+            tokio::time::sleep(backoff).await;
+        }
+        let request = files::list_folder_longpoll::ListFolderLongpollRequest {
+            payload: Some(files::ListFolderLongpollArgs {
+                cursor: cursor.clone(),
+                timeout: Some(
+                    self.options
+                        .longpoll_timeout
+                        .as_secs()
+                        .clamp(LONGPOLL_MIN_SECS, LONGPOLL_MAX_SECS),
+                ),
+            }),
+            ..Default::default()
+        };
+        let response = match self.client.execute(&request).await {
+            Ok(response) => response.context("files/list_folder/longpoll returned empty")?,
+            Err(err) if is_longpoll_reset(&err) => {
+                self.next = Next::List { reset: true };
+                return Ok(());
+            }
+            Err(err) => return Err(err),
+        };
+        let result = response.payload;
+        self.backoff = result.backoff.map(Duration::from_secs);
+        self.next = match result.changes {
+            true => Next::Continue { cursor },
+            false => Next::Poll { cursor },
+        };
+        Ok(())
+    }
+
+    /// Queue what follows `page` and turn it into a batch. Empty pages
+    /// only move the cursor, unless they start a fresh listing.
+    fn take(&mut self, page: Page<files::Metadata>, reset: bool) -> Option<ChangeBatch> {
+        let cursor = page.cursor.unwrap_or_default();
+        self.next = match page.has_more {
+            true => Next::Continue {
+                cursor: cursor.clone(),
+            },
+            false => Next::Poll {
+                cursor: cursor.clone(),
+            },
+        };
+        if page.items.is_empty() && !reset {
+            return None;
+        }
+        Some(ChangeBatch {
+            root: self.path.clone(),
+            entries: page.items,
+            cursor,
+            reset,
+        })
+    }
+}
+
+fn is_continue_reset(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<TypedError<files::ListFolderContinueError>>(),
+        Some(TypedError(files::ListFolderContinueError::Reset))
+    )
+}
+
+fn is_longpoll_reset(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<TypedError<files::ListFolderLongpollError>>(),
+        Some(TypedError(files::ListFolderLongpollError::Reset))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::TryStreamExt;
+
+    use crate::tests_utils::ScriptedTransport;
+
+    fn page(names: &[&str], cursor: &str, has_more: bool) -> String {
+        let entries: Vec<String> = names
+            .iter()
+            .map(|name| match name.strip_prefix('-') {
+                Some(name) => format!(r#"{{".tag":"deleted","name":"{name}"}}"#),
+                None => format!(r#"{{".tag":"folder","name":"{name}","id":"id:{name}"}}"#),
+            })
+            .collect();
+        format!(
+            r#"{{"entries":[{}],"cursor":"{cursor}","has_more":{has_more}}}"#,
+            entries.join(",")
+        )
+    }
+
+    fn names(batch: &ChangeBatch) -> Vec<String> {
+        batch
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                files::Metadata::Folder(folder) => folder.name.clone(),
+                files::Metadata::Deleted(deleted) => format!("-{}", deleted.name),
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect()
+    }
+
+    fn body(request: &crate::transport::HttpRequest) -> String {
+        String::from_utf8_lossy(request.body.as_deref().unwrap()).into_owned()
+    }
+
+    #[tokio::test]
+    async fn waits_on_longpoll_then_reads_the_changes() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], r#"{"cursor":"c0"}"#)
+            .reply(200, &[], r#"{"changes":false}"#)
+            .reply(200, &[], r#"{"changes":true}"#)
+            .reply(200, &[], &page(&["new"], "c1", true))
+            .reply(200, &[], &page(&["-old"], "c2", false));
+        let client = Client::new("t").with_transport(fake.clone());
+
+        let mut changes = watch(&client, "/Photos", WatchOptions::default());
+        let first = changes.try_next().await.unwrap().unwrap();
+        assert_eq!(
+            (first.root.as_str(), first.cursor.as_str()),
+            ("/Photos", "c1")
+        );
+        assert_eq!(names(&first), ["new"]);
+        assert!(!first.reset);
+        let second = changes.try_next().await.unwrap().unwrap();
+        assert_eq!(names(&second), ["-old"]);
+        assert_eq!(second.cursor, "c2");
+
+        let seen = fake.seen.lock().unwrap();
+        assert!(seen[0]
+            .url
+            .ends_with("/2/files/list_folder/get_latest_cursor"));
+        assert!(seen[1].url.ends_with("/2/files/list_folder/longpoll"));
+        assert_eq!(body(&seen[2]), r#"{"cursor":"c0","timeout":30}"#);
+        assert_eq!(seen[1].header("Authorization"), None);
+        assert!(seen[3].url.ends_with("/2/files/list_folder/continue"));
+        assert_eq!(body(&seen[4]), r#"{"cursor":"c1"}"#);
+    }
+
+    #[tokio::test]
+    async fn relists_the_root_after_a_cursor_reset() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], r#"{"changes":true}"#)
+            .reply(
+                409,
+                &[],
+                r#"{"error_summary":"reset/..","error":{".tag":"reset"}}"#,
+            )
+            .reply(200, &[], &page(&["a", "b"], "fresh", false));
+        let client = Client::new("t").with_transport(fake.clone());
+
+        let mut changes = Watcher::new(&client, WatchOptions::default())
+            .resume("/Docs", "stale")
+            .changes();
+        let batch = changes.try_next().await.unwrap().unwrap();
+        assert!(batch.reset);
+        assert_eq!(names(&batch), ["a", "b"]);
+        assert_eq!(batch.cursor, "fresh");
+
+        let seen = fake.seen.lock().unwrap();
+        assert_eq!(body(&seen[0]), r#"{"cursor":"stale","timeout":30}"#);
+        assert!(seen[2].url.ends_with("/2/files/list_folder"));
+        assert!(body(&seen[2]).contains(r#""path":"/Docs""#));
+    }
+
+    #[tokio::test]
+    async fn clamps_the_longpoll_timeout_to_what_dropbox_accepts() {
+        for (asked, sent) in [(5, 30), (120, 120), (3600, 480)] {
+            let fake = ScriptedTransport::new();
+            fake.reply(200, &[], r#"{"changes":true}"#)
+                .reply(200, &[], &page(&["x"], "c1", false));
+            let client = Client::new("t").with_transport(fake.clone());
+            let options = WatchOptions {
+                longpoll_timeout: Duration::from_secs(asked),
+                ..WatchOptions::default()
+            };
+
+            let mut changes = Watcher::new(&client, options).resume("", "c0").changes();
+            changes.try_next().await.unwrap().unwrap();
+            let seen = fake.seen.lock().unwrap();
+            assert_eq!(
+                body(&seen[0]),
+                format!(r#"{{"cursor":"c0","timeout":{sent}}}"#)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn honours_the_longpoll_backoff() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], r#"{"changes":false,"backoff":1}"#)
+            .reply(200, &[], r#"{"changes":true}"#)
+            .reply(200, &[], &page(&["x"], "c1", false));
+        let client = Client::new("t").with_transport(fake);
+
+        let started = std::time::Instant::now();
+        let mut changes = Watcher::new(&client, WatchOptions::default())
+            .resume("", "c0")
+            .changes();
+        changes.try_next().await.unwrap().unwrap();
+        assert!(started.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn merges_several_roots_and_ends_only_the_failing_one() {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], r#"{"changes":true}"#)
+            .reply(200, &[], &page(&["x"], "n1", false))
+            .reply(
+                409,
+                &[],
+                r#"{"error_summary":"path/not_found/..","error":{".tag":"path","path":{".tag":"not_found"}}}"#,
+            )
+            .reply(200, &[], r#"{"changes":true}"#)
+            .reply(200, &[], &page(&["y"], "n2", false));
+        let client = Client::new("t").with_transport(fake);
+
+        let mut changes = Watcher::new(&client, WatchOptions::default())
+            .resume("/a", "ca")
+            .resume("/b", "cb")
+            .changes();
+        let first = changes.try_next().await.unwrap().unwrap();
+        let err = changes.try_next().await.unwrap_err();
+        let failed = match first.root.as_str() {
+            "/a" => "/b",
+            _ => "/a",
+        };
+        assert!(format!("{err:#}").contains(&format!("watch {failed:?}")));
+        let second = changes.try_next().await.unwrap().unwrap();
+        assert_eq!(second.root, first.root);
+        assert_eq!(names(&second), ["y"]);
+    }
+}
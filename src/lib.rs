@@ -9,9 +9,7 @@
 //! # async fn run() -> anyhow::Result<()> {
 //! let token = std::env::var("DROPBOX_TOKEN")?;
 //! let client = rusty_dropbox_sdk::Client::new(token);
-//! let bearer = client.token();
 //! let req = api::files::list_folder::ListFolderRequest {
-//!     access_token: &bearer,
 //!     payload: Some(api::files::ListFolderArgs {
 //!         path: String::new(),
 //!         recursive: Some(false),
@@ -24,8 +22,9 @@
 //!         include_property_groups: None,
 //!         include_non_downloadable_files: None,
 //!     }),
+//!     ..Default::default()
 //! };
-//! let result = req.call().await?;
+//! let result = client.execute(&req).await?;
 //! println!("{:?}", result);
 //! # Ok(())
 //! # }
@@ -34,11 +33,24 @@
 //! # Organisation
 //!
 //! - [`api`] — request/response types grouped by Dropbox namespace
-//!   (`account`, `auth`, `check`, `contacts`, `file_properties`,
-//!   `file_requests`, `files`). Each endpoint has its own submodule
-//!   with a `*Request` struct you build and call.
+//!   (`account`, `auth`, `check`, `common`, `contacts`, `file_properties`,
+//!   `file_requests`, `files`, `openid`, `sharing`, `users`). Each endpoint
+//!   has its own submodule with a `*Request` struct you build and call.
+//! - [`api::team`] and [`api::team_log`] — the Business API surface for
+//!   team access tokens: team info, members, groups, namespaces, team
+//!   folders and the audit log. Run user endpoints as a member with
+//!   [`Client::with_select_user`] / [`Client::with_select_admin`].
+//! - [`helpers`] — multi-call workflows on top of the endpoints:
+//!   [`helpers::pagination`] follows any cursor listing as a `Stream` or
+//!   `Iterator`, [`helpers::jobs`] launches batch and team jobs and polls
+//!   them to completion, [`helpers::watch`] turns `list_folder/longpoll`
+//!   into a stream of changes, [`helpers::team_log`] pages through audit
+//!   events, and `chunked_upload`, `upload_stream` and `download_stream`
+//!   move large files.
 //! - Every request implements the [`Service`](api::Service) trait which
 //!   exposes both `call()` (async) and `call_sync()` (blocking) methods.
+//! - [`Client::execute`] / [`Client::execute_sync`] run any request with
+//!   the client's token, refreshing it as needed.
 //!
 //! # Reference
 //!
@@ -47,16 +59,32 @@
 pub mod api;
 pub mod auth;
 mod client;
+pub mod content_hash;
+mod dispatch;
 mod endpoints;
 mod errors;
 pub mod helpers;
 mod macros;
+mod middleware;
 mod models;
+mod rate_limit;
+mod retry;
+mod telemetry;
 mod tests_utils;
+pub mod token_store;
 mod traits;
+pub mod transport;
 
 pub use client::{Client, RefreshConfig};
-pub use errors::TypedError;
+pub use endpoints::Endpoint;
+pub use errors::{
+    ApiError, DropboxError, DropboxErrorKind, RateLimitReason, TypedError, UserMessage,
+};
+pub use middleware::{Middleware, ResponseInfo, Vetoed};
+pub use rate_limit::RateLimiter;
+pub use retry::RetryPolicy;
+pub use token_store::TokenStore;
+pub use transport::HttpConfig;
 
 /// Ergonomic re-exports. `use rusty_dropbox_sdk::prelude::*;` brings in the
 /// `Service` trait (so `request.call().await?` resolves), the `Client`
@@ -67,7 +95,7 @@ pub mod prelude {
     pub use crate::Client;
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "test-utils"))]
 static TEST_AUTH_TOKEN: &str = "12345";
 
 #[allow(unused)]
@@ -77,20 +105,8 @@ use std::sync::{Mutex, MutexGuard, OnceLock};
 // `use anyhow;` is intentional: it exposes `crate::anyhow` to dozens of
 // `src/models/**/*.rs` files which import `Result` via `crate::anyhow::Result`.
 #[allow(clippy::single_component_path_imports)]
-use {anyhow, lazy_static::lazy_static};
+use anyhow;
 
 /// User-Agent advertised on every request. Lets Dropbox support trace traffic
 /// from this SDK and lets you bump the version when filing issues against them.
 pub const USER_AGENT: &str = concat!("rusty_dropbox_sdk/", env!("CARGO_PKG_VERSION"));
-
-// Clients
-lazy_static! {
-    static ref SyncClient: reqwest::blocking::Client = reqwest::blocking::Client::builder()
-        .user_agent(USER_AGENT)
-        .build()
-        .expect("sync client");
-    static ref AsyncClient: reqwest::Client = reqwest::Client::builder()
-        .user_agent(USER_AGENT)
-        .build()
-        .expect("async client");
-}
@@ -7,221 +7,62 @@
 /// - `$resp_payload`: The type of the payload inside the response structure.
 /// - `$endpoints`: The API endpoint URLs, which may vary depending on conditions (sync/async).
 /// - `$headers`: A vector of headers to include in the request.
+/// - `$error` (optional): The endpoint's Dropbox error union. Defaults to
+///   `serde_json::Value` for endpoints whose error is `Void`.
 ///
-/// On non-2xx responses the response body is run through
-/// `$crate::errors::decode_dropbox_error::<serde_json::Value>` which parses the
-/// Dropbox `{"error": ..., "error_summary": ...}` envelope and includes both
-/// fields in the returned `anyhow::Error`. A future variant of this macro can
-/// accept a typed error parameter to enable `downcast_ref` pattern matching.
+/// The generated `call_with` / `call_sync_with` hand the request to
+/// `$crate::dispatch`, which owns token lookup, refresh and retries.
+/// `call` / `call_sync` run through a throwaway `Client` built from the
+/// request's own `access_token`, which has no refresh credentials: they
+/// never refresh, and a 401 is returned as is.
+///
+/// On non-2xx responses the returned `anyhow::Error` carries an `ApiError`,
+/// a structured `DropboxError` (status, route, `error_summary`,
+/// `user_message`, request id) and the Dropbox `{"error": ...}` union
+/// decoded as `TypedError<$error>`, all reachable with `downcast_ref`.
 #[macro_export]
 macro_rules! implement_service {
     ($req:ty, $resp:ident, $resp_payload:ty, $endpoints:expr, $headers:expr) => {
+        $crate::implement_service!(
+            $req,
+            $resp,
+            $resp_payload,
+            $endpoints,
+            $headers,
+            serde_json::Value
+        );
+    };
+    ($req:ty, $resp:ident, $resp_payload:ty, $endpoints:expr, $headers:expr, $error:ty) => {
         impl Service<$resp> for $req {
             // Synchronous call implementation
             fn call_sync(&self) -> Result<Option<$resp>> {
-                let mut endpoint = get_endpoint_url($endpoints).0;
-                if let Some(url) = get_endpoint_url($endpoints).1 {
-                    endpoint = url;
-                }
-
-                let headers: Vec<Headers> = $headers;
-
-                let mut response = SyncClient.post(endpoint).bearer_auth(self.access_token);
-
-                let is_content_endpoint = headers
-                    .iter()
-                    .any(|h| matches!(h, Headers::ContentTypeAppOctetStream));
-
-                for h in &headers {
-                    match h {
-                        Headers::DropboxApiArg(_) => {
-                            let temp_h = Headers::DropboxApiArg(
-                                serde_json::json!(self.payload().unwrap()).to_string(),
-                            );
-                            response = response.header(temp_h.get_str().0, temp_h.get_str().1);
-                        }
-                        _ => response = response.header(h.get_str().0, h.get_str().1),
-                    }
-                }
-
-                if !is_content_endpoint {
-                    if let Some(payload) = self.payload() {
-                        response = response.json(payload);
-                    }
-                } else if let Some(data) = self.content_body() {
-                    response = response.body(data.to_vec());
-                }
-
-                let mut attempts = 0u32;
-                let response = loop {
-                    let builder = response.try_clone().ok_or_else(|| {
-                        ApiError::Request(anyhow::anyhow!(
-                            "request body not clonable; retry unsupported"
-                        ))
-                    })?;
-                    let r = builder
-                        .send()
-                        .map_err(|err| ApiError::Request(err.into()))?;
-                    let status = r.status();
-                    let retryable = status == reqwest::StatusCode::TOO_MANY_REQUESTS
-                        || status.is_server_error();
-                    if !retryable || attempts >= 3 {
-                        break r;
-                    }
-                    let wait = r
-                        .headers()
-                        .get("Retry-After")
-                        .and_then(|v| v.to_str().ok())
-                        .and_then(|s| s.parse::<u64>().ok())
-                        .unwrap_or(1u64 << attempts); // exponential: 1s, 2s, 4s
-                    std::thread::sleep(std::time::Duration::from_secs(wait));
-                    attempts += 1;
-                };
-
-                let status = response.status();
-                let api_result_header = response
-                    .headers()
-                    .get("Dropbox-API-Result")
-                    .and_then(|v| v.to_str().ok())
-                    .map(|s| s.to_string());
-                let text = response
-                    .text()
-                    .map_err(|err| ApiError::Parsing(err.into()))?;
-
-                if !status.is_success() {
-                    let err =
-                        $crate::errors::decode_dropbox_error::<serde_json::Value>(status, &text);
-                    if status == reqwest::StatusCode::UNAUTHORIZED {
-                        return Err(ApiError::Unauthorized(err).into());
-                    }
-                    return Err(ApiError::DropBox(err).into());
-                }
-
-                let is_download_endpoint = headers
-                    .iter()
-                    .any(|h| matches!(h, Headers::DropboxApiResult));
-
-                let payload_source = if is_download_endpoint {
-                    api_result_header.unwrap_or_default()
-                } else {
-                    text
-                };
-
-                if payload_source.is_empty() {
-                    return Ok(None);
-                }
-
-                let response: $resp_payload = serde_json::from_str(&payload_source)
-                    .map_err(|err| ApiError::Parsing(err.into()))?;
-                let response = $resp { payload: response };
-                Ok(Some(response))
+                self.call_sync_with(&$crate::Client::new(self.access_token))
             }
 
             // Asynchronous call implementation
-            fn call(&self) -> Pin<Box<dyn Future<Output = Result<Option<$resp>>> + Send>> {
-                let mut endpoint = get_endpoint_url($endpoints).0;
-                if let Some(url) = get_endpoint_url($endpoints).2 {
-                    endpoint = url;
-                }
-
-                let headers: Vec<Headers> = $headers;
-
-                let mut response = AsyncClient.post(endpoint).bearer_auth(self.access_token);
-
-                let is_content_endpoint = headers
-                    .iter()
-                    .any(|h| matches!(h, Headers::ContentTypeAppOctetStream));
-
-                for h in &headers {
-                    match h {
-                        Headers::DropboxApiArg(_) => {
-                            let temp_h = Headers::DropboxApiArg(
-                                serde_json::json!(self.payload().unwrap()).to_string(),
-                            );
-                            response = response.header(temp_h.get_str().0, temp_h.get_str().1);
-                        }
-                        _ => response = response.header(h.get_str().0, h.get_str().1),
-                    }
-                }
-
-                if !is_content_endpoint {
-                    if let Some(payload) = &self.payload() {
-                        response = response.json(payload);
-                    }
-                } else if let Some(data) = self.content_body() {
-                    response = response.body(data.to_vec());
-                }
-
-                let is_download_endpoint = headers
-                    .iter()
-                    .any(|h| matches!(h, Headers::DropboxApiResult));
-
-                let block = async move {
-                    let mut attempts = 0u32;
-                    let response = loop {
-                        let builder = response.try_clone().ok_or_else(|| {
-                            ApiError::Request(anyhow::anyhow!(
-                                "request body not clonable; retry unsupported"
-                            ))
-                        })?;
-                        let r = builder
-                            .send()
-                            .await
-                            .map_err(|err| ApiError::Request(err.into()))?;
-                        let status = r.status();
-                        let retryable = status == reqwest::StatusCode::TOO_MANY_REQUESTS
-                            || status.is_server_error();
-                        if !retryable || attempts >= 3 {
-                            break r;
-                        }
-                        let wait = r
-                            .headers()
-                            .get("Retry-After")
-                            .and_then(|v| v.to_str().ok())
-                            .and_then(|s| s.parse::<u64>().ok())
-                            .unwrap_or(1u64 << attempts);
-                        tokio::time::sleep(std::time::Duration::from_secs(wait)).await;
-                        attempts += 1;
-                    };
-
-                    let status = response.status();
-                    let api_result_header = response
-                        .headers()
-                        .get("Dropbox-API-Result")
-                        .and_then(|v| v.to_str().ok())
-                        .map(|s| s.to_string());
-                    let text = response
-                        .text()
-                        .await
-                        .map_err(|err| ApiError::Parsing(err.into()))?;
-
-                    if !status.is_success() {
-                        let err = $crate::errors::decode_dropbox_error::<serde_json::Value>(
-                            status, &text,
-                        );
-                        if status == reqwest::StatusCode::UNAUTHORIZED {
-                            return Err(ApiError::Unauthorized(err).into());
-                        }
-                        return Err(ApiError::DropBox(err).into());
-                    }
-
-                    let payload_source = if is_download_endpoint {
-                        api_result_header.unwrap_or_default()
-                    } else {
-                        text
-                    };
-
-                    if payload_source.is_empty() {
-                        return Ok(None);
-                    }
+            fn call(&self) -> futures::future::BoxFuture<'static, Result<Option<$resp>>> {
+                self.call_with(&$crate::Client::new(self.access_token))
+            }
 
-                    let response: $resp_payload = serde_json::from_str(&payload_source)
-                        .map_err(|err| ApiError::Parsing(err.into()))?;
-                    let response = $resp { payload: response };
+            fn call_sync_with(&self, client: &$crate::Client) -> Result<Option<$resp>> {
+                let request = $crate::dispatch::PreparedRequest::new(self, $endpoints, $headers)?;
+                let response = $crate::dispatch::send_sync(client, &request)?;
+                let payload = response.into_payload::<$resp_payload, $error>(&request)?;
+                Ok(payload.map(|payload| $resp { payload }))
+            }
 
-                    Result::<Option<$resp>>::Ok(Some(response))
-                };
-                Box::pin(block)
+            fn call_with(
+                &self,
+                client: &$crate::Client,
+            ) -> futures::future::BoxFuture<'static, Result<Option<$resp>>> {
+                let request = $crate::dispatch::PreparedRequest::new(self, $endpoints, $headers);
+                let client = client.clone();
+                Box::pin(async move {
+                    let request = request?;
+                    let response = $crate::dispatch::send(&client, &request).await?;
+                    let payload = response.into_payload::<$resp_payload, $error>(&request)?;
+                    Result::<Option<$resp>>::Ok(payload.map(|payload| $resp { payload }))
+                })
             }
         }
     };
@@ -237,147 +78,46 @@ macro_rules! implement_service {
 #[macro_export]
 macro_rules! implement_download_service {
     ($req:ty, $resp:ident, $resp_payload:ty, $endpoints:expr, $headers:expr) => {
+        $crate::implement_download_service!(
+            $req,
+            $resp,
+            $resp_payload,
+            $endpoints,
+            $headers,
+            serde_json::Value
+        );
+    };
+    ($req:ty, $resp:ident, $resp_payload:ty, $endpoints:expr, $headers:expr, $error:ty) => {
         impl Service<$resp> for $req {
             fn call_sync(&self) -> Result<Option<$resp>> {
-                let mut endpoint = get_endpoint_url($endpoints).0;
-                if let Some(url) = get_endpoint_url($endpoints).1 {
-                    endpoint = url;
-                }
-
-                let headers: Vec<Headers> = $headers;
-                let mut response = SyncClient.post(endpoint).bearer_auth(self.access_token);
-
-                for h in &headers {
-                    match h {
-                        Headers::DropboxApiArg(_) => {
-                            let temp_h = Headers::DropboxApiArg(
-                                serde_json::json!(self.payload().unwrap()).to_string(),
-                            );
-                            response = response.header(temp_h.get_str().0, temp_h.get_str().1);
-                        }
-                        _ => response = response.header(h.get_str().0, h.get_str().1),
-                    }
-                }
-
-                let response = response
-                    .send()
-                    .map_err(|err| ApiError::Request(err.into()))?;
-
-                let status = response.status();
-                let api_result_header = response
-                    .headers()
-                    .get("Dropbox-API-Result")
-                    .and_then(|v| v.to_str().ok())
-                    .map(|s| s.to_string());
-                let bytes = response
-                    .bytes()
-                    .map_err(|err| ApiError::Parsing(err.into()))?;
-
-                if !status.is_success() {
-                    let text = String::from_utf8_lossy(&bytes);
-                    let err =
-                        $crate::errors::decode_dropbox_error::<serde_json::Value>(status, &text);
-                    if status == reqwest::StatusCode::UNAUTHORIZED {
-                        return Err(ApiError::Unauthorized(err).into());
-                    }
-                    return Err(ApiError::DropBox(err).into());
-                }
-
-                let header_json = api_result_header.unwrap_or_default();
-                if header_json.is_empty() {
-                    return Ok(None);
-                }
-                let payload: $resp_payload = serde_json::from_str(&header_json)
-                    .map_err(|err| ApiError::Parsing(err.into()))?;
-                Ok(Some($resp {
-                    payload,
-                    data: bytes.to_vec(),
-                }))
+                self.call_sync_with(&$crate::Client::new(self.access_token))
             }
 
-            fn call(&self) -> Pin<Box<dyn Future<Output = Result<Option<$resp>>> + Send>> {
-                let mut endpoint = get_endpoint_url($endpoints).0;
-                if let Some(url) = get_endpoint_url($endpoints).2 {
-                    endpoint = url;
-                }
-
-                let headers: Vec<Headers> = $headers;
-                let mut response = AsyncClient.post(endpoint).bearer_auth(self.access_token);
-
-                for h in &headers {
-                    match h {
-                        Headers::DropboxApiArg(_) => {
-                            let temp_h = Headers::DropboxApiArg(
-                                serde_json::json!(self.payload().unwrap()).to_string(),
-                            );
-                            response = response.header(temp_h.get_str().0, temp_h.get_str().1);
-                        }
-                        _ => response = response.header(h.get_str().0, h.get_str().1),
-                    }
-                }
-
-                let block = async move {
-                    let mut attempts = 0u32;
-                    let response = loop {
-                        let builder = response.try_clone().ok_or_else(|| {
-                            ApiError::Request(anyhow::anyhow!(
-                                "request body not clonable; retry unsupported"
-                            ))
-                        })?;
-                        let r = builder
-                            .send()
-                            .await
-                            .map_err(|err| ApiError::Request(err.into()))?;
-                        let status = r.status();
-                        let retryable = status == reqwest::StatusCode::TOO_MANY_REQUESTS
-                            || status.is_server_error();
-                        if !retryable || attempts >= 3 {
-                            break r;
-                        }
-                        let wait = r
-                            .headers()
-                            .get("Retry-After")
-                            .and_then(|v| v.to_str().ok())
-                            .and_then(|s| s.parse::<u64>().ok())
-                            .unwrap_or(1u64 << attempts);
-                        tokio::time::sleep(std::time::Duration::from_secs(wait)).await;
-                        attempts += 1;
-                    };
-
-                    let status = response.status();
-                    let api_result_header = response
-                        .headers()
-                        .get("Dropbox-API-Result")
-                        .and_then(|v| v.to_str().ok())
-                        .map(|s| s.to_string());
-                    let bytes = response
-                        .bytes()
-                        .await
-                        .map_err(|err| ApiError::Parsing(err.into()))?;
+            fn call(&self) -> futures::future::BoxFuture<'static, Result<Option<$resp>>> {
+                self.call_with(&$crate::Client::new(self.access_token))
+            }
 
-                    if !status.is_success() {
-                        let text = String::from_utf8_lossy(&bytes);
-                        let err = $crate::errors::decode_dropbox_error::<serde_json::Value>(
-                            status, &text,
-                        );
-                        if status == reqwest::StatusCode::UNAUTHORIZED {
-                            return Err(ApiError::Unauthorized(err).into());
-                        }
-                        return Err(ApiError::DropBox(err).into());
-                    }
+            fn call_sync_with(&self, client: &$crate::Client) -> Result<Option<$resp>> {
+                let request = $crate::dispatch::PreparedRequest::new(self, $endpoints, $headers)?;
+                let response = $crate::dispatch::send_sync(client, &request)?;
+                let download = response.into_download::<$resp_payload, $error>(&request)?;
+                Ok(download.map(|(payload, data)| $resp { payload, data }))
+            }
 
-                    let header_json = api_result_header.unwrap_or_default();
-                    if header_json.is_empty() {
-                        return Ok(None);
-                    }
-                    let payload: $resp_payload = serde_json::from_str(&header_json)
-                        .map_err(|err| ApiError::Parsing(err.into()))?;
-                    Result::<Option<$resp>>::Ok(Some($resp {
-                        payload,
-                        data: bytes.to_vec(),
-                    }))
-                };
-                Box::pin(block)
+            fn call_with(
+                &self,
+                client: &$crate::Client,
+            ) -> futures::future::BoxFuture<'static, Result<Option<$resp>>> {
+                let request = $crate::dispatch::PreparedRequest::new(self, $endpoints, $headers);
+                let client = client.clone();
+                Box::pin(async move {
+                    let request = request?;
+                    let response = $crate::dispatch::send(&client, &request).await?;
+                    let download = response.into_download::<$resp_payload, $error>(&request)?;
+                    Result::<Option<$resp>>::Ok(
+                        download.map(|(payload, data)| $resp { payload, data }),
+                    )
+                })
             }
         }
     };
@@ -0,0 +1,260 @@
+//! Request/response interceptors.
+//!
+//! A [`Middleware`] registered with
+//! [`Client::with_middleware`](crate::Client::with_middleware) sees every
+//! HTTP exchange the client makes: RPC and content endpoint calls, the OAuth
+//! refresh, and the streaming [`helpers`](crate::helpers). Hooks run once per
+//! attempt, so a retried call shows up as several exchanges.
+//!
+//! ```
+//! use rusty_dropbox_sdk::transport::HttpRequest;
+//! use rusty_dropbox_sdk::{Endpoint, Middleware, ResponseInfo};
+//!
+//! #[derive(Debug)]
+//! struct AuditLog;
+//!
+//! impl Middleware for AuditLog {
+//!     fn before(&self, endpoint: Endpoint, request: &mut HttpRequest) -> anyhow::Result<()> {
+//!         if endpoint == Endpoint::FilesPermanentlyDeletePost {
+//!             anyhow::bail!("permanent deletes are disabled");
+//!         }
+//!         request.headers.push(("X-Audit".into(), "1".into()));
+//!         Ok(())
+//!     }
+//!
+//!     fn after(&self, endpoint: Endpoint, response: &ResponseInfo<'_>) {
+//!         println!("{} -> {} in {:?}", endpoint.route(), response.status, response.elapsed);
+//!     }
+//! }
+//! ```
+//!
+//! Hooks are synchronous and run on the calling task; keep them cheap and
+//! hand slow work (shipping logs, pushing metrics) to a channel.
+
+use std::time::Duration;
+
+use crate::endpoints::Endpoint;
+use crate::transport::{find_header, HttpRequest};
+
+/// Interceptor around every HTTP exchange made through a
+/// [`Client`](crate::Client). All hooks default to doing nothing.
+pub trait Middleware: Send + Sync + std::fmt::Debug {
+    /// Runs before the request is sent. May add or change headers. Returning
+    /// an error vetoes the call: nothing is sent, nothing is retried, and the
+    /// caller gets the error wrapped in [`Vetoed`].
+    fn before(&self, endpoint: Endpoint, request: &mut HttpRequest) -> anyhow::Result<()> {
+        let _ = (endpoint, request);
+        Ok(())
+    }
+
+    /// Runs once the response status and headers are in, whatever the status.
+    fn after(&self, endpoint: Endpoint, response: &ResponseInfo<'_>) {
+        let _ = (endpoint, response);
+    }
+
+    /// Runs when no response came back at all (connection, TLS or timeout
+    /// errors from the transport).
+    fn failed(&self, endpoint: Endpoint, error: &anyhow::Error) {
+        let _ = (endpoint, error);
+    }
+}
+
+/// What [`Middleware::after`] gets to see of a response.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct ResponseInfo<'a> {
+    pub status: u16,
+    pub headers: &'a [(String, String)],
+    /// Time from handing the request to the transport until the headers
+    /// arrived.
+    pub elapsed: Duration,
+}
+
+impl<'a> ResponseInfo<'a> {
+    pub(crate) fn new(status: u16, headers: &'a [(String, String)], elapsed: Duration) -> Self {
+        Self {
+            status,
+            headers,
+            elapsed,
+        }
+    }
+
+    /// First value of header `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&'a str> {
+        find_header(self.headers, name)
+    }
+}
+
+/// Context on the error returned when a [`Middleware::before`] hook refused
+/// a request. The hook's own error is the cause underneath it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Vetoed {
+    pub endpoint: Endpoint,
+}
+
+impl std::fmt::Display for Vetoed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} vetoed by middleware", self.endpoint.route())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api;
+    use crate::tests_utils::ScriptedTransport;
+    use crate::{Client, RefreshConfig, RetryPolicy};
+    use std::sync::{Arc, Mutex};
+
+    const RAW_SPACE_USAGE: &str = r#"{"used":1,"allocation":{".tag":"individual","allocated":2}}"#;
+
+    /// Tags requests and records every hook call as a line.
+    #[derive(Debug, Default)]
+    struct Recorder {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+        veto: Option<Endpoint>,
+    }
+
+    impl Middleware for Recorder {
+        fn before(&self, endpoint: Endpoint, request: &mut HttpRequest) -> anyhow::Result<()> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} before {}", self.name, endpoint.route()));
+            if self.veto == Some(endpoint) {
+                anyhow::bail!("not allowed");
+            }
+            request
+                .headers
+                .push(("X-Seen-By".to_string(), self.name.to_string()));
+            Ok(())
+        }
+
+        fn after(&self, endpoint: Endpoint, response: &ResponseInfo<'_>) {
+            self.log.lock().unwrap().push(format!(
+                "{} after {} {}",
+                self.name,
+                endpoint.route(),
+                response.status
+            ));
+        }
+
+        fn failed(&self, endpoint: Endpoint, _error: &anyhow::Error) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} failed {}", self.name, endpoint.route()));
+        }
+    }
+
+    #[tokio::test]
+    async fn hooks_wrap_each_attempt_in_order() {
+        let fake = ScriptedTransport::new();
+        fake.fail("connection reset")
+            .reply(200, &[], RAW_SPACE_USAGE);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let client = Client::new("t")
+            .with_transport(fake.clone())
+            .with_retry_policy(RetryPolicy {
+                initial_backoff: std::time::Duration::from_millis(1),
+                ..RetryPolicy::default()
+            })
+            .with_middleware(Arc::new(Recorder {
+                name: "a",
+                log: log.clone(),
+                ..Default::default()
+            }))
+            .with_middleware(Arc::new(Recorder {
+                name: "b",
+                log: log.clone(),
+                ..Default::default()
+            }));
+
+        let req = api::users::get_space_usage::GetSpaceUsageRequest::default();
+        client.execute(&req).await.expect("retried");
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "a before users/get_space_usage",
+                "b before users/get_space_usage",
+                "b failed users/get_space_usage",
+                "a failed users/get_space_usage",
+                "a before users/get_space_usage",
+                "b before users/get_space_usage",
+                "b after users/get_space_usage 200",
+                "a after users/get_space_usage 200",
+            ]
+        );
+        let seen = fake.seen.lock().unwrap();
+        let tags: Vec<_> = seen[1]
+            .headers
+            .iter()
+            .filter(|(k, _)| k == "X-Seen-By")
+            .map(|(_, v)| v.as_str())
+            .collect();
+        assert_eq!(tags, ["a", "b"]);
+    }
+
+    #[test]
+    fn veto_stops_the_call_without_retrying() {
+        let fake = ScriptedTransport::new();
+        let client = Client::new("t")
+            .with_transport(fake.clone())
+            .with_middleware(Arc::new(Recorder {
+                name: "guard",
+                veto: Some(Endpoint::UsersGetSpaceUsagePost),
+                ..Default::default()
+            }));
+
+        let req = api::users::get_space_usage::GetSpaceUsageRequest::default();
+        let err = client.execute_sync(&req).expect_err("vetoed");
+        assert_eq!(
+            err.downcast_ref::<Vetoed>(),
+            Some(&Vetoed {
+                endpoint: Endpoint::UsersGetSpaceUsagePost
+            })
+        );
+        assert_eq!(fake.calls(), 0);
+    }
+
+    #[test]
+    fn token_refresh_goes_through_the_chain() {
+        let fake = ScriptedTransport::new();
+        fake.reply(
+            200,
+            &[],
+            r#"{"access_token":"new","expires_in":14400,"token_type":"bearer"}"#,
+        )
+        .reply(200, &[], RAW_SPACE_USAGE);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let client = Client::with_refresh(
+            "old",
+            0,
+            RefreshConfig {
+                client_id: "id".into(),
+                client_secret: Some("secret".into()),
+                refresh_token: "rt".into(),
+            },
+        )
+        .with_transport(fake.clone())
+        .with_middleware(Arc::new(Recorder {
+            name: "m",
+            log: log.clone(),
+            ..Default::default()
+        }));
+
+        let req = api::users::get_space_usage::GetSpaceUsageRequest::default();
+        client.execute_sync(&req).expect("refreshed");
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "m before oauth2/token",
+                "m after oauth2/token 200",
+                "m before users/get_space_usage",
+                "m after users/get_space_usage 200",
+            ]
+        );
+    }
+}
@@ -30,4 +30,6 @@ pub enum SetProfilePhotoError {
     DimensionError,
     ThumbnailError,
     TransientError,
+    #[serde(other)]
+    Other,
 }
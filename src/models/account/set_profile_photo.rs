@@ -3,27 +3,25 @@ use super::{SetProfilePhotoArg, SetProfilePhotoResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = SetProfilePhotoRequest<'a>;
 type Response = SetProfilePhotoResponse;
 type RequestPayload = SetProfilePhotoArg;
 type ResponsePayload = SetProfilePhotoResult;
+type ResponseError = super::SetProfilePhotoError;
 
 /// Set profile photo request
 /// <https://www.dropbox.com/developers/documentation/http/documentation#account-set_profile_photo>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct SetProfilePhotoRequest<'a> {
-    access_token: &'a str,
+    pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
 }
 
@@ -43,7 +41,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::AccountSetProfilePhotoPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -1,15 +1,12 @@
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = TokenRevokeRequest<'a>;
@@ -19,7 +16,7 @@ type ResponsePayload = ();
 
 /// Struct for token revoke
 /// <https://api.dropboxapi.com/2/auth/token/revoke>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct TokenRevokeRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -3,14 +3,11 @@ use super::{EchoArg, EchoResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = CheckUserRequest<'a>;
@@ -20,9 +17,9 @@ type ResponsePayload = EchoResult;
 
 /// Struct for user authentication
 /// <https://www.dropbox.com/developers/documentation/http/documentation#check-user>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct CheckUserRequest<'a> {
-    access_token: &'a str,
+    pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
 }
 
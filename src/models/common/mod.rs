@@ -0,0 +1,90 @@
+//! Types from the Dropbox `common` namespace shared across endpoints.
+//!
+//! Reference: <https://www.dropbox.com/developers/reference/path-root-header-modes>
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::users::RootInfo;
+
+/// Value of the `Dropbox-API-Path-Root` header: which namespace relative
+/// paths resolve against. Set it on a client with
+/// [`Client::with_path_root`](crate::Client::with_path_root).
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum PathRoot {
+    /// The user's home namespace (the default without the header).
+    Home,
+    /// The user's root namespace, e.g. the team space, validated against
+    /// the given root namespace id.
+    Root { root: String },
+    /// Any namespace the user has access to, e.g. a shared folder.
+    NamespaceId { namespace_id: String },
+}
+
+impl PathRoot {
+    pub fn home() -> Self {
+        Self::Home
+    }
+
+    pub fn root(namespace_id: impl Into<String>) -> Self {
+        Self::Root {
+            root: namespace_id.into(),
+        }
+    }
+
+    pub fn namespace_id(namespace_id: impl Into<String>) -> Self {
+        Self::NamespaceId {
+            namespace_id: namespace_id.into(),
+        }
+    }
+
+    /// The header value: the union as compact JSON.
+    pub fn header_value(&self) -> String {
+        serde_json::to_string(self).expect("PathRoot serializes")
+    }
+}
+
+/// Returned with HTTP 422 when the `Dropbox-API-Path-Root` header is
+/// rejected. Reachable as `TypedError<PathRootError>` on the error chain.
+#[derive(Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum PathRootError {
+    /// The root namespace changed; retry with the current `RootInfo`.
+    InvalidRoot { invalid_root: RootInfo },
+    /// No access to the namespace.
+    NoPermission,
+    #[serde(other)]
+    Other,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_root_header_values() {
+        assert_eq!(PathRoot::home().header_value(), r#"{".tag":"home"}"#);
+        assert_eq!(
+            PathRoot::root("123").header_value(),
+            r#"{".tag":"root","root":"123"}"#
+        );
+        assert_eq!(
+            PathRoot::namespace_id("456").header_value(),
+            r#"{".tag":"namespace_id","namespace_id":"456"}"#
+        );
+    }
+
+    #[test]
+    fn decodes_invalid_root() {
+        let err: PathRootError = serde_json::from_str(
+            r#"{".tag":"invalid_root","invalid_root":{".tag":"team","root_namespace_id":"1","home_namespace_id":"2","home_path":"/Me"}}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            err,
+            PathRootError::InvalidRoot {
+                invalid_root: RootInfo::Team(_)
+            }
+        ));
+    }
+}
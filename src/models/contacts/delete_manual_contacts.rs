@@ -1,14 +1,11 @@
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = DeleteManualContactsRequest<'a>;
@@ -18,9 +15,9 @@ type ResponsePayload = ();
 
 /// Struct for removing manually contacts batch
 /// <https://www.dropbox.com/developers/documentation/http/documentation#contacts-delete_manual_contacts>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct DeleteManualContactsRequest<'a> {
-    access_token: &'a str,
+    pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
 }
 
@@ -3,27 +3,24 @@ use super::DeleteManualContactsBatchArg;
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = DeleteManualContactsBatchRequest<'a>;
 type Response = DeleteManualContactsBatchResponse;
 type RequestPayload = Option<DeleteManualContactsBatchArg>;
 type ResponsePayload = ();
+type ResponseError = super::DeleteManualContactsError;
 
 /// Add properties struct for setting up a profile picture
 /// <https://www.dropbox.com/developers/documentation/http/documentation#contacts-delete_manual_contacts_batch>
-#[derive(Debug)]
-#[allow(dead_code)]
+#[derive(Debug, Default)]
 pub struct DeleteManualContactsBatchRequest<'a> {
-    access_token: &'a str,
+    pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
 }
 
@@ -43,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::ContactsDeleteManualContactsBatchPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -7,3 +7,14 @@ use serde::{Deserialize, Serialize};
 pub struct DeleteManualContactsBatchArg {
     pub email_addresses: Vec<String>,
 }
+
+/// Error union for `delete_manual_contacts_batch`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum DeleteManualContactsError {
+    ContactsNotFound {
+        contacts_not_found: Vec<String>,
+    },
+    #[serde(other)]
+    Other,
+}
@@ -77,6 +77,10 @@ pub struct PropertiesSearchMatch {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct PropertiesSearchResult {
     pub matches: Vec<PropertiesSearchMatch>,
+    /// Pass to `properties/search/continue` for more matches; absent on
+    /// the last page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -161,3 +165,138 @@ pub struct UpdateTemplateArg {
     pub name: String,
     pub template_id: String,
 }
+
+// =============================================================================
+// Error unions
+// =============================================================================
+
+/// Error union for `templates/get_for_user`, `list_for_user` and `remove_for_user`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TemplateError {
+    TemplateNotFound {
+        template_not_found: String,
+    },
+    RestrictedContent,
+    #[serde(other)]
+    Other,
+}
+
+/// Error union for `templates/add_for_user` and `update_for_user`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum ModifyTemplateError {
+    TemplateNotFound {
+        template_not_found: String,
+    },
+    RestrictedContent,
+    ConflictingPropertyNames,
+    TooManyProperties,
+    TooManyTemplates,
+    TemplateAttributeTooLarge,
+    #[serde(other)]
+    Other,
+}
+
+/// Error union for `properties/overwrite`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum InvalidPropertyGroupError {
+    TemplateNotFound {
+        template_not_found: String,
+    },
+    RestrictedContent,
+    Path {
+        path: crate::models::files::LookupError,
+    },
+    UnsupportedFolder,
+    PropertyFieldTooLarge,
+    DoesNotFitTemplate,
+    DuplicatePropertyGroups,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum AddPropertiesError {
+    TemplateNotFound {
+        template_not_found: String,
+    },
+    RestrictedContent,
+    Path {
+        path: crate::models::files::LookupError,
+    },
+    UnsupportedFolder,
+    PropertyFieldTooLarge,
+    DoesNotFitTemplate,
+    DuplicatePropertyGroups,
+    PropertyGroupAlreadyExists,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum UpdatePropertiesError {
+    TemplateNotFound {
+        template_not_found: String,
+    },
+    RestrictedContent,
+    Path {
+        path: crate::models::files::LookupError,
+    },
+    UnsupportedFolder,
+    PropertyFieldTooLarge,
+    DoesNotFitTemplate,
+    DuplicatePropertyGroups,
+    PropertyGroupLookup {
+        property_group_lookup: LookUpPropertiesError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum RemovePropertiesError {
+    TemplateNotFound {
+        template_not_found: String,
+    },
+    RestrictedContent,
+    Path {
+        path: crate::models::files::LookupError,
+    },
+    UnsupportedFolder,
+    PropertyGroupLookup {
+        property_group_lookup: LookUpPropertiesError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum LookUpPropertiesError {
+    PropertyGroupNotFound,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum PropertiesSearchError {
+    PropertyGroupLookup {
+        property_group_lookup: LookUpPropertiesError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum PropertiesSearchContinueError {
+    Reset,
+    #[serde(other)]
+    Other,
+}
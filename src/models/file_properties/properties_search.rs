@@ -3,24 +3,22 @@ use super::{PropertiesSearchArg, PropertiesSearchResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = PropertiesSearchRequest<'a>;
 type Response = PropertiesSearchResponse;
 type RequestPayload = PropertiesSearchArg;
 type ResponsePayload = PropertiesSearchResult;
+type ResponseError = super::PropertiesSearchError;
 
 /// Add properties struct for file request
 /// <https://www.dropbox.com/developers/documentation/http/documentation#file_properties-properties-search>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct PropertiesSearchRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FilePropertiesPropertiesSearchPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -3,24 +3,22 @@ use super::{AddTemplateArg, AddTemplateResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = TemplatesAddForUserRequest<'a>;
 type Response = TemplatesAddForUserResponse;
 type RequestPayload = AddTemplateArg;
 type ResponsePayload = AddTemplateResult;
+type ResponseError = super::ModifyTemplateError;
 
 /// Add properties struct for file request
 /// <https://www.dropbox.com/developers/documentation/http/documentation#file_properties-templates-add_for_user>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct TemplatesAddForUserRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FilePropertiesTemplatesAddForUserPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -2,25 +2,22 @@ use super::CountFileRequestsResult;
 
 use crate::{
     anyhow::Result,
-    endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = CountFilesRequest<'a>;
 type Response = CountFilesResponse;
 type RequestPayload = ();
 type ResponsePayload = CountFileRequestsResult;
+type ResponseError = super::GeneralFileRequestsError;
 
 /// Count files
 /// <https://www.dropbox.com/developers/documentation/http/documentation#file_requests-count>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct CountFilesRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +39,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FileRequestsCountPost,
-    vec![]
+    vec![],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -3,24 +3,22 @@ use super::{DeleteFileRequestArgs, DeleteFileRequestResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = DeleteFilesRequest<'a>;
 type Response = DeleteFilesResponse;
 type RequestPayload = DeleteFileRequestArgs;
 type ResponsePayload = DeleteFileRequestResult;
+type ResponseError = super::DeleteFileRequestError;
 
 /// Delete file
 /// <https://www.dropbox.com/developers/documentation/http/documentation#file_requests-delete>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct DeleteFilesRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FileRequestsDeletePost,
-    vec![Headers::TestAuthorization]
+    vec![Headers::TestAuthorization],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
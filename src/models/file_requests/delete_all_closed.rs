@@ -3,24 +3,22 @@ use super::DeleteAllClosedFileRequestsResult;
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = DeleteAllClosedFilesRequest<'a>;
 type Response = DeleteAllClosedFilesResponse;
 type RequestPayload = ();
 type ResponsePayload = DeleteAllClosedFileRequestsResult;
+type ResponseError = super::FileRequestError;
 
 /// Delete all closed
 /// <https://www.dropbox.com/developers/documentation/http/documentation#file_requests-delete_all_closed>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct DeleteAllClosedFilesRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FileRequestsDeleteAllClosedPost,
-    vec![Headers::TestAuthorization]
+    vec![Headers::TestAuthorization],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -3,24 +3,22 @@ use super::{GetFileRequestArgs, GetFileRequestResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = GetFileRequest<'a>;
 type Response = GetFileResponse;
 type RequestPayload = GetFileRequestArgs;
 type ResponsePayload = GetFileRequestResult;
+type ResponseError = super::FileRequestError;
 
 /// Get file
 /// <https://www.dropbox.com/developers/documentation/http/documentation#file_requests-get>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GetFileRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FileRequestsGetPost,
-    vec![Headers::TestAuthorization, Headers::ContentTypeAppJson]
+    vec![Headers::TestAuthorization, Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -3,24 +3,22 @@ use super::{ListFileRequestsArgs, ListFileRequestsResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = ListRequest<'a>;
 type Response = ListResponse;
 type RequestPayload = ListFileRequestsArgs;
 type ResponsePayload = ListFileRequestsResult;
+type ResponseError = super::GeneralFileRequestsError;
 
 /// List file
 /// <https://www.dropbox.com/developers/documentation/http/documentation#file_requests-list>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ListRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FileRequestsListPost,
-    vec![Headers::TestAuthorization, Headers::ContentTypeAppJson]
+    vec![Headers::TestAuthorization, Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -3,24 +3,22 @@ use super::{ListFileRequestsContinueArgs, ListFileRequestsContinueResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = ListContinueRequest<'a>;
 type Response = ListContinueResponse;
 type RequestPayload = ListFileRequestsContinueArgs;
 type ResponsePayload = ListFileRequestsContinueResult;
+type ResponseError = super::ListFileRequestsContinueError;
 
 /// List continue files
 /// <https://www.dropbox.com/developers/documentation/http/documentation#file_requests-list-continue>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ListContinueRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FileRequestsListContinuePost,
-    vec![Headers::TestAuthorization, Headers::ContentTypeAppJson]
+    vec![Headers::TestAuthorization, Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -141,6 +141,8 @@ pub struct ListFileRequestsContinueArgs {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ListFileRequestsContinueResult {
     pub file_requests: Vec<FileRequest>,
+    pub cursor: String,
+    pub has_more: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -161,3 +163,69 @@ pub struct UpdateFileRequestResult {
     pub deadline: Option<FileRequestDeadline>,
     pub open: bool,
 }
+
+// Error unions
+
+/// Error union for `count` and `list_v2`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GeneralFileRequestsError {
+    DisabledForTeam,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum ListFileRequestsContinueError {
+    DisabledForTeam,
+    InvalidCursor,
+    #[serde(other)]
+    Other,
+}
+
+/// Error union for `get` and `delete_all_closed`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum FileRequestError {
+    DisabledForTeam,
+    NotFound,
+    NotAFolder,
+    AppLacksAccess,
+    NoPermission,
+    EmailUnverified,
+    ValidationError,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum CreateFileRequestError {
+    DisabledForTeam,
+    NotFound,
+    NotAFolder,
+    AppLacksAccess,
+    NoPermission,
+    EmailUnverified,
+    ValidationError,
+    InvalidLocation,
+    RateLimit,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum DeleteFileRequestError {
+    DisabledForTeam,
+    NotFound,
+    NotAFolder,
+    AppLacksAccess,
+    NoPermission,
+    EmailUnverified,
+    ValidationError,
+    FileRequestOpen,
+    #[serde(other)]
+    Other,
+}
@@ -1,16 +1,13 @@
-use super::{CopyBatchArgs as Args, RelocationBatchV2Result as RequestResult};
+use super::{CopyBatchArgs as Args, RelocationBatchV2Launch as RequestResult};
 
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = CopyBatchRequest<'a>;
@@ -20,7 +17,7 @@ type ResponsePayload = RequestResult;
 
 /// Copy batch
 /// <https://api.dropboxapi.com/2/files/copy_batch_v2>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct CopyBatchRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -3,24 +3,22 @@ use super::{AsyncJobCheckArgs as Args, CopyBatchCheckResult as RequestResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = CopyBatchRequest<'a>;
 type Response = CopyBatchResponse;
 type RequestPayload = Args;
 type ResponsePayload = RequestResult;
+type ResponseError = super::PollError;
 
 /// Copy batch check
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-copy_batch-check>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct CopyBatchRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FilesCopyBatchCheckPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
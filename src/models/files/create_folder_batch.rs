@@ -1,16 +1,13 @@
-use super::{CreateFolderBatchArgs as Args, CreateFolderBatchResult as RequestResult};
+use super::{CreateFolderBatchArgs as Args, CreateFolderBatchLaunch as RequestResult};
 
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = CreateFolderBatchRequest<'a>;
@@ -20,7 +17,7 @@ type ResponsePayload = RequestResult;
 
 /// Create folder
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-create_folder_batch>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct CreateFolderBatchRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
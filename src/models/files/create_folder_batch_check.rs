@@ -3,24 +3,22 @@ use super::{AsyncJobCheckArgs as Args, CreateFolderBatchCheckResult as RequestRe
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = CreateFolderBatchCheckRequest<'a>;
 type Response = CreateFolderBatchCheckResponse;
 type RequestPayload = Args;
 type ResponsePayload = RequestResult;
+type ResponseError = super::PollError;
 
 /// Create folder check
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-create_folder_batch-check>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct CreateFolderBatchCheckRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FilesCreateFolderBatchCheckPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -1,16 +1,13 @@
-use super::{DeleteBatchArgs as Args, DeleteBatchResult as RequestResult};
+use super::{DeleteBatchArgs as Args, DeleteBatchLaunch as RequestResult};
 
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = DeleteBatchRequest<'a>;
@@ -20,7 +17,7 @@ type ResponsePayload = RequestResult;
 
 /// Delete file
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-delete_batch>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct DeleteBatchRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
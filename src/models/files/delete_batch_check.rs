@@ -1 +1,68 @@
+use super::{AsyncJobCheckArgs as Args, DeleteBatchJobStatus as RequestResult};
 
+use crate::{
+    anyhow::Result,
+    endpoints::headers::Headers,
+    endpoints::Endpoint,
+    implement_service, implement_utils,
+    traits::{Service, Utils},
+};
+use serde::Deserialize;
+
+/// Type aliases for readability
+type Request<'a> = DeleteBatchCheckRequest<'a>;
+type Response = DeleteBatchCheckResponse;
+type RequestPayload = Args;
+type ResponsePayload = RequestResult;
+type ResponseError = super::PollError;
+
+/// Delete batch check
+/// <https://www.dropbox.com/developers/documentation/http/documentation#files-delete_batch-check>
+#[derive(Debug, Default)]
+pub struct DeleteBatchCheckRequest<'a> {
+    pub access_token: &'a str,
+    pub payload: Option<RequestPayload>,
+}
+
+/// Response struct for adding properties
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+pub struct DeleteBatchCheckResponse {
+    pub payload: ResponsePayload,
+}
+
+// Impl utils trait
+implement_utils!(Request<'_>, RequestPayload);
+
+// Impl service trait
+implement_service!(
+    Request<'_>,
+    Response,
+    ResponsePayload,
+    Endpoint::FilesDeleteBatchCheckPost,
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
+);
+
+#[cfg(all(test, feature = "test-utils"))]
+mod tests {
+    use crate::TEST_AUTH_TOKEN;
+
+    use super::{Request, RequestPayload};
+
+    use tokio;
+
+    use crate::{
+        endpoints::{get_endpoint_url, headers::Headers, Endpoint},
+        implement_tests,
+        tests_utils::get_endpoint_test_body_response,
+        traits::Service,
+    };
+
+    implement_tests!(
+        Endpoint::FilesDeleteBatchCheckPost,
+        vec![Headers::TestAuthorization, Headers::ContentTypeAppJson],
+        Request,
+        RequestPayload
+    );
+}
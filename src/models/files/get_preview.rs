@@ -3,24 +3,22 @@ use super::{GetPreviewArg as Args, GetPreviewResult as RequestResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_download_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = GetPreviewRequest<'a>;
 type Response = GetPreviewResponse;
 type RequestPayload = Args;
 type ResponsePayload = RequestResult;
+type ResponseError = super::PreviewError;
 
 /// Export
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-export>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GetPreviewRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -47,7 +45,8 @@ implement_download_service!(
     vec![
         Headers::DropboxApiArg("".to_string()),
         Headers::DropboxApiResult,
-    ]
+    ],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
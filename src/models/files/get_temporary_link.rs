@@ -3,24 +3,22 @@ use super::{GetTemporaryLinkArgs as Args, GetTemporaryLinkResult as RequestResul
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = GetTemporaryLinkRequest<'a>;
 type Response = GetTemporaryLinkResponse;
 type RequestPayload = Args;
 type ResponsePayload = RequestResult;
+type ResponseError = super::GetTemporaryLinkError;
 
 /// Export
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-export>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GetTemporaryLinkRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FilesGetTemporaryLinkPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -3,14 +3,11 @@ use super::{GetTemporaryUploadLinkArgs as Args, GetTemporaryUploadLinkResult as
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = GetTemporaryUploadLinkRequest<'a>;
@@ -20,7 +17,7 @@ type ResponsePayload = RequestResult;
 
 /// Export
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-export>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GetTemporaryUploadLinkRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -3,24 +3,22 @@ use super::{GetThumbnailResult as RequestResult, ThumbnailV2Arg as Args};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_download_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = GetThumbnailRequest<'a>;
 type Response = GetThumbnailResponse;
 type RequestPayload = Args;
 type ResponsePayload = RequestResult;
+type ResponseError = super::ThumbnailV2Error;
 
 /// Get thumbnail v2
 /// <https://content.dropboxapi.com/2/files/get_thumbnail_v2>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GetThumbnailRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -47,7 +45,8 @@ implement_download_service!(
     vec![
         Headers::DropboxApiArg("".to_string()),
         Headers::DropboxApiResult,
-    ]
+    ],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
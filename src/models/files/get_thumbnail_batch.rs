@@ -3,24 +3,22 @@ use super::{GetThumbnailBatchResult as RequestResult, ThumbnailArgs as Args};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = GetThumbnailBatchRequest<'a>;
 type Response = GetThumbnailBatchResponse;
 type RequestPayload = Args;
 type ResponsePayload = RequestResult;
+type ResponseError = super::GetThumbnailBatchError;
 
 /// Get thumbnail batch
 /// <https://content.dropboxapi.com/2/files/get_thumbnail_batch>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GetThumbnailBatchRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FilesGetThumbnailBatchPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
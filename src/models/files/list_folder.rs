@@ -3,24 +3,22 @@ use super::{ListFolderArgs as Args, ListFolderResult as RequestResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = ListFolderRequest<'a>;
 type Response = ListFolderResponse;
 type RequestPayload = Args;
 type ResponsePayload = RequestResult;
+type ResponseError = super::ListFolderError;
 
 /// List files
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-list_folder>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ListFolderRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FilesListFolderPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 impl<'a> ListFolderRequest<'a> {
@@ -3,24 +3,22 @@ use super::{ListFolderLongpollArgs as Args, ListFolderLongpollResult as RequestR
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = ListFolderLongpollRequest<'a>;
 type Response = ListFolderLongpollResponse;
 type RequestPayload = Args;
 type ResponsePayload = RequestResult;
+type ResponseError = super::ListFolderLongpollError;
 
 /// List folder longpoll
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-list_folder-longpoll>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ListFolderLongpollRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FilesListFolderLongpollPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
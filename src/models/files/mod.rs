@@ -282,14 +282,14 @@ pub struct DeletedMetadata {
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum ApiError {
     #[serde(rename = "path_lookup")]
-    PathLookup(LookupError),
+    PathLookup { path_lookup: LookupError },
     #[serde(rename = "path_write")]
-    PathWrite(WriteError),
+    PathWrite { path_write: WriteError },
     #[serde(rename = "internal_error")]
     InternalError,
     #[serde(rename = "invalid_async_job_id")]
     InvalidAsyncJobId,
-    #[serde(rename = "other")]
+    #[serde(other)]
     Other,
 }
 
@@ -297,7 +297,7 @@ pub enum ApiError {
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum LookupError {
     #[serde(rename = "malformed_path")]
-    MalformedPath(Option<String>),
+    MalformedPath { malformed_path: Option<String> },
     #[serde(rename = "not_found")]
     NotFound,
     #[serde(rename = "not_file")]
@@ -310,15 +310,17 @@ pub enum LookupError {
     UnsupportedContentType,
     #[serde(rename = "locked")]
     Locked,
+    #[serde(other)]
+    Other,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum WriteError {
     #[serde(rename = "malformed_path")]
-    MalformedPath(Option<String>),
+    MalformedPath { malformed_path: Option<String> },
     #[serde(rename = "conflict")]
-    Conflict(ConflictType),
+    Conflict { conflict: ConflictType },
     #[serde(rename = "no_write_permission")]
     NoWritePermission,
     #[serde(rename = "insufficient_space")]
@@ -331,6 +333,8 @@ pub enum WriteError {
     OperationSuppressed,
     #[serde(rename = "too_many_write_operations")]
     TooManyWriteOperations,
+    #[serde(other)]
+    Other,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -342,6 +346,8 @@ pub enum ConflictType {
     Folder,
     #[serde(rename = "file_ancestor")]
     FileAncestor,
+    #[serde(other)]
+    Other,
 }
 
 // files/copy_v2
@@ -502,9 +508,14 @@ pub struct CreateFolderEntryResult {
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum CreateFolderEntryError {
     #[serde(rename = "path")]
-    Path(WriteError),
+    Path { path: WriteError },
+    #[serde(other)]
+    Other,
 }
 
+/// Error union for `create_folder_v2`; same shape as the batch entry error.
+pub type CreateFolderError = CreateFolderEntryError;
+
 // files/create_folder_batch/check
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -514,6 +525,17 @@ pub enum CreateFolderBatchCheckResult {
     Complete(CreateFolderBatchResult),
     #[serde(rename = "in_progress")]
     InProgress,
+    #[serde(rename = "failed")]
+    Failed { failed: CreateFolderBatchError },
+}
+
+/// Why a whole `create_folder_batch` job failed.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum CreateFolderBatchError {
+    TooManyFiles,
+    #[serde(other)]
+    Other,
 }
 
 // files/delete_v2
@@ -574,6 +596,17 @@ pub enum DeleteBatchJobStatus {
     Complete(DeleteBatchResult),
     #[serde(rename = "in_progress")]
     InProgress,
+    #[serde(rename = "failed")]
+    Failed { failed: DeleteBatchError },
+}
+
+/// Why a whole `delete_batch` job failed.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum DeleteBatchError {
+    TooManyWriteOperations,
+    #[serde(other)]
+    Other,
 }
 
 // files/download
@@ -848,13 +881,45 @@ pub struct MinimalFileLinkMetadata {
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-#[serde(tag = "error_type", rename_all = "snake_case")]
+#[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum ThumbnailError {
-    PathLookupError(LookupError),
+    Path {
+        path: LookupError,
+    },
+    UnsupportedExtension,
+    UnsupportedImage,
+    EncryptedContent,
+    ConversionError,
+    #[serde(other)]
+    Other,
+}
+
+/// Error union for `get_thumbnail_v2`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum ThumbnailV2Error {
+    AccessError {
+        access_error: crate::models::sharing::SharedLinkAccessFailureReason,
+    },
+    Path {
+        path: LookupError,
+    },
     UnsupportedExtension,
     UnsupportedImage,
     EncryptedContent,
     ConversionError,
+    NotFound,
+    #[serde(other)]
+    Other,
+}
+
+/// Error union for `get_thumbnail_batch`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GetThumbnailBatchError {
+    TooManyFiles,
+    #[serde(other)]
+    Other,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -973,10 +1038,10 @@ pub struct ListFolderContinueResult {
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum ListFolderContinueError {
     #[serde(rename = "path")]
-    Path(LookupError),
+    Path { path: LookupError },
     #[serde(rename = "reset")]
     Reset,
-    #[serde(rename = "other")]
+    #[serde(other)]
     Other,
 }
 
@@ -1052,8 +1117,22 @@ pub struct ListRevisionsResult {
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-pub struct LockFileError {
-    pub lock_conflict: Option<FileLock>,
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum LockFileError {
+    PathLookup {
+        path_lookup: LookupError,
+    },
+    TooManyWriteOperations,
+    TooManyFiles,
+    NoWritePermission,
+    CannotBeLocked,
+    FileNotShared,
+    LockConflict {
+        lock: FileLock,
+    },
+    InternalError,
+    #[serde(other)]
+    Other,
 }
 
 // files/move_v2
@@ -1182,10 +1261,14 @@ pub enum SaveUrlResult {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum SaveUrlError {
-    Path(LookupError),
+    Path {
+        path: WriteError,
+    },
     DownloadFailed,
     InvalidUrl,
     NotFound,
+    #[serde(other)]
+    Other,
 }
 
 // files/save_url/check_job_status
@@ -1198,7 +1281,7 @@ pub enum SaveUrlJobStatus {
     #[serde(rename = "complete")]
     Complete(FileMetadata),
     #[serde(rename = "failed")]
-    Failed(SaveUrlError),
+    Failed { failed: SaveUrlError },
 }
 
 // files/search_v2
@@ -1295,8 +1378,12 @@ pub struct AddTagArg {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum AddTagError {
-    Path(LookupError),
+    Path {
+        path: LookupError,
+    },
     TooManyTags,
+    #[serde(other)]
+    Other,
 }
 
 // files/tags/get
@@ -1340,8 +1427,23 @@ pub struct RemoveTagArg {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum RemoveTagError {
-    Path(LookupError),
+    Path {
+        path: LookupError,
+    },
     TagNotPresent,
+    #[serde(other)]
+    Other,
+}
+
+/// Error union for `tags/get`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum BaseTagError {
+    Path {
+        path: LookupError,
+    },
+    #[serde(other)]
+    Other,
 }
 
 // files/unlock_file_batch
@@ -1386,9 +1488,14 @@ pub struct UploadWriteFailed {
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum UploadError {
     Path(UploadWriteFailed),
-    PropertiesError,
+    PropertiesError {
+        properties_error: crate::models::file_properties::InvalidPropertyGroupError,
+    },
     PayloadTooLarge,
     ContentHashMismatch,
+    EncryptionNotSupported,
+    #[serde(other)]
+    Other,
 }
 
 // files/upload_session/append_v2
@@ -1412,13 +1519,39 @@ pub struct UploadSessionCursor {
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum UploadSessionAppendError {
     NotFound,
-    IncorrectOffset,
+    IncorrectOffset {
+        correct_offset: u64,
+    },
     Closed,
+    NotClosed,
     TooLarge,
     ConcurrentSessionInvalidOffset,
     ConcurrentSessionInvalidDataSize,
     PayloadTooLarge,
     ContentHashMismatch,
+    EncryptionNotSupported,
+    #[serde(other)]
+    Other,
+}
+
+/// Why an upload session couldn't be found or continued. Nested in
+/// `UploadSessionFinishError::LookupFailed`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum UploadSessionLookupError {
+    NotFound,
+    IncorrectOffset {
+        correct_offset: u64,
+    },
+    Closed,
+    NotClosed,
+    TooLarge,
+    ConcurrentSessionInvalidOffset,
+    ConcurrentSessionInvalidDataSize,
+    PayloadTooLarge,
+    EncryptionNotSupported,
+    #[serde(other)]
+    Other,
 }
 
 // files/upload_session/append_batch
@@ -1462,9 +1595,15 @@ pub struct UploadSessionFinishArg {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum UploadSessionFinishError {
-    LookupFailed,
-    Path(LookupError),
-    PropertiesError,
+    LookupFailed {
+        lookup_failed: UploadSessionLookupError,
+    },
+    Path {
+        path: WriteError,
+    },
+    PropertiesError {
+        properties_error: crate::models::file_properties::InvalidPropertyGroupError,
+    },
     TooManySharedFolderTargets,
     TooManyWriteOperations,
     ConcurrentSessionDataNotAllowed,
@@ -1472,6 +1611,9 @@ pub enum UploadSessionFinishError {
     ConcurrentSessionMissingData,
     PayloadTooLarge,
     ContentHashMismatch,
+    EncryptionNotSupported,
+    #[serde(other)]
+    Other,
 }
 
 // files/upload_session/finish_batch_v2
@@ -1544,6 +1686,9 @@ pub enum UploadSessionStartError {
     ConcurrentSessionCloseNotAllowed,
     PayloadTooLarge,
     ContentHashMismatch,
+    EncryptionNotSupported,
+    #[serde(other)]
+    Other,
 }
 
 // files/upload_session/start_batch
@@ -1575,11 +1720,11 @@ pub enum UploadSessionType {
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum RelocationError {
     #[serde(rename = "from_lookup")]
-    FromLookup(LookupError),
+    FromLookup { from_lookup: LookupError },
     #[serde(rename = "from_write")]
-    FromWrite(WriteError),
+    FromWrite { from_write: WriteError },
     #[serde(rename = "to")]
-    To(WriteError),
+    To { to: WriteError },
     #[serde(rename = "cant_copy_shared_folder")]
     CantCopySharedFolder,
     #[serde(rename = "cant_nest_shared_folder")]
@@ -1599,10 +1744,14 @@ pub enum RelocationError {
     #[serde(rename = "cant_move_shared_folder")]
     CantMoveSharedFolder,
     #[serde(rename = "cant_move_into_vault")]
-    CantMoveIntoVault(MoveIntoVaultError),
+    CantMoveIntoVault {
+        cant_move_into_vault: MoveIntoVaultError,
+    },
     #[serde(rename = "cant_move_into_family")]
-    CantMoveIntoFamily(MoveIntoFamilyError),
-    #[serde(rename = "other")]
+    CantMoveIntoFamily {
+        cant_move_into_family: MoveIntoFamilyError,
+    },
+    #[serde(other)]
     Other,
 }
 
@@ -1613,7 +1762,7 @@ pub enum RelocationError {
 pub enum MoveIntoVaultError {
     #[serde(rename = "is_shared_folder")]
     IsSharedFolder,
-    #[serde(rename = "other")]
+    #[serde(other)]
     Other,
 }
 
@@ -1624,7 +1773,7 @@ pub enum MoveIntoVaultError {
 pub enum MoveIntoFamilyError {
     #[serde(rename = "is_shared_folder")]
     IsSharedFolder,
-    #[serde(rename = "other")]
+    #[serde(other)]
     Other,
 }
 
@@ -1643,14 +1792,14 @@ pub struct DeleteArg {
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum DeleteError {
     #[serde(rename = "path_lookup")]
-    PathLookup(LookupError),
+    PathLookup { path_lookup: LookupError },
     #[serde(rename = "path_write")]
-    PathWrite(WriteError),
+    PathWrite { path_write: WriteError },
     #[serde(rename = "too_many_write_operations")]
     TooManyWriteOperations,
     #[serde(rename = "too_many_files")]
     TooManyFiles,
-    #[serde(rename = "other")]
+    #[serde(other)]
     Other,
 }
 
@@ -1659,8 +1808,11 @@ pub enum DeleteError {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum DownloadError {
-    Path(LookupError),
+    Path {
+        path: LookupError,
+    },
     UnsupportedFile,
+    #[serde(other)]
     Other,
 }
 
@@ -1669,9 +1821,12 @@ pub enum DownloadError {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum DownloadZipError {
-    Path(LookupError),
+    Path {
+        path: LookupError,
+    },
     TooLarge,
     TooManyFiles,
+    #[serde(other)]
     Other,
 }
 
@@ -1680,10 +1835,13 @@ pub enum DownloadZipError {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum ExportError {
-    Path(LookupError),
+    Path {
+        path: LookupError,
+    },
     NonExportable,
     InvalidExportFormat,
     RetryError,
+    #[serde(other)]
     Other,
 }
 
@@ -1692,10 +1850,13 @@ pub enum ExportError {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum PreviewError {
-    Path(LookupError),
+    Path {
+        path: LookupError,
+    },
     InProgress,
     UnsupportedExtension,
     UnsupportedContent,
+    #[serde(other)]
     Other,
 }
 
@@ -1704,10 +1865,13 @@ pub enum PreviewError {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum GetTemporaryLinkError {
-    Path(LookupError),
+    Path {
+        path: LookupError,
+    },
     EmailNotVerified,
     UnsupportedFile,
     NotAllowed,
+    #[serde(other)]
     Other,
 }
 
@@ -1716,7 +1880,10 @@ pub enum GetTemporaryLinkError {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum ListFolderError {
-    Path(LookupError),
+    Path {
+        path: LookupError,
+    },
+    #[serde(other)]
     Other,
 }
 
@@ -1726,6 +1893,7 @@ pub enum ListFolderError {
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum ListFolderLongpollError {
     Reset,
+    #[serde(other)]
     Other,
 }
 
@@ -1734,7 +1902,10 @@ pub enum ListFolderLongpollError {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum ListRevisionsError {
-    Path(LookupError),
+    Path {
+        path: LookupError,
+    },
+    #[serde(other)]
     Other,
 }
 
@@ -1743,8 +1914,114 @@ pub enum ListRevisionsError {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum SearchError {
-    Path(LookupError),
-    InvalidArgument(Option<String>),
+    Path {
+        path: LookupError,
+    },
+    InvalidArgument {
+        invalid_argument: Option<String>,
+    },
+    InternalError,
+    #[serde(other)]
+    Other,
+}
+
+// GetMetadataError
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GetMetadataError {
+    Path {
+        path: LookupError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+// GetCopyReferenceError / SaveCopyReferenceError
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GetCopyReferenceError {
+    Path {
+        path: LookupError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum SaveCopyReferenceError {
+    Path {
+        path: WriteError,
+    },
+    InvalidCopyReference,
+    NoPermission,
+    NotFound,
+    TooManyFiles,
+    #[serde(other)]
+    Other,
+}
+
+// RestoreError
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum RestoreError {
+    PathLookup {
+        path_lookup: LookupError,
+    },
+    PathWrite {
+        path_write: WriteError,
+    },
+    InvalidRevision,
+    InProgress,
+    #[serde(other)]
+    Other,
+}
+
+// PaperCreateError / PaperUpdateError
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum PaperCreateError {
+    InsufficientPermissions,
+    ContentMalformed,
+    DocLengthExceeded,
+    ImageSizeExceeded,
+    InvalidPath,
+    EmailUnverified,
+    InvalidFileExtension,
+    PaperDisabled,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum PaperUpdateError {
+    InsufficientPermissions,
+    ContentMalformed,
+    DocLengthExceeded,
+    ImageSizeExceeded,
+    Path {
+        path: LookupError,
+    },
+    RevisionMismatch,
+    DocArchived,
+    DocDeleted,
+    #[serde(other)]
+    Other,
+}
+
+// PollError
+
+/// Error union for the `*/check` job-status endpoints, here and in `sharing`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum PollError {
+    InvalidAsyncJobId,
     InternalError,
+    #[serde(other)]
     Other,
 }
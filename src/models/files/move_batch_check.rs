@@ -1,26 +1,24 @@
-use super::{AsyncJobCheckArgs as Args, RelocationBatchV2Result as RequestResult};
+use super::{AsyncJobCheckArgs as Args, MoveBatchJobStatus as RequestResult};
 
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = MoveBatchCheckRequest<'a>;
 type Response = MoveBatchCheckResponse;
 type RequestPayload = Args;
 type ResponsePayload = RequestResult;
+type ResponseError = super::PollError;
 
 /// Move batch check
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-move_batch-check>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct MoveBatchCheckRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FilesMoveBatchCheckPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
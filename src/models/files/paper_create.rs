@@ -3,24 +3,22 @@ use super::{PaperCreateArgs as Args, PaperCreateResult as RequestResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_content_upload_utils, implement_service,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = PaperCreateRequest<'a>;
 type Response = PaperCreateResponse;
 type RequestPayload = Args;
 type ResponsePayload = RequestResult;
+type ResponseError = super::PaperCreateError;
 
 /// Paper create
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-paper-create>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct PaperCreateRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -47,7 +45,8 @@ implement_service!(
     vec![
         Headers::ContentTypeAppOctetStream,
         Headers::DropboxApiArg("".to_string())
-    ]
+    ],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
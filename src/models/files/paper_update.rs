@@ -3,24 +3,22 @@ use super::{PaperUpdateArgs as Args, PaperUpdateResult as RequestResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_content_upload_utils, implement_service,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = PaperUpdateRequest<'a>;
 type Response = PaperUpdateResponse;
 type RequestPayload = Args;
 type ResponsePayload = RequestResult;
+type ResponseError = super::PaperUpdateError;
 
 /// Paper update
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-paper-update>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct PaperUpdateRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -47,7 +45,8 @@ implement_service!(
     vec![
         Headers::ContentTypeAppOctetStream,
         Headers::DropboxApiArg("".to_string())
-    ]
+    ],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -3,24 +3,22 @@ use super::AddTagArg as Args;
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = AddTagsRequest<'a>;
 type Response = AddTagsResponse;
 type RequestPayload = Args;
 type ResponsePayload = ();
+type ResponseError = super::AddTagError;
 
 /// Add tags
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-tags-add>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct AddTagsRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FilesTagsAddPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -3,24 +3,22 @@ use super::{GetTagsArg as Args, GetTagsResult as RequestResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readabilitydTag
 type Request<'a> = GetTagsRequest<'a>;
 type Response = GetTagsResponse;
 type RequestPayload = Args;
 type ResponsePayload = RequestResult;
+type ResponseError = super::BaseTagError;
 
 /// Get tags
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-tags-get>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GetTagsRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FilesTagsGetPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -3,24 +3,22 @@ use super::RemoveTagArg as Args;
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readabilitydTag
 type Request<'a> = RemoveTagsRequest<'a>;
 type Response = RemoveTagsResponse;
 type RequestPayload = Args;
 type ResponsePayload = ();
+type ResponseError = super::RemoveTagError;
 
 /// Remove tags
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-tags-remove>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct RemoveTagsRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FilesTagsRemovePost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -3,24 +3,22 @@ use super::{LockFileBatchResult as RequestResult, UnlockFileBatchArg as Args};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readabilitydTag
 type Request<'a> = UnlockFileBatchRequest<'a>;
 type Response = UnlockFileBatchResponse;
 type RequestPayload = Args;
 type ResponsePayload = RequestResult;
+type ResponseError = super::LockFileError;
 
 /// Unlock file batch
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-unlock_file_batch>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct UnlockFileBatchRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -42,7 +40,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::FilesUnlockFileBatchPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -3,24 +3,22 @@ use super::UploadSessionAppendArg;
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_content_upload_utils, implement_service,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 type Request<'a> = UploadSessionAppendRequest<'a>;
 type Response = UploadSessionAppendResponse;
 type RequestPayload = UploadSessionAppendArg;
 /// `upload_session/append_v2` returns null on success.
 type ResponsePayload = serde_json::Value;
+type ResponseError = super::UploadSessionAppendError;
 
 /// Upload session append v2
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-upload_session-append_v2>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct UploadSessionAppendRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -44,5 +42,6 @@ implement_service!(
     vec![
         Headers::ContentTypeAppOctetStream,
         Headers::DropboxApiArg("".to_string()),
-    ]
+    ],
+    ResponseError
 );
@@ -3,24 +3,22 @@ use super::{FileMetadata, UploadSessionFinishArg};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_content_upload_utils, implement_service,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = UploadSessionFinishRequest<'a>;
 type Response = UploadSessionFinishResponse;
 type RequestPayload = UploadSessionFinishArg;
 type ResponsePayload = FileMetadata;
+type ResponseError = super::UploadSessionFinishError;
 
 /// UploadSessionFinish
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-UploadSessionFinish>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct UploadSessionFinishRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -47,7 +45,8 @@ implement_service!(
     vec![
         Headers::ContentTypeAppOctetStream,
         Headers::DropboxApiArg("".to_string())
-    ]
+    ],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -3,14 +3,11 @@ use super::{UploadSessionFinishBatchArg, UploadSessionFinishBatchLaunch};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = UploadSessionFinishBatchRequest<'a>;
@@ -20,7 +17,7 @@ type ResponsePayload = UploadSessionFinishBatchLaunch;
 
 /// UploadSessionFinishBatch
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-UploadSessionFinishBatch>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct UploadSessionFinishBatchRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
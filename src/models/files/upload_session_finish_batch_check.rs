@@ -3,24 +3,22 @@ use super::{PollArg, UploadSessionFinishBatchJobStatus};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// Type aliases for readability
 type Request<'a> = UploadSessionFinishBatchCheckRequest<'a>;
 type Response = UploadSessionFinishBatchCheckResponse;
 type RequestPayload = PollArg;
 type ResponsePayload = UploadSessionFinishBatchJobStatus;
+type ResponseError = super::PollError;
 
 /// UploadSessionFinishBatchCheck
 /// <https://www.dropbox.com/developers/documentation/http/documentation#files-UploadSessionFinishBatchCheck>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct UploadSessionFinishBatchCheckRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -45,7 +43,8 @@ implement_service!(
     vec![
         Headers::ContentTypeAppOctetStream,
         Headers::DropboxApiArg("".to_string())
-    ]
+    ],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -1,10 +1,13 @@
 pub mod account;
 pub mod auth;
 pub mod check;
+pub mod common;
 pub mod contacts;
 pub mod file_properties;
 pub mod file_requests;
 pub mod files;
 pub mod openid;
 pub mod sharing;
+pub mod team;
+pub mod team_log;
 pub mod users;
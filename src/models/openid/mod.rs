@@ -21,3 +21,13 @@ pub struct UserInfoResult {
     #[serde(default)]
     pub sub: Option<String>,
 }
+
+/// Error struct for `userinfo`. Unlike most endpoints this is a struct, not
+/// a tagged union.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UserInfoError {
+    #[serde(default)]
+    pub err: Option<serde_json::Value>,
+    #[serde(default)]
+    pub error_message: String,
+}
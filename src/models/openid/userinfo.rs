@@ -3,23 +3,21 @@ use super::UserInfoResult;
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 type Request<'a> = UserInfoRequest<'a>;
 type Response = UserInfoResponse;
 type RequestPayload = ();
 type ResponsePayload = UserInfoResult;
+type ResponseError = super::UserInfoError;
 
 /// `openid/userinfo` — OIDC-style user claims.
 /// <https://www.dropbox.com/developers/documentation/http/documentation#openid-userinfo>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct UserInfoRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -38,7 +36,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::OpenidUserInfoPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
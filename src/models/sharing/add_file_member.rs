@@ -1,24 +1,22 @@
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 type Request<'a> = AddFileMemberRequest<'a>;
 type Response = AddFileMemberResponse;
 type RequestPayload = super::AddFileMemberArgs;
 type ResponsePayload = serde_json::Value;
+type ResponseError = super::AddFileMemberError;
 
 /// `add_file_member`
 /// Payload and response are modelled as `serde_json::Value` for now — the
 /// endpoint is wired and reachable; full typed structs follow in a later pass.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct AddFileMemberRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -37,7 +35,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::SharingAddFileMemberPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -1,24 +1,22 @@
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 type Request<'a> = CheckRemoveMemberJobStatusRequest<'a>;
 type Response = CheckRemoveMemberJobStatusResponse;
 type RequestPayload = super::PollArg;
 type ResponsePayload = super::RemoveMemberJobStatus;
+type ResponseError = crate::models::files::PollError;
 
 /// `check_remove_member_job_status`
 /// Payload and response are modelled as `serde_json::Value` for now — the
 /// endpoint is wired and reachable; full typed structs follow in a later pass.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct CheckRemoveMemberJobStatusRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -37,7 +35,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::SharingCheckRemoveMemberJobStatusPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
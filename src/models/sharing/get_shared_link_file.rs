@@ -3,24 +3,22 @@ use super::{GetSharedLinkMetadataArg, SharedLinkMetadata};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_content_upload_utils, implement_download_service,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 type Request<'a> = GetSharedLinkFileRequest<'a>;
 type Response = GetSharedLinkFileResponse;
 type RequestPayload = GetSharedLinkMetadataArg;
 type ResponsePayload = SharedLinkMetadata;
+type ResponseError = super::GetSharedLinkFileError;
 
 /// `get_shared_link_file` — downloads the file behind a shared link. Metadata
 /// arrives in the `Dropbox-API-Result` response header; bytes in the body.
 /// <https://www.dropbox.com/developers/documentation/http/documentation#sharing-get_shared_link_file>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GetSharedLinkFileRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -46,7 +44,8 @@ implement_download_service!(
     vec![
         Headers::DropboxApiArg("".to_string()),
         Headers::DropboxApiResult,
-    ]
+    ],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
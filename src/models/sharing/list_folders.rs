@@ -1,14 +1,11 @@
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 type Request<'a> = ListFoldersRequest<'a>;
 type Response = ListFoldersResponse;
@@ -18,7 +15,7 @@ type ResponsePayload = super::ListFoldersResult;
 /// `list_folders`
 /// Payload and response are modelled as `serde_json::Value` for now — the
 /// endpoint is wired and reachable; full typed structs follow in a later pass.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ListFoldersRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
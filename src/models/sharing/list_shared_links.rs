@@ -3,23 +3,21 @@ use super::{ListSharedLinksArg, ListSharedLinksResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 type Request<'a> = ListSharedLinksRequest<'a>;
 type Response = ListSharedLinksResponse;
 type RequestPayload = ListSharedLinksArg;
 type ResponsePayload = ListSharedLinksResult;
+type ResponseError = super::ListSharedLinksError;
 
 /// List shared links
 /// <https://www.dropbox.com/developers/documentation/http/documentation#sharing-list_shared_links>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ListSharedLinksRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -38,7 +36,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::SharingListSharedLinksPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
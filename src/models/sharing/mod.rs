@@ -11,6 +11,10 @@
 //!
 //! Reference: <https://www.dropbox.com/developers/documentation/http/documentation#sharing>
 
+// Share-job results nest the full folder metadata as a union variant; see
+// the note in `files` on why the lint is silenced rather than boxing.
+#![allow(clippy::large_enum_variant)]
+
 pub mod add_file_member;
 pub mod add_folder_member;
 pub mod check_job_status;
@@ -508,7 +512,14 @@ pub struct ListFolderMembersResult {
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum ShareFolderLaunch {
     AsyncJobId { async_job_id: String },
-    Complete(serde_json::Value),
+    Complete(SharedFolderMetadata),
+}
+
+/// Launch envelope for `remove_folder_member`, which always runs as a job.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum LaunchResultBase {
+    AsyncJobId { async_job_id: String },
 }
 
 /// Launch envelope for `unshare_folder` and similar fire-and-forget folder ops.
@@ -524,8 +535,8 @@ pub enum LaunchEmptyResult {
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum ShareFolderJobStatus {
     InProgress,
-    Complete(serde_json::Value),
-    Failed(serde_json::Value),
+    Complete(SharedFolderMetadata),
+    Failed { failed: ShareFolderError },
 }
 
 /// Job status for `check_remove_member_job_status`.
@@ -533,8 +544,28 @@ pub enum ShareFolderJobStatus {
 #[serde(tag = ".tag", rename_all = "snake_case")]
 pub enum RemoveMemberJobStatus {
     InProgress,
-    Complete(serde_json::Value),
-    Failed(serde_json::Value),
+    Complete(MemberAccessLevelResult),
+    Failed { failed: RemoveFolderMemberError },
+}
+
+/// What access the removed member keeps, e.g. through a parent folder.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MemberAccessLevelResult {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_level: Option<AccessLevel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_details: Option<Vec<ParentFolderAccessInfo>>,
+}
+
+/// A parent folder the member still has access through.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ParentFolderAccessInfo {
+    pub folder_name: String,
+    pub shared_folder_id: String,
+    pub permissions: Vec<MemberPermission>,
+    pub path: String,
 }
 
 /// Job status for `check_job_status` (generic).
@@ -543,7 +574,24 @@ pub enum RemoveMemberJobStatus {
 pub enum JobStatus {
     InProgress,
     Complete,
-    Failed(serde_json::Value),
+    Failed { failed: JobError },
+}
+
+/// Why a `check_job_status` job failed.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum JobError {
+    UnshareFolderError {
+        unshare_folder_error: UnshareFolderError,
+    },
+    RemoveFolderMemberError {
+        remove_folder_member_error: RemoveFolderMemberError,
+    },
+    RelinquishFolderMembershipError {
+        relinquish_folder_membership_error: RelinquishFolderMembershipError,
+    },
+    #[serde(other)]
+    Other,
 }
 
 // =============================================================================
@@ -1023,3 +1071,482 @@ pub enum LinkAction {
     SetPassword,
     Other,
 }
+
+// =============================================================================
+// Error unions. Deeply nested members stay as `serde_json::Value`, same as
+// the response types above.
+// =============================================================================
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum SharingUserError {
+    EmailUnverified,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum SharingFileAccessError {
+    NoPermission,
+    InvalidFile,
+    IsFolder,
+    InsideOsxPackage,
+    InsidePublicFolder,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum SharedFolderAccessError {
+    InvalidId,
+    NotAMember,
+    InvalidMember,
+    EmailUnverified,
+    Unmounted,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum SharedFolderMemberError {
+    InvalidDropboxId,
+    NotAMember,
+    NoExplicitAccess(serde_json::Value),
+    #[serde(other)]
+    Other,
+}
+
+/// Error union for `get_shared_link_metadata`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum SharedLinkError {
+    SharedLinkNotFound,
+    SharedLinkAccessDenied,
+    UnsupportedLinkType,
+    UnsupportedParameterField,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum SharedLinkSettingsError {
+    InvalidSettings,
+    NotAuthorized,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum AddFileMemberError {
+    UserError {
+        user_error: SharingUserError,
+    },
+    AccessError {
+        access_error: SharingFileAccessError,
+    },
+    RateLimit,
+    InvalidComment,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum AddFolderMemberError {
+    AccessError {
+        access_error: SharedFolderAccessError,
+    },
+    EmailUnverified,
+    BannedMember,
+    BadMember {
+        bad_member: serde_json::Value,
+    },
+    CantShareOutsideTeam,
+    TooManyMembers {
+        too_many_members: u64,
+    },
+    TooManyPendingInvites {
+        too_many_pending_invites: u64,
+    },
+    RateLimit,
+    TooManyInvitees,
+    InsufficientPlan,
+    TeamFolder,
+    NoPermission,
+    InvalidSharedFolder,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum CreateSharedLinkWithSettingsError {
+    Path {
+        path: crate::models::files::LookupError,
+    },
+    EmailNotVerified,
+    SharedLinkAlreadyExists {
+        shared_link_already_exists: Option<serde_json::Value>,
+    },
+    SettingsError {
+        settings_error: SharedLinkSettingsError,
+    },
+    AccessError {
+        access_error: SharingFileAccessError,
+    },
+    BannedMember,
+    #[serde(other)]
+    Other,
+}
+
+/// Error union for `get_file_metadata`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GetFileMetadataError {
+    UserError {
+        user_error: SharingUserError,
+    },
+    AccessError {
+        access_error: SharingFileAccessError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GetSharedLinkFileError {
+    SharedLinkNotFound,
+    SharedLinkAccessDenied,
+    UnsupportedLinkType,
+    UnsupportedParameterField,
+    SharedLinkIsDirectory,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum ListFileMembersError {
+    UserError {
+        user_error: SharingUserError,
+    },
+    AccessError {
+        access_error: SharingFileAccessError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum ListFileMembersContinueError {
+    UserError {
+        user_error: SharingUserError,
+    },
+    AccessError {
+        access_error: SharingFileAccessError,
+    },
+    InvalidCursor,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum ListFolderMembersContinueError {
+    AccessError {
+        access_error: SharedFolderAccessError,
+    },
+    InvalidCursor,
+    #[serde(other)]
+    Other,
+}
+
+/// Error union for `list_folders/continue` and `list_mountable_folders/continue`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum ListFoldersContinueError {
+    InvalidCursor,
+    #[serde(other)]
+    Other,
+}
+
+/// Error union for `list_received_files/continue`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum ListFilesContinueError {
+    UserError {
+        user_error: SharingUserError,
+    },
+    InvalidCursor,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum ListSharedLinksError {
+    Path {
+        path: crate::models::files::LookupError,
+    },
+    Reset,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum ModifySharedLinkSettingsError {
+    SharedLinkNotFound,
+    SharedLinkAccessDenied,
+    UnsupportedLinkType,
+    UnsupportedParameterField,
+    SettingsError {
+        settings_error: SharedLinkSettingsError,
+    },
+    EmailNotVerified,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum MountFolderError {
+    AccessError {
+        access_error: SharedFolderAccessError,
+    },
+    InsideSharedFolder,
+    InsufficientQuota(serde_json::Value),
+    AlreadyMounted,
+    NoPermission,
+    NotMountable,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum RelinquishFileMembershipError {
+    AccessError {
+        access_error: SharingFileAccessError,
+    },
+    GroupAccess,
+    NoPermission,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum RelinquishFolderMembershipError {
+    AccessError {
+        access_error: SharedFolderAccessError,
+    },
+    FolderOwner,
+    Mounted,
+    GroupAccess,
+    TeamFolder,
+    NoPermission,
+    NoExplicitAccess,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum RemoveFileMemberError {
+    UserError {
+        user_error: SharingUserError,
+    },
+    AccessError {
+        access_error: SharingFileAccessError,
+    },
+    NoExplicitAccess(serde_json::Value),
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum RemoveFolderMemberError {
+    AccessError {
+        access_error: SharedFolderAccessError,
+    },
+    MemberError {
+        member_error: SharedFolderMemberError,
+    },
+    FolderOwner,
+    GroupAccess,
+    TeamFolder,
+    NoPermission,
+    TooManyFiles,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum RevokeSharedLinkError {
+    SharedLinkNotFound,
+    SharedLinkAccessDenied,
+    UnsupportedLinkType,
+    UnsupportedParameterField,
+    SharedLinkMalformed,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum SetAccessInheritanceError {
+    AccessError {
+        access_error: SharedFolderAccessError,
+    },
+    NoPermission,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum SharePathError {
+    IsFile,
+    InsideSharedFolder,
+    ContainsSharedFolder,
+    ContainsAppFolder,
+    ContainsTeamFolder,
+    IsAppFolder,
+    InsideAppFolder,
+    IsPublicFolder,
+    InsidePublicFolder,
+    AlreadyShared(serde_json::Value),
+    InvalidPath,
+    IsOsxPackage,
+    InsideOsxPackage,
+    IsVault,
+    IsVaultLocked,
+    IsFamily,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum ShareFolderError {
+    EmailUnverified,
+    BadPath {
+        bad_path: SharePathError,
+    },
+    TeamPolicyDisallowsMemberPolicy,
+    DisallowedSharedLinkPolicy,
+    NoPermission,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TransferFolderError {
+    AccessError {
+        access_error: SharedFolderAccessError,
+    },
+    InvalidDropboxId,
+    NewOwnerNotAMember,
+    NewOwnerUnmounted,
+    NewOwnerEmailUnverified,
+    TeamFolder,
+    NoPermission,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum UnmountFolderError {
+    AccessError {
+        access_error: SharedFolderAccessError,
+    },
+    NoPermission,
+    NotUnmountable,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum UnshareFileError {
+    UserError {
+        user_error: SharingUserError,
+    },
+    AccessError {
+        access_error: SharingFileAccessError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum UnshareFolderError {
+    AccessError {
+        access_error: SharedFolderAccessError,
+    },
+    TeamFolder,
+    NoPermission,
+    TooManyFiles,
+    #[serde(other)]
+    Other,
+}
+
+/// Error union for `update_file_member`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum FileMemberActionError {
+    InvalidMember,
+    NoPermission,
+    AccessError {
+        access_error: SharingFileAccessError,
+    },
+    NoExplicitAccess(serde_json::Value),
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum UpdateFolderMemberError {
+    AccessError {
+        access_error: SharedFolderAccessError,
+    },
+    MemberError {
+        member_error: SharedFolderMemberError,
+    },
+    NoExplicitAccess {
+        no_explicit_access: AddFolderMemberError,
+    },
+    InsufficientPlan,
+    NoPermission,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum UpdateFolderPolicyError {
+    AccessError {
+        access_error: SharedFolderAccessError,
+    },
+    NotOnTeam,
+    TeamPolicyDisallowsMemberPolicy,
+    DisallowedSharedLinkPolicy,
+    NoPermission,
+    TeamFolder,
+    #[serde(other)]
+    Other,
+}
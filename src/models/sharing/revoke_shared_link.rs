@@ -3,14 +3,11 @@ use super::RevokeSharedLinkArg;
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 type Request<'a> = RevokeSharedLinkRequest<'a>;
 type Response = RevokeSharedLinkResponse;
@@ -18,10 +15,11 @@ type RequestPayload = RevokeSharedLinkArg;
 /// `revoke_shared_link` returns `null` on success. Modelled as
 /// `serde_json::Value` to accept null without gymnastics.
 type ResponsePayload = serde_json::Value;
+type ResponseError = super::RevokeSharedLinkError;
 
 /// Revoke shared link
 /// <https://www.dropbox.com/developers/documentation/http/documentation#sharing-revoke_shared_link>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct RevokeSharedLinkRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -40,7 +38,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::SharingRevokeSharedLinkPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -0,0 +1,62 @@
+use crate::{
+    anyhow::Result,
+    endpoints::headers::Headers,
+    endpoints::Endpoint,
+    implement_service, implement_utils,
+    traits::{Service, Utils},
+};
+use serde::Deserialize;
+
+type Request<'a> = GroupsJobStatusGetRequest<'a>;
+type Response = GroupsJobStatusGetResponse;
+type RequestPayload = crate::models::sharing::PollArg;
+type ResponsePayload = super::PollEmptyResult;
+type ResponseError = super::GroupsPollError;
+
+/// Poll an asynchronous group delete or membership change
+/// <https://www.dropbox.com/developers/documentation/http/teams#team-groups-job_status-get>
+#[derive(Debug, Default)]
+pub struct GroupsJobStatusGetRequest<'a> {
+    pub access_token: &'a str,
+    pub payload: Option<RequestPayload>,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+pub struct GroupsJobStatusGetResponse {
+    pub payload: ResponsePayload,
+}
+
+implement_utils!(Request<'_>, RequestPayload);
+
+implement_service!(
+    Request<'_>,
+    Response,
+    ResponsePayload,
+    Endpoint::TeamGroupsJobStatusGetPost,
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
+);
+
+#[cfg(all(test, feature = "test-utils"))]
+mod tests {
+    use crate::TEST_AUTH_TOKEN;
+
+    use super::{Request, RequestPayload};
+
+    use tokio;
+
+    use crate::{
+        endpoints::{get_endpoint_url, headers::Headers, Endpoint},
+        implement_tests,
+        tests_utils::get_endpoint_test_body_response,
+        traits::Service,
+    };
+
+    implement_tests!(
+        Endpoint::TeamGroupsJobStatusGetPost,
+        vec![Headers::TestAuthorization, Headers::ContentTypeAppJson],
+        Request,
+        RequestPayload
+    );
+}
@@ -0,0 +1,60 @@
+use crate::{
+    anyhow::Result,
+    endpoints::headers::Headers,
+    endpoints::Endpoint,
+    implement_service, implement_utils,
+    traits::{Service, Utils},
+};
+use serde::Deserialize;
+
+type Request<'a> = GroupsListRequest<'a>;
+type Response = GroupsListResponse;
+type RequestPayload = super::GroupsListArg;
+type ResponsePayload = super::GroupsListResult;
+
+/// List the team's groups
+/// <https://www.dropbox.com/developers/documentation/http/teams#team-groups-list>
+#[derive(Debug, Default)]
+pub struct GroupsListRequest<'a> {
+    pub access_token: &'a str,
+    pub payload: Option<RequestPayload>,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+pub struct GroupsListResponse {
+    pub payload: ResponsePayload,
+}
+
+implement_utils!(Request<'_>, RequestPayload);
+
+implement_service!(
+    Request<'_>,
+    Response,
+    ResponsePayload,
+    Endpoint::TeamGroupsListPost,
+    vec![Headers::ContentTypeAppJson]
+);
+
+#[cfg(all(test, feature = "test-utils"))]
+mod tests {
+    use crate::TEST_AUTH_TOKEN;
+
+    use super::{Request, RequestPayload};
+
+    use tokio;
+
+    use crate::{
+        endpoints::{get_endpoint_url, headers::Headers, Endpoint},
+        implement_tests,
+        tests_utils::get_endpoint_test_body_response,
+        traits::Service,
+    };
+
+    implement_tests!(
+        Endpoint::TeamGroupsListPost,
+        vec![Headers::TestAuthorization, Headers::ContentTypeAppJson],
+        Request,
+        RequestPayload
+    );
+}
@@ -0,0 +1,62 @@
+use crate::{
+    anyhow::Result,
+    endpoints::headers::Headers,
+    endpoints::Endpoint,
+    implement_service, implement_utils,
+    traits::{Service, Utils},
+};
+use serde::Deserialize;
+
+type Request<'a> = GroupsListContinueRequest<'a>;
+type Response = GroupsListContinueResponse;
+type RequestPayload = super::GroupsListContinueArg;
+type ResponsePayload = super::GroupsListResult;
+type ResponseError = super::GroupsListContinueError;
+
+/// Continue listing groups
+/// <https://www.dropbox.com/developers/documentation/http/teams#team-groups-list-continue>
+#[derive(Debug, Default)]
+pub struct GroupsListContinueRequest<'a> {
+    pub access_token: &'a str,
+    pub payload: Option<RequestPayload>,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+pub struct GroupsListContinueResponse {
+    pub payload: ResponsePayload,
+}
+
+implement_utils!(Request<'_>, RequestPayload);
+
+implement_service!(
+    Request<'_>,
+    Response,
+    ResponsePayload,
+    Endpoint::TeamGroupsListContinuePost,
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
+);
+
+#[cfg(all(test, feature = "test-utils"))]
+mod tests {
+    use crate::TEST_AUTH_TOKEN;
+
+    use super::{Request, RequestPayload};
+
+    use tokio;
+
+    use crate::{
+        endpoints::{get_endpoint_url, headers::Headers, Endpoint},
+        implement_tests,
+        tests_utils::get_endpoint_test_body_response,
+        traits::Service,
+    };
+
+    implement_tests!(
+        Endpoint::TeamGroupsListContinuePost,
+        vec![Headers::TestAuthorization, Headers::ContentTypeAppJson],
+        Request,
+        RequestPayload
+    );
+}
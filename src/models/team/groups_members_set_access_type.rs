@@ -0,0 +1,62 @@
+use crate::{
+    anyhow::Result,
+    endpoints::headers::Headers,
+    endpoints::Endpoint,
+    implement_service, implement_utils,
+    traits::{Service, Utils},
+};
+use serde::Deserialize;
+
+type Request<'a> = GroupsMembersSetAccessTypeRequest<'a>;
+type Response = GroupsMembersSetAccessTypeResponse;
+type RequestPayload = super::GroupMembersSetAccessTypeArg;
+type ResponsePayload = Vec<super::GroupsGetInfoItem>;
+type ResponseError = super::GroupMemberSetAccessTypeError;
+
+/// Make a group member an owner or a plain member
+/// <https://www.dropbox.com/developers/documentation/http/teams#team-groups-members-set_access_type>
+#[derive(Debug, Default)]
+pub struct GroupsMembersSetAccessTypeRequest<'a> {
+    pub access_token: &'a str,
+    pub payload: Option<RequestPayload>,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+pub struct GroupsMembersSetAccessTypeResponse {
+    pub payload: ResponsePayload,
+}
+
+implement_utils!(Request<'_>, RequestPayload);
+
+implement_service!(
+    Request<'_>,
+    Response,
+    ResponsePayload,
+    Endpoint::TeamGroupsMembersSetAccessTypePost,
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
+);
+
+#[cfg(all(test, feature = "test-utils"))]
+mod tests {
+    use crate::TEST_AUTH_TOKEN;
+
+    use super::{Request, RequestPayload};
+
+    use tokio;
+
+    use crate::{
+        endpoints::{get_endpoint_url, headers::Headers, Endpoint},
+        implement_tests,
+        tests_utils::get_endpoint_test_body_response,
+        traits::Service,
+    };
+
+    implement_tests!(
+        Endpoint::TeamGroupsMembersSetAccessTypePost,
+        vec![Headers::TestAuthorization, Headers::ContentTypeAppJson],
+        Request,
+        RequestPayload
+    );
+}
@@ -0,0 +1,1161 @@
+//! Types and endpoints for the Dropbox `team` namespace.
+//!
+//! Every endpoint here needs a team access token (a team-linked app); the
+//! member endpoints act on the whole team, so no `Dropbox-API-Select-User`
+//! is needed.
+//!
+//! To walk every namespace in the team (member folders, shared and team
+//! folders), page through `namespaces/list` and scope a client to each one
+//! with [`NamespaceMetadata::path_root`]:
+//!
+//! ```ignore
+//! let scoped = admin.with_path_root(namespace.path_root());
+//! scoped.execute(&ListFolderRequest { .. }).await?;
+//! ```
+//!
+//! Member folders additionally need `with_select_admin` or
+//! `with_select_user` so the call runs as someone who can see them.
+//!
+//! Reference: <https://www.dropbox.com/developers/documentation/http/teams>
+
+// Member results nest the full profile as a union variant; see the note in
+// `files` on why the lint is silenced rather than boxing.
+#![allow(clippy::large_enum_variant)]
+
+pub mod get_info;
+pub mod groups_create;
+pub mod groups_delete;
+pub mod groups_get_info;
+pub mod groups_job_status_get;
+pub mod groups_list;
+pub mod groups_list_continue;
+pub mod groups_members_add;
+pub mod groups_members_list;
+pub mod groups_members_list_continue;
+pub mod groups_members_remove;
+pub mod groups_members_set_access_type;
+pub mod members_add;
+pub mod members_add_job_status_get;
+pub mod members_get_info;
+pub mod members_list;
+pub mod members_list_continue;
+pub mod members_remove;
+pub mod members_remove_job_status_get;
+pub mod members_set_profile;
+pub mod members_suspend;
+pub mod members_unsuspend;
+pub mod namespaces_list;
+pub mod namespaces_list_continue;
+pub mod team_folder_archive;
+pub mod team_folder_archive_check;
+pub mod team_folder_create;
+pub mod team_folder_list;
+pub mod team_folder_list_continue;
+pub mod team_folder_permanently_delete;
+pub mod team_folder_rename;
+pub mod team_folder_update_sync_settings;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::common::PathRoot;
+use crate::models::files::LookupError;
+use crate::models::sharing::GroupManagementType;
+use crate::models::users::{Name, OfficeAddInPolicy, TeamSharingPolicies};
+
+// ---- team/get_info ----
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamGetInfoResult {
+    pub name: String,
+    pub team_id: String,
+    pub num_licensed_users: u32,
+    pub num_provisioned_users: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub num_used_licenses: Option<u32>,
+    pub policies: TeamMemberPolicies,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamMemberPolicies {
+    pub sharing: TeamSharingPolicies,
+    pub emm_state: EmmState,
+    pub office_addin: OfficeAddInPolicy,
+    pub suggest_members_policy: SuggestMembersPolicy,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum EmmState {
+    Disabled,
+    Optional,
+    Required,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum SuggestMembersPolicy {
+    Disabled,
+    Enabled,
+    #[serde(other)]
+    Other,
+}
+
+// ---- member selectors and profiles ----
+
+/// Stone: `union UserSelectorArg { team_member_id String | external_id
+/// String | email String }`. Struct variants for the same reason as
+/// `sharing::MemberSelector`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum UserSelectorArg {
+    TeamMemberId { team_member_id: String },
+    ExternalId { external_id: String },
+    Email { email: String },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamMemberInfoV2 {
+    pub profile: TeamMemberProfile,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<TeamMemberRole>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamMemberProfile {
+    pub team_member_id: String,
+    pub email: String,
+    pub email_verified: bool,
+    pub status: TeamMemberStatus,
+    pub name: Name,
+    pub membership_type: TeamMembershipType,
+    pub member_folder_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secondary_emails: Option<Vec<SecondaryEmail>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invited_on: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub joined_on: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suspended_on: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persistent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_directory_restricted: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile_photo_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_folder_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SecondaryEmail {
+    pub email: String,
+    pub is_verified: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TeamMemberStatus {
+    Active,
+    Invited,
+    Suspended,
+    Removed(RemovedStatus),
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RemovedStatus {
+    pub is_recoverable: bool,
+    pub is_disconnected: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TeamMembershipType {
+    Full,
+    Limited,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamMemberRole {
+    pub role_id: String,
+    pub name: String,
+    pub description: String,
+}
+
+// ---- team/members/list_v2 (+ continue) ----
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct MembersListArg {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_removed: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MembersListContinueArg {
+    pub cursor: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MembersListV2Result {
+    pub members: Vec<TeamMemberInfoV2>,
+    pub cursor: String,
+    pub has_more: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum MembersListError {
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum MembersListContinueError {
+    InvalidCursor,
+    #[serde(other)]
+    Other,
+}
+
+// ---- team/members/get_info_v2 ----
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MembersGetInfoV2Arg {
+    pub members: Vec<UserSelectorArg>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MembersGetInfoV2Result {
+    pub members_info: Vec<TeamMemberInfoV2OrError>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TeamMemberInfoV2OrError {
+    /// The selector matched no member; carries the value that was looked up.
+    IdNotFound {
+        id_not_found: String,
+    },
+    MemberInfo(TeamMemberInfoV2),
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum MembersGetInfoError {
+    #[serde(other)]
+    Other,
+}
+
+// ---- team/members/add_v2 (+ job_status/get_v2) ----
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MembersAddV2Arg {
+    pub new_members: Vec<MemberAddV2Arg>,
+    /// Always return an `async_job_id`, even for small batches.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_async: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct MemberAddV2Arg {
+    pub member_email: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member_given_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member_surname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member_external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member_persistent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_welcome_email: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_directory_restricted: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role_ids: Option<Vec<String>>,
+}
+
+/// Either the finished batch or a job id to poll with
+/// `members/add/job_status/get`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum MembersAddLaunchV2Result {
+    AsyncJobId {
+        async_job_id: String,
+    },
+    Complete {
+        complete: Vec<MemberAddV2Result>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum MembersAddJobStatusV2Result {
+    InProgress,
+    Complete {
+        complete: Vec<MemberAddV2Result>,
+    },
+    /// The job failed; carries the reason.
+    Failed {
+        failed: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// Per-member outcome of an add. Every failure carries the email address
+/// it applies to.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum MemberAddV2Result {
+    Success(TeamMemberInfoV2),
+    TeamLicenseLimit {
+        team_license_limit: String,
+    },
+    FreeTeamMemberLimitReached {
+        free_team_member_limit_reached: String,
+    },
+    UserAlreadyOnTeam {
+        user_already_on_team: String,
+    },
+    UserOnAnotherTeam {
+        user_on_another_team: String,
+    },
+    UserAlreadyPaired {
+        user_already_paired: String,
+    },
+    UserMigrationFailed {
+        user_migration_failed: String,
+    },
+    DuplicateExternalMemberId {
+        duplicate_external_member_id: String,
+    },
+    DuplicateMemberPersistentId {
+        duplicate_member_persistent_id: String,
+    },
+    PersistentIdDisabled {
+        persistent_id_disabled: String,
+    },
+    UserCreationFailed {
+        user_creation_failed: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+// ---- team/members/suspend, unsuspend ----
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MembersDeactivateArg {
+    pub user: UserSelectorArg,
+    /// Wipe the member's data on linked devices. Defaults to true.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wipe_data: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MembersUnsuspendArg {
+    pub user: UserSelectorArg,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum MembersSuspendError {
+    UserNotFound,
+    UserNotInTeam,
+    SuspendInactiveUser,
+    SuspendLastAdmin,
+    TeamLicenseLimit,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum MembersUnsuspendError {
+    UserNotFound,
+    UserNotInTeam,
+    UnsuspendNonSuspendedMember,
+    TeamLicenseLimit,
+    #[serde(other)]
+    Other,
+}
+
+// ---- team/members/remove (+ job_status/get) ----
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MembersRemoveArg {
+    pub user: UserSelectorArg,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wipe_data: Option<bool>,
+    /// Member who receives the removed member's files.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_dest_id: Option<UserSelectorArg>,
+    /// Admin notified of transfer errors; required with `transfer_dest_id`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer_admin_id: Option<UserSelectorArg>,
+    /// Downgrade to a Basic account instead of deleting it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keep_account: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retain_team_shares: Option<bool>,
+}
+
+/// Async launch with no result payload: done already, or a job id to poll
+/// with `members/remove/job_status/get` or `groups/job_status/get`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum LaunchEmptyResult {
+    AsyncJobId {
+        async_job_id: String,
+    },
+    Complete,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum PollEmptyResult {
+    InProgress,
+    Complete,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum MembersRemoveError {
+    UserNotFound,
+    UserNotInTeam,
+    RemoveLastAdmin,
+    RemovedAndTransferDestShouldDiffer,
+    RemovedAndTransferAdminShouldDiffer,
+    TransferDestUserNotFound,
+    TransferDestUserNotInTeam,
+    TransferAdminUserNotInTeam,
+    TransferAdminUserNotFound,
+    UnspecifiedTransferAdminId,
+    TransferAdminIsNotAdmin,
+    RecipientNotVerified,
+    RemoveLastManagerException,
+    CannotKeepAccountAndDeleteData,
+    EmailAddressTooLongToBeDisabled,
+    CannotKeepInvitedUserAccount,
+    CannotRetainSharesWhenDataWiped,
+    CannotRetainSharesWhenNoAccountKept,
+    CannotRetainSharesWhenTeamExternalSharingOff,
+    CannotKeepAccount,
+    CannotKeepAccountUnderLegalHold,
+    CannotKeepAccountRequiredToSignTos,
+    #[serde(other)]
+    Other,
+}
+
+// ---- team/members/set_profile_v2 ----
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MembersSetProfileArg {
+    pub user: UserSelectorArg,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_given_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_surname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_persistent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_is_directory_restricted: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamMemberInfoV2Result {
+    pub member_info: TeamMemberInfoV2,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum MembersSetProfileError {
+    UserNotFound,
+    UserNotInTeam,
+    ExternalIdAndNewExternalIdUnsafe,
+    NoNewDataSpecified,
+    EmailReservedForOtherUser,
+    ExternalIdUsedByOtherUser,
+    SetProfileDisallowed,
+    ParamCannotBeEmpty,
+    PersistentIdDisabled,
+    PersistentIdUsedByOtherUser,
+    DirectoryRestrictedOff,
+    #[serde(other)]
+    Other,
+}
+
+// ---- team/groups/* ----
+
+/// Stone: `union GroupSelector { group_id String | group_external_id String }`.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GroupSelector {
+    GroupId { group_id: String },
+    GroupExternalId { group_external_id: String },
+}
+
+/// Several groups at once, all by id or all by external id.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GroupsSelector {
+    GroupIds { group_ids: Vec<String> },
+    GroupExternalIds { group_external_ids: Vec<String> },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GroupSummary {
+    pub group_name: String,
+    pub group_id: String,
+    pub group_management_type: GroupManagementType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member_count: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GroupFullInfo {
+    pub group_name: String,
+    pub group_id: String,
+    pub group_management_type: GroupManagementType,
+    /// Creation time, milliseconds since the epoch.
+    pub created: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub member_count: Option<u32>,
+    /// Only filled in when the request asked for members back.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<GroupMemberInfo>>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GroupMemberInfo {
+    pub profile: MemberProfile,
+    pub access_type: GroupAccessType,
+}
+
+/// Team member profile as reported inside group listings.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MemberProfile {
+    pub team_member_id: String,
+    pub email: String,
+    pub email_verified: bool,
+    pub status: TeamMemberStatus,
+    pub name: Name,
+    pub membership_type: TeamMembershipType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secondary_emails: Option<Vec<SecondaryEmail>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invited_on: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub joined_on: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suspended_on: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persistent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_directory_restricted: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile_photo_url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GroupAccessType {
+    Member,
+    Owner,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MemberAccess {
+    pub user: UserSelectorArg,
+    pub access_type: GroupAccessType,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct GroupsListArg {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GroupsListContinueArg {
+    pub cursor: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GroupsListResult {
+    pub groups: Vec<GroupSummary>,
+    pub cursor: String,
+    pub has_more: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GroupsListContinueError {
+    InvalidCursor,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GroupCreateArg {
+    pub group_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub add_creator_as_owner: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_management_type: Option<GroupManagementType>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GroupCreateError {
+    GroupNameAlreadyUsed,
+    GroupNameInvalid,
+    ExternalIdAlreadyInUse,
+    SystemManagedGroupDisallowed,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GroupDeleteError {
+    GroupNotFound,
+    SystemManagedGroupDisallowed,
+    GroupAlreadyDeleted,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GroupsGetInfoItem {
+    /// No group matched; carries the id that was looked up.
+    IdNotFound {
+        id_not_found: String,
+    },
+    GroupInfo(GroupFullInfo),
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GroupsGetInfoError {
+    GroupNotOnTeam,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GroupMembersAddArg {
+    pub group: GroupSelector,
+    pub members: Vec<MemberAccess>,
+    /// Return the group's member list in the result. Defaults to true.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_members: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GroupMembersRemoveArg {
+    pub group: GroupSelector,
+    pub users: Vec<UserSelectorArg>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_members: Option<bool>,
+}
+
+/// Result of adding or removing members. The change finishes in the
+/// background; poll `async_job_id` with `groups/job_status/get`.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GroupMembersChangeResult {
+    pub group_info: GroupFullInfo,
+    pub async_job_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GroupMembersAddError {
+    GroupNotFound,
+    SystemManagedGroupDisallowed,
+    DuplicateUser,
+    GroupNotInTeam,
+    /// Emails or ids of users who are not members of the team.
+    MembersNotInTeam {
+        members_not_in_team: Vec<String>,
+    },
+    UsersNotFound {
+        users_not_found: Vec<String>,
+    },
+    UserMustBeActiveToBeOwner,
+    UserCannotBeManagerOfCompanyManagedGroup {
+        user_cannot_be_manager_of_company_managed_group: Vec<String>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GroupMembersRemoveError {
+    GroupNotFound,
+    SystemManagedGroupDisallowed,
+    MemberNotInGroup,
+    GroupNotInTeam,
+    MembersNotInTeam {
+        members_not_in_team: Vec<String>,
+    },
+    UsersNotFound {
+        users_not_found: Vec<String>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GroupsMembersListArg {
+    pub group: GroupSelector,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GroupsMembersListContinueArg {
+    pub cursor: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GroupsMembersListResult {
+    pub members: Vec<GroupMemberInfo>,
+    pub cursor: String,
+    pub has_more: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GroupSelectorError {
+    GroupNotFound,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GroupsMembersListContinueError {
+    InvalidCursor,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GroupMembersSetAccessTypeArg {
+    pub group: GroupSelector,
+    pub user: UserSelectorArg,
+    pub access_type: GroupAccessType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_members: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GroupMemberSetAccessTypeError {
+    GroupNotFound,
+    SystemManagedGroupDisallowed,
+    MemberNotInGroup,
+    UserCannotBeManagerOfCompanyManagedGroup,
+    #[serde(other)]
+    Other,
+}
+
+/// Error union for `groups/job_status/get`: [`PollError`] plus
+/// `access_denied`.
+///
+/// [`PollError`]: crate::models::files::PollError
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GroupsPollError {
+    InvalidAsyncJobId,
+    InternalError,
+    AccessDenied,
+    #[serde(other)]
+    Other,
+}
+
+// ---- team/team_folder/* and team/namespaces/* ----
+
+/// Team folders live in their own namespace: `team_folder_id` is a shared
+/// folder id (see [`SharedFolderMetadata`]) and also the namespace id, so
+/// [`TeamFolderMetadata::path_root`] scopes a client to the folder.
+///
+/// [`SharedFolderMetadata`]: crate::models::sharing::SharedFolderMetadata
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamFolderMetadata {
+    pub team_folder_id: String,
+    pub name: String,
+    pub status: TeamFolderStatus,
+    pub is_team_shared_dropbox: bool,
+    pub sync_setting: SyncSetting,
+    pub content_sync_settings: Vec<ContentSyncSetting>,
+}
+
+impl TeamFolderMetadata {
+    /// `Dropbox-API-Path-Root` value addressing this team folder.
+    pub fn path_root(&self) -> PathRoot {
+        PathRoot::namespace_id(self.team_folder_id.clone())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TeamFolderStatus {
+    Active,
+    Archived,
+    ArchiveInProgress,
+    #[serde(other)]
+    Other,
+}
+
+/// Stone: `files.SyncSetting`, the folder's default on desktop clients.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum SyncSetting {
+    Default,
+    NotSynced,
+    NotSyncedInactive,
+    #[serde(other)]
+    Other,
+}
+
+/// Stone: `files.SyncSettingArg`, the values a caller may set.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum SyncSettingArg {
+    Default,
+    NotSynced,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ContentSyncSetting {
+    pub id: String,
+    pub sync_setting: SyncSetting,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ContentSyncSettingArg {
+    /// Id of a subfolder of the team folder.
+    pub id: String,
+    pub sync_setting: SyncSettingArg,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamFolderCreateArg {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sync_setting: Option<SyncSettingArg>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamFolderIdArg {
+    pub team_folder_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamFolderRenameArg {
+    pub team_folder_id: String,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamFolderArchiveArg {
+    pub team_folder_id: String,
+    /// Wait for the archive to finish instead of returning a job id.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_async_off: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamFolderUpdateSyncSettingsArg {
+    pub team_folder_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sync_setting: Option<SyncSettingArg>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_sync_settings: Option<Vec<ContentSyncSettingArg>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct TeamFolderListArg {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamFolderListContinueArg {
+    pub cursor: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamFolderListResult {
+    pub team_folders: Vec<TeamFolderMetadata>,
+    pub cursor: String,
+    pub has_more: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TeamFolderArchiveLaunch {
+    AsyncJobId {
+        async_job_id: String,
+    },
+    Complete(TeamFolderMetadata),
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TeamFolderArchiveJobStatus {
+    InProgress,
+    Complete(TeamFolderMetadata),
+    Failed {
+        failed: BaseTeamFolderError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TeamFolderAccessError {
+    InvalidTeamFolderId,
+    NoAccess,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TeamFolderInvalidStatusError {
+    Active,
+    Archived,
+    ArchiveInProgress,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TeamSharedDropboxError {
+    Disallowed,
+    #[serde(other)]
+    Other,
+}
+
+/// Stone: `files.SyncSettingsError`.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum SyncSettingsError {
+    Path {
+        path: LookupError,
+    },
+    UnsupportedCombination,
+    UnsupportedConfiguration,
+    #[serde(other)]
+    Other,
+}
+
+/// Shared by archive, permanently_delete and the archive job status.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum BaseTeamFolderError {
+    AccessError {
+        access_error: TeamFolderAccessError,
+    },
+    StatusError {
+        status_error: TeamFolderInvalidStatusError,
+    },
+    TeamSharedDropboxError {
+        team_shared_dropbox_error: TeamSharedDropboxError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TeamFolderCreateError {
+    InvalidFolderName,
+    FolderNameAlreadyUsed,
+    FolderNameReserved,
+    SyncSettingsError {
+        sync_settings_error: SyncSettingsError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TeamFolderRenameError {
+    AccessError {
+        access_error: TeamFolderAccessError,
+    },
+    StatusError {
+        status_error: TeamFolderInvalidStatusError,
+    },
+    TeamSharedDropboxError {
+        team_shared_dropbox_error: TeamSharedDropboxError,
+    },
+    InvalidFolderName,
+    FolderNameAlreadyUsed,
+    FolderNameReserved,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TeamFolderUpdateSyncSettingsError {
+    AccessError {
+        access_error: TeamFolderAccessError,
+    },
+    StatusError {
+        status_error: TeamFolderInvalidStatusError,
+    },
+    TeamSharedDropboxError {
+        team_shared_dropbox_error: TeamSharedDropboxError,
+    },
+    SyncSettingsError {
+        sync_settings_error: SyncSettingsError,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// `team_folder/list` fails with a struct rather than a union.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamFolderListError {
+    pub access_error: TeamFolderAccessError,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TeamFolderListContinueError {
+    InvalidCursor,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct TeamNamespacesListArg {
+    /// Page size, at most 1000 (the server default).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamNamespacesListContinueArg {
+    pub cursor: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TeamNamespacesListResult {
+    pub namespaces: Vec<NamespaceMetadata>,
+    pub cursor: String,
+    pub has_more: bool,
+}
+
+/// One namespace in the team: a member's home folder, a shared or team
+/// folder, or an app folder.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NamespaceMetadata {
+    pub name: String,
+    pub namespace_id: String,
+    pub namespace_type: NamespaceType,
+    /// Owner of a `team_member_folder` or `app_folder`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team_member_id: Option<String>,
+}
+
+impl NamespaceMetadata {
+    /// `Dropbox-API-Path-Root` value addressing this namespace, for
+    /// [`Client::with_path_root`](crate::Client::with_path_root).
+    pub fn path_root(&self) -> PathRoot {
+        PathRoot::namespace_id(self.namespace_id.clone())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum NamespaceType {
+    AppFolder,
+    SharedFolder,
+    TeamFolder,
+    TeamMemberFolder,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TeamNamespacesListError {
+    InvalidArg,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum TeamNamespacesListContinueError {
+    InvalidArg,
+    InvalidCursor,
+    #[serde(other)]
+    Other,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn namespaces_map_to_path_roots() {
+        let ns: NamespaceMetadata = serde_json::from_str(
+            r#"{"name":"Marketing","namespace_id":"123456789","namespace_type":{".tag":"team_folder"}}"#,
+        )
+        .unwrap();
+        assert_eq!(ns.namespace_type, NamespaceType::TeamFolder);
+        assert_eq!(ns.path_root(), PathRoot::namespace_id("123456789"));
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    anyhow::Result,
+    endpoints::headers::Headers,
+    endpoints::Endpoint,
+    implement_service, implement_utils,
+    traits::{Service, Utils},
+};
+use serde::Deserialize;
+
+type Request<'a> = TeamFolderArchiveCheckRequest<'a>;
+type Response = TeamFolderArchiveCheckResponse;
+type RequestPayload = crate::models::sharing::PollArg;
+type ResponsePayload = super::TeamFolderArchiveJobStatus;
+type ResponseError = crate::models::files::PollError;
+
+/// Poll an asynchronous team folder archive
+/// <https://www.dropbox.com/developers/documentation/http/teams#team-team_folder-archive-check>
+#[derive(Debug, Default)]
+pub struct TeamFolderArchiveCheckRequest<'a> {
+    pub access_token: &'a str,
+    pub payload: Option<RequestPayload>,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+pub struct TeamFolderArchiveCheckResponse {
+    pub payload: ResponsePayload,
+}
+
+implement_utils!(Request<'_>, RequestPayload);
+
+implement_service!(
+    Request<'_>,
+    Response,
+    ResponsePayload,
+    Endpoint::TeamTeamFolderArchiveCheckPost,
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
+);
+
+#[cfg(all(test, feature = "test-utils"))]
+mod tests {
+    use crate::TEST_AUTH_TOKEN;
+
+    use super::{Request, RequestPayload};
+
+    use tokio;
+
+    use crate::{
+        endpoints::{get_endpoint_url, headers::Headers, Endpoint},
+        implement_tests,
+        tests_utils::get_endpoint_test_body_response,
+        traits::Service,
+    };
+
+    implement_tests!(
+        Endpoint::TeamTeamFolderArchiveCheckPost,
+        vec![Headers::TestAuthorization, Headers::ContentTypeAppJson],
+        Request,
+        RequestPayload
+    );
+}
@@ -0,0 +1,62 @@
+use crate::{
+    anyhow::Result,
+    endpoints::headers::Headers,
+    endpoints::Endpoint,
+    implement_service, implement_utils,
+    traits::{Service, Utils},
+};
+use serde::Deserialize;
+
+type Request<'a> = TeamFolderUpdateSyncSettingsRequest<'a>;
+type Response = TeamFolderUpdateSyncSettingsResponse;
+type RequestPayload = super::TeamFolderUpdateSyncSettingsArg;
+type ResponsePayload = super::TeamFolderMetadata;
+type ResponseError = super::TeamFolderUpdateSyncSettingsError;
+
+/// Change the desktop sync defaults of a team folder
+/// <https://www.dropbox.com/developers/documentation/http/teams#team-team_folder-update_sync_settings>
+#[derive(Debug, Default)]
+pub struct TeamFolderUpdateSyncSettingsRequest<'a> {
+    pub access_token: &'a str,
+    pub payload: Option<RequestPayload>,
+}
+
+#[derive(Deserialize, Debug)]
+#[allow(dead_code)]
+pub struct TeamFolderUpdateSyncSettingsResponse {
+    pub payload: ResponsePayload,
+}
+
+implement_utils!(Request<'_>, RequestPayload);
+
+implement_service!(
+    Request<'_>,
+    Response,
+    ResponsePayload,
+    Endpoint::TeamTeamFolderUpdateSyncSettingsPost,
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
+);
+
+#[cfg(all(test, feature = "test-utils"))]
+mod tests {
+    use crate::TEST_AUTH_TOKEN;
+
+    use super::{Request, RequestPayload};
+
+    use tokio;
+
+    use crate::{
+        endpoints::{get_endpoint_url, headers::Headers, Endpoint},
+        implement_tests,
+        tests_utils::get_endpoint_test_body_response,
+        traits::Service,
+    };
+
+    implement_tests!(
+        Endpoint::TeamTeamFolderUpdateSyncSettingsPost,
+        vec![Headers::TestAuthorization, Headers::ContentTypeAppJson],
+        Request,
+        RequestPayload
+    );
+}
@@ -3,23 +3,21 @@ use super::{UserFeaturesGetValuesBatchArg, UserFeaturesGetValuesBatchResult};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 type Request<'a> = FeaturesGetValuesRequest<'a>;
 type Response = FeaturesGetValuesResponse;
 type RequestPayload = UserFeaturesGetValuesBatchArg;
 type ResponsePayload = UserFeaturesGetValuesBatchResult;
+type ResponseError = super::UserFeaturesGetValuesBatchError;
 
 /// Features get values
 /// <https://www.dropbox.com/developers/documentation/http/documentation#users-features-get_values>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct FeaturesGetValuesRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -38,7 +36,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::UsersFeaturesGetValuesPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -3,23 +3,21 @@ use super::{BasicAccount, GetAccountArg};
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 type Request<'a> = GetAccountRequest<'a>;
 type Response = GetAccountResponse;
 type RequestPayload = GetAccountArg;
 type ResponsePayload = BasicAccount;
+type ResponseError = super::GetAccountError;
 
 /// Get account
 /// <https://www.dropbox.com/developers/documentation/http/documentation#users-get_account>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GetAccountRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -38,7 +36,8 @@ implement_service!(
     Response,
     ResponsePayload,
     Endpoint::UsersGetAccountPost,
-    vec![Headers::ContentTypeAppJson]
+    vec![Headers::ContentTypeAppJson],
+    ResponseError
 );
 
 #[cfg(all(test, feature = "test-utils"))]
@@ -3,14 +3,11 @@ use super::FullAccount;
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 /// `/users/get_current_account` takes no body. The payload type is `()` to
 /// match the Request-struct shape used across the crate.
@@ -21,7 +18,7 @@ type ResponsePayload = FullAccount;
 
 /// Get current account
 /// <https://www.dropbox.com/developers/documentation/http/documentation#users-get_current_account>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GetCurrentAccountRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
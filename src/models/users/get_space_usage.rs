@@ -3,14 +3,11 @@ use super::SpaceUsage;
 use crate::{
     anyhow::Result,
     endpoints::headers::Headers,
-    endpoints::{get_endpoint_url, Endpoint},
-    errors::ApiError,
+    endpoints::Endpoint,
     implement_service, implement_utils,
     traits::{Service, Utils},
-    AsyncClient, SyncClient,
 };
 use serde::Deserialize;
-use std::{future::Future, pin::Pin};
 
 type Request<'a> = GetSpaceUsageRequest<'a>;
 type Response = GetSpaceUsageResponse;
@@ -19,7 +16,7 @@ type ResponsePayload = SpaceUsage;
 
 /// Get space usage
 /// <https://www.dropbox.com/developers/documentation/http/documentation#users-get_space_usage>
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct GetSpaceUsageRequest<'a> {
     pub access_token: &'a str,
     pub payload: Option<RequestPayload>,
@@ -248,3 +248,31 @@ pub struct PaperAsFilesValue {
 pub struct FileLockingValue {
     pub enabled: bool,
 }
+
+// Error unions
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GetAccountError {
+    NoAccount,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum GetAccountBatchError {
+    NoAccount {
+        no_account: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = ".tag", rename_all = "snake_case")]
+pub enum UserFeaturesGetValuesBatchError {
+    EmptyFeaturesList,
+    #[serde(other)]
+    Other,
+}
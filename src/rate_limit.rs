@@ -0,0 +1,168 @@
+//! Client-wide request throttling.
+//!
+//! A [`RateLimiter`] attached with
+//! [`Client::with_rate_limiter`](crate::Client::with_rate_limiter) is shared
+//! by every clone and derived client, so hundreds of tasks on one token
+//! queue up behind a single budget instead of each discovering the limit on
+//! its own:
+//!
+//! ```ignore
+//! let limiter = Arc::new(RateLimiter::per_second(10.0).with_burst(20));
+//! let client = Client::new(token).with_rate_limiter(limiter);
+//! ```
+//!
+//! Two mechanisms, both optional:
+//! - a token bucket capping the steady request rate, and
+//! - a pause window: any 429 seen by any request holds back every request
+//!   through the limiter until its `Retry-After` has passed.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Longest spacing `per_second` sets up, so that slot arithmetic on tiny
+/// rates stays far from `Instant` / `Duration` overflow.
+const MAX_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Shared request budget. See the [module docs](self).
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Minimum spacing between requests, `None` for no steady-state cap.
+    interval: Option<Duration>,
+    burst: u32,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    /// When the bucket is next empty ("theoretical arrival time"); requests
+    /// may run up to `burst - 1` intervals ahead of it.
+    next_slot: Option<Instant>,
+    /// No request leaves before this instant.
+    paused_until: Option<Instant>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RateLimiter {
+    /// A limiter with no rate cap that only shares 429 pauses.
+    pub fn new() -> Self {
+        Self {
+            interval: None,
+            burst: 1,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Cap the steady rate at `requests` per second (burst of one). Values
+    /// `<= 0` mean no cap; rates below one request a day are raised to one
+    /// a day.
+    pub fn per_second(requests: f64) -> Self {
+        Self {
+            interval: (requests > 0.0).then(|| {
+                Duration::try_from_secs_f64(1.0 / requests)
+                    .map_or(MAX_INTERVAL, |interval| interval.min(MAX_INTERVAL))
+            }),
+            ..Self::new()
+        }
+    }
+
+    /// Let up to `burst` requests through back to back after an idle spell.
+    pub fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = burst.max(1);
+        self
+    }
+
+    /// Hold back every request through this limiter for `wait`. Extends,
+    /// never shortens, a pause already in force.
+    pub fn pause_for(&self, wait: Duration) {
+        let until = Instant::now() + wait;
+        let mut state = self.state.lock().unwrap();
+        if state.paused_until.map_or(true, |current| current < until) {
+            state.paused_until = Some(until);
+        }
+    }
+
+    /// Claim the next slot and return how long to wait before using it.
+    fn reserve(&self) -> Duration {
+        let now = Instant::now();
+        let mut state = self.state.lock().unwrap();
+        let start = state.paused_until.map_or(now, |until| until.max(now));
+        let send_at = match self.interval {
+            None => start,
+            Some(interval) => {
+                let slot = state.next_slot.map_or(start, |slot| slot.max(start));
+                let tolerance = interval * (self.burst - 1);
+                state.next_slot = Some(slot + interval);
+                slot.checked_sub(tolerance).map_or(start, |t| t.max(start))
+            }
+        };
+        send_at - now
+    }
+
+    /// Wait for a slot (async).
+    pub(crate) async fn acquire(&self) {
+        let wait = self.reserve();
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Wait for a slot (blocking).
+    pub(crate) fn acquire_sync(&self) {
+        let wait = self.reserve();
+        if !wait.is_zero() {
+            std::thread::sleep(wait);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_never_waits() {
+        let limiter = RateLimiter::new();
+        for _ in 0..100 {
+            assert_eq!(limiter.reserve(), Duration::ZERO);
+        }
+    }
+
+    #[test]
+    fn spaces_requests_after_the_burst() {
+        let limiter = RateLimiter::per_second(10.0).with_burst(3);
+        for _ in 0..3 {
+            assert!(limiter.reserve() < Duration::from_millis(5));
+        }
+        let fourth = limiter.reserve();
+        assert!(fourth > Duration::from_millis(90) && fourth <= Duration::from_millis(100));
+        let fifth = limiter.reserve();
+        assert!(fifth > Duration::from_millis(190) && fifth <= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn tiny_rates_saturate_instead_of_overflowing() {
+        for rate in [1e-20, f64::MIN_POSITIVE, 5e-324] {
+            let limiter = RateLimiter::per_second(rate).with_burst(u32::MAX);
+            assert_eq!(limiter.interval, Some(MAX_INTERVAL));
+            assert_eq!(limiter.reserve(), Duration::ZERO);
+        }
+        let limiter = RateLimiter::per_second(1e-20);
+        limiter.reserve();
+        assert!(limiter.reserve() > MAX_INTERVAL - Duration::from_secs(1));
+        assert_eq!(RateLimiter::per_second(f64::NAN).interval, None);
+    }
+
+    #[test]
+    fn pause_holds_back_everyone_and_only_extends() {
+        let limiter = RateLimiter::new();
+        limiter.pause_for(Duration::from_secs(5));
+        limiter.pause_for(Duration::from_secs(1));
+        let wait = limiter.reserve();
+        assert!(wait > Duration::from_secs(4) && wait <= Duration::from_secs(5));
+    }
+}
@@ -0,0 +1,186 @@
+//! Retry policy for 429, 5xx and transport failures.
+//!
+//! Every endpoint call made through a [`Client`](crate::Client) consults the
+//! client's [`RetryPolicy`]. Derive a client with
+//! [`Client::with_retry_policy`](crate::Client::with_retry_policy) to change
+//! it — including `RetryPolicy::none()` to switch retries off for a single
+//! request:
+//!
+//! ```ignore
+//! client.with_retry_policy(RetryPolicy::none()).execute(&req).await?;
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+/// How failed calls are retried.
+///
+/// A response is retryable when its status is 429 or 5xx. A transport error
+/// (connection reset, timeout, DNS) is retryable only when
+/// `retry_network_errors` is set *and* the endpoint is read-only, since a
+/// write may already have been applied server-side.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total attempts including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    /// Stop retrying once this much time has passed since the first attempt
+    /// (the wait before the next attempt counts too).
+    pub max_elapsed: Option<Duration>,
+    /// Backoff before the first retry; doubled after each further one.
+    pub initial_backoff: Duration,
+    /// Upper bound for the exponential backoff.
+    pub max_backoff: Duration,
+    /// Randomise each backoff between half and the full value, so clients
+    /// that failed together don't retry in lockstep.
+    pub jitter: bool,
+    /// Longest `Retry-After` the SDK will wait out. A 429 asking for more is
+    /// returned to the caller straight away.
+    pub max_retry_after: Duration,
+    /// Retry transport errors on idempotent (read-only) endpoints.
+    pub retry_network_errors: bool,
+}
+
+impl Default for RetryPolicy {
+    /// Four attempts, 1s → 2s → 4s jittered backoff, `Retry-After` honoured
+    /// up to a minute, network errors retried on read-only endpoints.
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            max_elapsed: Some(Duration::from_secs(120)),
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            max_retry_after: Duration::from_secs(60),
+            retry_network_errors: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Backoff before retry number `retry` (0-based), before jitter.
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u32.checked_shl(retry).unwrap_or(u32::MAX);
+        self.initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |d| d.min(self.max_backoff))
+    }
+
+    /// How long to wait before the next attempt, or `None` to give up.
+    ///
+    /// `attempt` is the 1-based number of the attempt that just failed and
+    /// `retry_after` the server's hint, if any.
+    pub(crate) fn next_delay(
+        &self,
+        attempt: u32,
+        elapsed: Duration,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        let delay = match retry_after {
+            Some(hint) if hint > self.max_retry_after => return None,
+            Some(hint) => hint,
+            None => {
+                let base = self.backoff(attempt - 1);
+                if self.jitter {
+                    base / 2 + base.mul_f64(random_unit() / 2.0)
+                } else {
+                    base
+                }
+            }
+        };
+        if let Some(limit) = self.max_elapsed {
+            if elapsed + delay > limit {
+                return None;
+            }
+        }
+        Some(delay)
+    }
+}
+
+/// True for responses worth retrying: 429 and any 5xx.
+pub(crate) fn is_retryable_status(status: u16) -> bool {
+    status == 429 || (500..600).contains(&status)
+}
+
+/// Uniform-ish value in `[0, 1)`. `RandomState` is randomly keyed per
+/// instance, which is plenty for spreading out retries and saves a `rand`
+/// dependency.
+fn random_unit() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixed() -> RetryPolicy {
+        RetryPolicy {
+            jitter: false,
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[test]
+    fn backs_off_exponentially_up_to_max_attempts() {
+        let p = fixed();
+        let zero = Duration::ZERO;
+        assert_eq!(p.next_delay(1, zero, None), Some(Duration::from_secs(1)));
+        assert_eq!(p.next_delay(2, zero, None), Some(Duration::from_secs(2)));
+        assert_eq!(p.next_delay(3, zero, None), Some(Duration::from_secs(4)));
+        assert_eq!(p.next_delay(4, zero, None), None);
+    }
+
+    #[test]
+    fn caps_backoff_and_elapsed() {
+        let p = RetryPolicy {
+            max_attempts: 20,
+            max_elapsed: Some(Duration::from_secs(100)),
+            ..fixed()
+        };
+        assert_eq!(
+            p.next_delay(10, Duration::ZERO, None),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(p.next_delay(10, Duration::from_secs(80), None), None);
+    }
+
+    #[test]
+    fn honours_retry_after_up_to_cap() {
+        let p = fixed();
+        let zero = Duration::ZERO;
+        assert_eq!(
+            p.next_delay(1, zero, Some(Duration::from_secs(7))),
+            Some(Duration::from_secs(7))
+        );
+        assert_eq!(p.next_delay(1, zero, Some(Duration::from_secs(300))), None);
+    }
+
+    #[test]
+    fn jitter_stays_within_half_and_full_backoff() {
+        let p = RetryPolicy::default();
+        for _ in 0..100 {
+            let d = p.next_delay(3, Duration::ZERO, None).unwrap();
+            assert!(d >= Duration::from_secs(2) && d <= Duration::from_secs(4));
+        }
+    }
+
+    #[test]
+    fn none_never_retries() {
+        assert_eq!(
+            RetryPolicy::none().next_delay(1, Duration::ZERO, None),
+            None
+        );
+    }
+}
@@ -0,0 +1,295 @@
+//! `tracing` instrumentation, compiled in with the `tracing` cargo feature.
+//!
+//! Every endpoint call runs inside a `dropbox.call` span carrying the route,
+//! host, attempt number, final status, latency and `X-Dropbox-Request-Id`.
+//! Retries, token refreshes and upload-session steps are events inside it.
+//! Access tokens are never recorded, and `Dropbox-API-Arg` (which holds
+//! paths) only when the client opts in with `Client::with_traced_api_args`.
+//!
+//! Without the feature every hook here is an empty inline function.
+
+#[cfg(feature = "tracing")]
+mod imp {
+    use anyhow::Result;
+    use std::future::Future;
+    use std::time::{Duration, Instant};
+    use tracing::field::Empty;
+    use tracing::Instrument;
+
+    use crate::dispatch::PreparedRequest;
+    use crate::transport::HttpResponse;
+    use crate::Client;
+
+    pub(crate) struct CallSpan(tracing::Span);
+
+    pub(crate) fn call_span(client: &Client, request: &PreparedRequest) -> CallSpan {
+        let span = tracing::info_span!(
+            "dropbox.call",
+            endpoint = %request.endpoint.route(),
+            host = host(&request.url),
+            attempt = Empty,
+            status = Empty,
+            latency_ms = Empty,
+            request_id = Empty,
+            api_arg = Empty,
+        );
+        if client.traces_api_args() {
+            if let Some((_, arg)) = request
+                .headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case("Dropbox-API-Arg"))
+            {
+                span.record("api_arg", arg.as_str());
+            }
+        }
+        CallSpan(span)
+    }
+
+    impl CallSpan {
+        pub(crate) fn enter(&self) -> tracing::span::Entered<'_> {
+            self.0.enter()
+        }
+
+        pub(crate) fn instrument<F: Future>(&self, future: F) -> impl Future<Output = F::Output> {
+            future.instrument(self.0.clone())
+        }
+
+        /// Record the overall outcome once retries and replays are done.
+        pub(crate) fn finish(&self, started: Instant, outcome: &Result<HttpResponse>) {
+            self.0
+                .record("latency_ms", started.elapsed().as_millis() as u64);
+            match outcome {
+                Ok(response) => {
+                    self.0.record("status", response.status);
+                    if let Some(id) = response.header("X-Dropbox-Request-Id") {
+                        self.0.record("request_id", id);
+                    }
+                }
+                Err(err) => tracing::warn!(error = %err, "request failed"),
+            }
+        }
+    }
+
+    pub(crate) fn attempt(attempt: u32, started: Instant, outcome: &Result<HttpResponse>) {
+        tracing::Span::current().record("attempt", attempt);
+        let latency_ms = started.elapsed().as_millis() as u64;
+        match outcome {
+            Ok(response) => tracing::debug!(
+                attempt,
+                status = response.status,
+                latency_ms,
+                request_id = response.header("X-Dropbox-Request-Id"),
+                "attempt finished"
+            ),
+            Err(err) => tracing::debug!(attempt, latency_ms, error = %err, "attempt failed"),
+        }
+    }
+
+    pub(crate) fn retrying(attempt: u32, wait: Duration, outcome: &Result<HttpResponse>) {
+        let wait_ms = wait.as_millis() as u64;
+        match outcome {
+            Ok(response) => {
+                tracing::info!(attempt, wait_ms, status = response.status, "retrying")
+            }
+            Err(err) => tracing::info!(attempt, wait_ms, error = %err, "retrying"),
+        }
+    }
+
+    pub(crate) fn token_refresh(reason: &'static str) {
+        tracing::info!(reason, "refreshing access token");
+    }
+
+    pub(crate) fn token_refreshed(expires_in: u64) {
+        tracing::info!(expires_in, "access token refreshed");
+    }
+
+    pub(crate) fn token_refresh_failed(err: &anyhow::Error) {
+        tracing::warn!(error = %err, "access token refresh failed");
+    }
+
+    pub(crate) fn token_store_failed(err: &anyhow::Error) {
+        tracing::warn!(error = %err, "saving access token failed");
+    }
+
+    pub(crate) fn upload_step(step: &'static str, session_id: &str, offset: u64, len: usize) {
+        tracing::debug!(step, session_id, offset, len, "upload session");
+    }
+
+    pub(super) fn host(url: &str) -> &str {
+        let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+        rest.split('/').next().unwrap_or(rest)
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+mod imp {
+    use anyhow::Result;
+    use std::future::Future;
+    use std::time::{Duration, Instant};
+
+    use crate::dispatch::PreparedRequest;
+    use crate::transport::HttpResponse;
+    use crate::Client;
+
+    pub(crate) struct CallSpan;
+
+    /// Stand-in for `tracing::span::Entered`.
+    pub(crate) struct Entered;
+
+    #[inline]
+    pub(crate) fn call_span(_client: &Client, _request: &PreparedRequest) -> CallSpan {
+        CallSpan
+    }
+
+    impl CallSpan {
+        #[inline]
+        pub(crate) fn enter(&self) -> Entered {
+            Entered
+        }
+
+        #[inline]
+        pub(crate) fn instrument<F: Future>(&self, future: F) -> F {
+            future
+        }
+
+        #[inline]
+        pub(crate) fn finish(&self, _started: Instant, _outcome: &Result<HttpResponse>) {}
+    }
+
+    #[inline]
+    pub(crate) fn attempt(_attempt: u32, _started: Instant, _outcome: &Result<HttpResponse>) {}
+
+    #[inline]
+    pub(crate) fn retrying(_attempt: u32, _wait: Duration, _outcome: &Result<HttpResponse>) {}
+
+    #[inline]
+    pub(crate) fn token_refresh(_reason: &'static str) {}
+
+    #[inline]
+    pub(crate) fn token_refreshed(_expires_in: u64) {}
+
+    #[inline]
+    pub(crate) fn token_refresh_failed(_err: &anyhow::Error) {}
+
+    #[inline]
+    pub(crate) fn token_store_failed(_err: &anyhow::Error) {}
+
+    #[inline]
+    pub(crate) fn upload_step(_step: &'static str, _session_id: &str, _offset: u64, _len: usize) {}
+}
+
+pub(crate) use imp::*;
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::fmt::Write as _;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata};
+
+    use crate::api::files::upload_session_start::UploadSessionStartRequest;
+    use crate::api::files::UploadSessionStartArg;
+    use crate::tests_utils::ScriptedTransport;
+    use crate::Client;
+
+    /// Subscriber that writes every span field and event as `name=value`
+    /// text, so tests can look at exactly what a collector would get.
+    #[derive(Default)]
+    struct Capture {
+        next_id: AtomicU64,
+        fields: Arc<Mutex<Vec<String>>>,
+    }
+
+    struct Fields<'a>(&'a mut Vec<String>);
+
+    impl Visit for Fields<'_> {
+        fn record_str(&mut self, field: &Field, value: &str) {
+            self.0.push(format!("{}={value}", field.name()));
+        }
+
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            let mut text = format!("{}=", field.name());
+            let _ = write!(text, "{value:?}");
+            self.0.push(text);
+        }
+    }
+
+    impl tracing::Subscriber for Capture {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            span.record(&mut Fields(&mut self.fields.lock().unwrap()));
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _: &Id, values: &Record<'_>) {
+            values.record(&mut Fields(&mut self.fields.lock().unwrap()));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut Fields(&mut self.fields.lock().unwrap()));
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    /// Run one `upload_session/start` call and return what was traced.
+    fn traced_call(trace_api_args: bool) -> Vec<String> {
+        let fake = ScriptedTransport::new();
+        fake.reply(200, &[], r#"{"session_id":"s1"}"#);
+        let client = Client::new("secret-token")
+            .with_transport(fake)
+            .with_traced_api_args(trace_api_args);
+        let req = UploadSessionStartRequest {
+            payload: Some(UploadSessionStartArg {
+                close: Some(false),
+                session_type: None,
+                content_hash: None,
+            }),
+            data: Some(b"chunk".to_vec()),
+            ..Default::default()
+        };
+
+        let capture = Capture::default();
+        let fields = capture.fields.clone();
+        tracing::subscriber::with_default(capture, || {
+            client.execute_sync(&req).expect("call succeeds");
+        });
+        let fields = fields.lock().unwrap().clone();
+        fields
+    }
+
+    #[test]
+    fn api_arg_is_traced_only_when_enabled_and_the_token_never_is() {
+        let off = traced_call(false);
+        assert!(off
+            .iter()
+            .any(|f| f == "endpoint=files/upload_session/start"));
+        assert!(off.iter().any(|f| f == "status=200"));
+        assert!(!off.iter().any(|f| f.starts_with("api_arg=")));
+
+        let on = traced_call(true);
+        assert!(on.iter().any(|f| f == r#"api_arg={"close":false}"#));
+
+        for field in off.iter().chain(&on) {
+            assert!(!field.contains("secret-token"), "token traced: {field}");
+        }
+    }
+
+    #[test]
+    fn host_strips_scheme_and_path() {
+        assert_eq!(
+            super::imp::host("https://api.dropboxapi.com/2/files/list_folder"),
+            "api.dropboxapi.com"
+        );
+        assert_eq!(super::imp::host("127.0.0.1:1234/2/x"), "127.0.0.1:1234");
+    }
+}
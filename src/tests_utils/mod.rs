@@ -67,6 +67,72 @@ where
     result
 }
 
+/// In-process [`Transport`](crate::transport::Transport) for tests that
+/// don't need a real socket: replays a script of canned outcomes in order
+/// and records every request it was handed.
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct ScriptedTransport {
+    replies: std::sync::Mutex<
+        std::collections::VecDeque<anyhow::Result<crate::transport::HttpResponse>>,
+    >,
+    pub seen: std::sync::Mutex<Vec<crate::transport::HttpRequest>>,
+}
+
+#[cfg(test)]
+impl ScriptedTransport {
+    pub fn new() -> std::sync::Arc<Self> {
+        std::sync::Arc::new(Self::default())
+    }
+
+    /// Queue a response.
+    pub fn reply(&self, status: u16, headers: &[(&str, &str)], body: &str) -> &Self {
+        let headers = headers
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        self.replies
+            .lock()
+            .unwrap()
+            .push_back(Ok(crate::transport::HttpResponse::new(
+                status,
+                headers,
+                body.to_string(),
+            )));
+        self
+    }
+
+    /// Queue a transport-level failure (connection reset and the like).
+    pub fn fail(&self, message: &'static str) -> &Self {
+        self.replies
+            .lock()
+            .unwrap()
+            .push_back(Err(anyhow::anyhow!(message)));
+        self
+    }
+
+    pub fn calls(&self) -> usize {
+        self.seen.lock().unwrap().len()
+    }
+}
+
+#[cfg(test)]
+impl crate::transport::Transport for ScriptedTransport {
+    fn send(
+        &self,
+        request: crate::transport::HttpRequest,
+    ) -> futures::future::BoxFuture<'_, anyhow::Result<crate::transport::HttpResponse>> {
+        self.seen.lock().unwrap().push(request);
+        let next = self
+            .replies
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("ScriptedTransport ran out of replies");
+        Box::pin(async move { next })
+    }
+}
+
 #[cfg(feature = "test-utils")]
 pub fn get_endpoint_test_body_response(
     endpoint: Endpoint,
@@ -4588,6 +4654,1324 @@ pub fn get_endpoint_test_body_response(
     "preview_url": "https://www.dropbox.com/scl/fo/fir9vjelf",
     "shared_folder_id": "84528192421",
     "time_invited": "2016-01-20T00:00:00Z"
+}"##,
+            ),
+        ),
+        Endpoint::TeamGetInfoPost => (
+            None,
+            Some(
+                r##"{
+    "name": "Dropbox Inc.",
+    "num_licensed_users": 5,
+    "num_provisioned_users": 2,
+    "num_used_licenses": 1,
+    "policies": {
+        "emm_state": {
+            ".tag": "disabled"
+        },
+        "office_addin": {
+            ".tag": "disabled"
+        },
+        "sharing": {
+            "group_creation_policy": {
+                ".tag": "admins_only"
+            },
+            "shared_folder_join_policy": {
+                ".tag": "from_anyone"
+            },
+            "shared_folder_link_restriction_policy": {
+                ".tag": "anyone"
+            },
+            "shared_folder_member_policy": {
+                ".tag": "team"
+            },
+            "shared_link_create_policy": {
+                ".tag": "team_only"
+            }
+        },
+        "suggest_members_policy": {
+            ".tag": "enabled"
+        }
+    },
+    "team_id": "dbtid:1234abcd"
+}"##,
+            ),
+        ),
+        Endpoint::TeamMembersAddPost => (
+            Some(
+                r##"{
+    "force_async": false,
+    "new_members": [
+        {
+            "member_email": "tom.s@company.com",
+            "member_external_id": "company_id:342432",
+            "member_given_name": "Tom",
+            "member_surname": "Silverstone",
+            "send_welcome_email": true
+        }
+    ]
+}"##,
+            ),
+            Some(
+                r##"{
+    ".tag": "async_job_id",
+    "async_job_id": "dbjid:AAAmHdVYx-TLvhUzKB3CzBELXzgXA1_lKZdOyxQXrvmgJtS8Ot1h1JxobXJh3gqDIhWzsq8FzKu5KVo0M3MCrOBz"
+}"##,
+            ),
+        ),
+        Endpoint::TeamMembersAddJobStatusGetPost => (
+            Some(
+                r##"{
+    "async_job_id": "34g93hh34h04y384084"
+}"##,
+            ),
+            Some(
+                r##"{
+    ".tag": "complete",
+    "complete": [
+        {
+            ".tag": "user_already_on_team",
+            "user_already_on_team": "tom.s@company.com"
+        }
+    ]
+}"##,
+            ),
+        ),
+        Endpoint::TeamMembersGetInfoPost => (
+            Some(
+                r##"{
+    "members": [
+        {
+            ".tag": "team_member_id",
+            "team_member_id": "dbmid:efgh5678"
+        },
+        {
+            ".tag": "email",
+            "email": "nobody@example.com"
+        }
+    ]
+}"##,
+            ),
+            Some(
+                r##"{
+    "members_info": [
+        {
+            ".tag": "member_info",
+            "profile": {
+                "account_id": "dbid:AAH4f99T0taONIb-OurWxbNQ6ywGRopQngc",
+                "email": "tami@seagull.com",
+                "email_verified": false,
+                "external_id": "244423",
+                "joined_on": "2015-05-12T15:50:38Z",
+                "member_folder_id": "20",
+                "membership_type": {
+                    ".tag": "full"
+                },
+                "name": {
+                    "abbreviated_name": "FF",
+                    "display_name": "Franz Ferdinand (Personal)",
+                    "familiar_name": "Franz",
+                    "given_name": "Franz",
+                    "surname": "Ferdinand"
+                },
+                "secondary_emails": [
+                    {
+                        "email": "grape@strawberry.com",
+                        "is_verified": false
+                    }
+                ],
+                "status": {
+                    ".tag": "active"
+                },
+                "team_member_id": "dbmid:FDFSVF-DFSDF"
+            }
+        },
+        {
+            ".tag": "id_not_found",
+            "id_not_found": "nobody@example.com"
+        }
+    ]
+}"##,
+            ),
+        ),
+        Endpoint::TeamMembersListPost => (
+            Some(
+                r##"{
+    "include_removed": false,
+    "limit": 100
+}"##,
+            ),
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu",
+    "has_more": true,
+    "members": [
+        {
+            "profile": {
+                "account_id": "dbid:AAH4f99T0taONIb-OurWxbNQ6ywGRopQngc",
+                "email": "tami@seagull.com",
+                "email_verified": false,
+                "external_id": "244423",
+                "joined_on": "2015-05-12T15:50:38Z",
+                "member_folder_id": "20",
+                "membership_type": {
+                    ".tag": "full"
+                },
+                "name": {
+                    "abbreviated_name": "FF",
+                    "display_name": "Franz Ferdinand (Personal)",
+                    "familiar_name": "Franz",
+                    "given_name": "Franz",
+                    "surname": "Ferdinand"
+                },
+                "secondary_emails": [
+                    {
+                        "email": "grape@strawberry.com",
+                        "is_verified": false
+                    }
+                ],
+                "status": {
+                    ".tag": "active"
+                },
+                "team_member_id": "dbmid:FDFSVF-DFSDF"
+            },
+            "roles": [
+                {
+                    "description": "Add, remove, and manage member accounts.",
+                    "name": "User management admin",
+                    "role_id": "pid_dbtmr:3456"
+                }
+            ]
+        }
+    ]
+}"##,
+            ),
+        ),
+        Endpoint::TeamMembersListContinuePost => (
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu"
+}"##,
+            ),
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu",
+    "has_more": false,
+    "members": [
+        {
+            "profile": {
+                "account_id": "dbid:AAH4f99T0taONIb-OurWxbNQ6ywGRopQngc",
+                "email": "tami@seagull.com",
+                "email_verified": false,
+                "external_id": "244423",
+                "joined_on": "2015-05-12T15:50:38Z",
+                "member_folder_id": "20",
+                "membership_type": {
+                    ".tag": "full"
+                },
+                "name": {
+                    "abbreviated_name": "FF",
+                    "display_name": "Franz Ferdinand (Personal)",
+                    "familiar_name": "Franz",
+                    "given_name": "Franz",
+                    "surname": "Ferdinand"
+                },
+                "secondary_emails": [
+                    {
+                        "email": "grape@strawberry.com",
+                        "is_verified": false
+                    }
+                ],
+                "status": {
+                    ".tag": "active"
+                },
+                "team_member_id": "dbmid:FDFSVF-DFSDF"
+            },
+            "roles": [
+                {
+                    "description": "Add, remove, and manage member accounts.",
+                    "name": "User management admin",
+                    "role_id": "pid_dbtmr:3456"
+                }
+            ]
+        }
+    ]
+}"##,
+            ),
+        ),
+        Endpoint::TeamMembersRemovePost => (
+            Some(
+                r##"{
+    "keep_account": false,
+    "retain_team_shares": false,
+    "transfer_admin_id": {
+        ".tag": "team_member_id",
+        "team_member_id": "dbmid:efgh5678"
+    },
+    "transfer_dest_id": {
+        ".tag": "team_member_id",
+        "team_member_id": "dbmid:efgh5678"
+    },
+    "user": {
+        ".tag": "team_member_id",
+        "team_member_id": "dbmid:efgh5678"
+    },
+    "wipe_data": true
+}"##,
+            ),
+            Some(
+                r##"{
+    ".tag": "complete"
+}"##,
+            ),
+        ),
+        Endpoint::TeamMembersRemoveJobStatusGetPost => (
+            Some(
+                r##"{
+    "async_job_id": "34g93hh34h04y384084"
+}"##,
+            ),
+            Some(
+                r##"{
+    ".tag": "in_progress"
+}"##,
+            ),
+        ),
+        Endpoint::TeamMembersSetProfilePost => (
+            Some(
+                r##"{
+    "new_email": "t.smith@domain.com",
+    "new_surname": "Smith",
+    "user": {
+        ".tag": "external_id",
+        "external_id": "dbmid:efgh5678"
+    }
+}"##,
+            ),
+            Some(
+                r##"{
+    "member_info": {
+        "profile": {
+            "account_id": "dbid:AAH4f99T0taONIb-OurWxbNQ6ywGRopQngc",
+            "email": "tami@seagull.com",
+            "email_verified": false,
+            "external_id": "244423",
+            "joined_on": "2015-05-12T15:50:38Z",
+            "member_folder_id": "20",
+            "membership_type": {
+                ".tag": "full"
+            },
+            "name": {
+                "abbreviated_name": "FF",
+                "display_name": "Franz Ferdinand (Personal)",
+                "familiar_name": "Franz",
+                "given_name": "Franz",
+                "surname": "Ferdinand"
+            },
+            "secondary_emails": [
+                {
+                    "email": "grape@strawberry.com",
+                    "is_verified": false
+                }
+            ],
+            "status": {
+                ".tag": "active"
+            },
+            "team_member_id": "dbmid:FDFSVF-DFSDF"
+        },
+        "roles": [
+            {
+                "description": "Add, remove, and manage member accounts.",
+                "name": "User management admin",
+                "role_id": "pid_dbtmr:3456"
+            }
+        ]
+    }
+}"##,
+            ),
+        ),
+        Endpoint::TeamMembersSuspendPost => (
+            Some(
+                r##"{
+    "user": {
+        ".tag": "team_member_id",
+        "team_member_id": "dbmid:efgh5678"
+    },
+    "wipe_data": false
+}"##,
+            ),
+            None,
+        ),
+        Endpoint::TeamMembersUnsuspendPost => (
+            Some(
+                r##"{
+    "user": {
+        ".tag": "team_member_id",
+        "team_member_id": "dbmid:efgh5678"
+    }
+}"##,
+            ),
+            None,
+        ),
+        Endpoint::TeamGroupsCreatePost => (
+            Some(
+                r##"{
+    "add_creator_as_owner": false,
+    "group_external_id": "group-134",
+    "group_management_type": {
+        ".tag": "company_managed"
+    },
+    "group_name": "Europe sales"
+}"##,
+            ),
+            Some(
+                r##"{
+    "created": 1447255518000,
+    "group_id": "g:e2db7665347abcd600000000001a2b3c",
+    "group_management_type": {
+        ".tag": "user_managed"
+    },
+    "group_name": "project launch",
+    "member_count": 5,
+    "members": [
+        {
+            "access_type": {
+                ".tag": "member"
+            },
+            "profile": {
+                "account_id": "dbid:AAH4f99T0taONIb-OurWxbNQ6ywGRopQngc",
+                "email": "tami@seagull.com",
+                "email_verified": false,
+                "external_id": "244423",
+                "joined_on": "2015-05-12T15:50:38Z",
+                "membership_type": {
+                    ".tag": "full"
+                },
+                "name": {
+                    "abbreviated_name": "FF",
+                    "display_name": "Franz Ferdinand (Personal)",
+                    "familiar_name": "Franz",
+                    "given_name": "Franz",
+                    "surname": "Ferdinand"
+                },
+                "secondary_emails": [
+                    {
+                        "email": "grape@strawberry.com",
+                        "is_verified": false
+                    }
+                ],
+                "status": {
+                    ".tag": "active"
+                },
+                "team_member_id": "dbmid:FDFSVF-DFSDF"
+            }
+        }
+    ]
+}"##,
+            ),
+        ),
+        Endpoint::TeamGroupsDeletePost => (
+            Some(
+                r##"{
+    ".tag": "group_id",
+    "group_id": "g:e2db7665347abcd600000000001a2b3c"
+}"##,
+            ),
+            Some(
+                r##"{
+    ".tag": "complete"
+}"##,
+            ),
+        ),
+        Endpoint::TeamGroupsGetInfoPost => (
+            Some(
+                r##"{
+    ".tag": "group_ids",
+    "group_ids": [
+        "g:e2db7665347abcd600000000001a2b3c",
+        "g:111111147abcd6000000000222222c"
+    ]
+}"##,
+            ),
+            Some(
+                r##"[
+    {
+        ".tag": "group_info",
+        "created": 1447255518000,
+        "group_id": "g:e2db7665347abcd600000000001a2b3c",
+        "group_management_type": {
+            ".tag": "user_managed"
+        },
+        "group_name": "project launch",
+        "member_count": 5,
+        "members": [
+            {
+                "access_type": {
+                    ".tag": "member"
+                },
+                "profile": {
+                    "account_id": "dbid:AAH4f99T0taONIb-OurWxbNQ6ywGRopQngc",
+                    "email": "tami@seagull.com",
+                    "email_verified": false,
+                    "external_id": "244423",
+                    "joined_on": "2015-05-12T15:50:38Z",
+                    "membership_type": {
+                        ".tag": "full"
+                    },
+                    "name": {
+                        "abbreviated_name": "FF",
+                        "display_name": "Franz Ferdinand (Personal)",
+                        "familiar_name": "Franz",
+                        "given_name": "Franz",
+                        "surname": "Ferdinand"
+                    },
+                    "secondary_emails": [
+                        {
+                            "email": "grape@strawberry.com",
+                            "is_verified": false
+                        }
+                    ],
+                    "status": {
+                        ".tag": "active"
+                    },
+                    "team_member_id": "dbmid:FDFSVF-DFSDF"
+                }
+            }
+        ]
+    },
+    {
+        ".tag": "id_not_found",
+        "id_not_found": "g:111111147abcd6000000000222222c"
+    }
+]"##,
+            ),
+        ),
+        Endpoint::TeamGroupsJobStatusGetPost => (
+            Some(
+                r##"{
+    "async_job_id": "34g93hh34h04y384084"
+}"##,
+            ),
+            Some(
+                r##"{
+    ".tag": "complete"
+}"##,
+            ),
+        ),
+        Endpoint::TeamGroupsListPost => (
+            Some(
+                r##"{
+    "limit": 100
+}"##,
+            ),
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu",
+    "groups": [
+        {
+            "group_external_id": "group-134",
+            "group_id": "g:e2db7665347abcd600000000001a2b3c",
+            "group_management_type": {
+                ".tag": "user_managed"
+            },
+            "group_name": "Test group",
+            "member_count": 10
+        }
+    ],
+    "has_more": false
+}"##,
+            ),
+        ),
+        Endpoint::TeamGroupsListContinuePost => (
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu"
+}"##,
+            ),
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu",
+    "groups": [
+        {
+            "group_external_id": "group-134",
+            "group_id": "g:e2db7665347abcd600000000001a2b3c",
+            "group_management_type": {
+                ".tag": "user_managed"
+            },
+            "group_name": "Test group",
+            "member_count": 10
+        }
+    ],
+    "has_more": false
+}"##,
+            ),
+        ),
+        Endpoint::TeamGroupsMembersAddPost => (
+            Some(
+                r##"{
+    "group": {
+        ".tag": "group_id",
+        "group_id": "g:e2db7665347abcd600000000001a2b3c"
+    },
+    "members": [
+        {
+            "access_type": {
+                ".tag": "member"
+            },
+            "user": {
+                ".tag": "team_member_id",
+                "team_member_id": "dbmid:efgh5678"
+            }
+        }
+    ],
+    "return_members": true
+}"##,
+            ),
+            Some(
+                r##"{
+    "async_job_id": "99988877733388",
+    "group_info": {
+        "created": 1447255518000,
+        "group_id": "g:e2db7665347abcd600000000001a2b3c",
+        "group_management_type": {
+            ".tag": "user_managed"
+        },
+        "group_name": "project launch",
+        "member_count": 5,
+        "members": [
+            {
+                "access_type": {
+                    ".tag": "member"
+                },
+                "profile": {
+                    "account_id": "dbid:AAH4f99T0taONIb-OurWxbNQ6ywGRopQngc",
+                    "email": "tami@seagull.com",
+                    "email_verified": false,
+                    "external_id": "244423",
+                    "joined_on": "2015-05-12T15:50:38Z",
+                    "membership_type": {
+                        ".tag": "full"
+                    },
+                    "name": {
+                        "abbreviated_name": "FF",
+                        "display_name": "Franz Ferdinand (Personal)",
+                        "familiar_name": "Franz",
+                        "given_name": "Franz",
+                        "surname": "Ferdinand"
+                    },
+                    "secondary_emails": [
+                        {
+                            "email": "grape@strawberry.com",
+                            "is_verified": false
+                        }
+                    ],
+                    "status": {
+                        ".tag": "active"
+                    },
+                    "team_member_id": "dbmid:FDFSVF-DFSDF"
+                }
+            }
+        ]
+    }
+}"##,
+            ),
+        ),
+        Endpoint::TeamGroupsMembersListPost => (
+            Some(
+                r##"{
+    "group": {
+        ".tag": "group_id",
+        "group_id": "g:e2db7665347abcd600000000001a2b3c"
+    },
+    "limit": 100
+}"##,
+            ),
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu",
+    "has_more": false,
+    "members": [
+        {
+            "access_type": {
+                ".tag": "member"
+            },
+            "profile": {
+                "account_id": "dbid:AAH4f99T0taONIb-OurWxbNQ6ywGRopQngc",
+                "email": "tami@seagull.com",
+                "email_verified": false,
+                "external_id": "244423",
+                "joined_on": "2015-05-12T15:50:38Z",
+                "membership_type": {
+                    ".tag": "full"
+                },
+                "name": {
+                    "abbreviated_name": "FF",
+                    "display_name": "Franz Ferdinand (Personal)",
+                    "familiar_name": "Franz",
+                    "given_name": "Franz",
+                    "surname": "Ferdinand"
+                },
+                "secondary_emails": [
+                    {
+                        "email": "grape@strawberry.com",
+                        "is_verified": false
+                    }
+                ],
+                "status": {
+                    ".tag": "active"
+                },
+                "team_member_id": "dbmid:FDFSVF-DFSDF"
+            }
+        }
+    ]
+}"##,
+            ),
+        ),
+        Endpoint::TeamGroupsMembersListContinuePost => (
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu"
+}"##,
+            ),
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu",
+    "has_more": false,
+    "members": [
+        {
+            "access_type": {
+                ".tag": "member"
+            },
+            "profile": {
+                "account_id": "dbid:AAH4f99T0taONIb-OurWxbNQ6ywGRopQngc",
+                "email": "tami@seagull.com",
+                "email_verified": false,
+                "external_id": "244423",
+                "joined_on": "2015-05-12T15:50:38Z",
+                "membership_type": {
+                    ".tag": "full"
+                },
+                "name": {
+                    "abbreviated_name": "FF",
+                    "display_name": "Franz Ferdinand (Personal)",
+                    "familiar_name": "Franz",
+                    "given_name": "Franz",
+                    "surname": "Ferdinand"
+                },
+                "secondary_emails": [
+                    {
+                        "email": "grape@strawberry.com",
+                        "is_verified": false
+                    }
+                ],
+                "status": {
+                    ".tag": "active"
+                },
+                "team_member_id": "dbmid:FDFSVF-DFSDF"
+            }
+        }
+    ]
+}"##,
+            ),
+        ),
+        Endpoint::TeamGroupsMembersRemovePost => (
+            Some(
+                r##"{
+    "group": {
+        ".tag": "group_id",
+        "group_id": "g:e2db7665347abcd600000000001a2b3c"
+    },
+    "return_members": true,
+    "users": [
+        {
+            ".tag": "team_member_id",
+            "team_member_id": "dbmid:efgh5678"
+        }
+    ]
+}"##,
+            ),
+            Some(
+                r##"{
+    "async_job_id": "99988877733388",
+    "group_info": {
+        "created": 1447255518000,
+        "group_id": "g:e2db7665347abcd600000000001a2b3c",
+        "group_management_type": {
+            ".tag": "user_managed"
+        },
+        "group_name": "project launch",
+        "member_count": 5,
+        "members": [
+            {
+                "access_type": {
+                    ".tag": "member"
+                },
+                "profile": {
+                    "account_id": "dbid:AAH4f99T0taONIb-OurWxbNQ6ywGRopQngc",
+                    "email": "tami@seagull.com",
+                    "email_verified": false,
+                    "external_id": "244423",
+                    "joined_on": "2015-05-12T15:50:38Z",
+                    "membership_type": {
+                        ".tag": "full"
+                    },
+                    "name": {
+                        "abbreviated_name": "FF",
+                        "display_name": "Franz Ferdinand (Personal)",
+                        "familiar_name": "Franz",
+                        "given_name": "Franz",
+                        "surname": "Ferdinand"
+                    },
+                    "secondary_emails": [
+                        {
+                            "email": "grape@strawberry.com",
+                            "is_verified": false
+                        }
+                    ],
+                    "status": {
+                        ".tag": "active"
+                    },
+                    "team_member_id": "dbmid:FDFSVF-DFSDF"
+                }
+            }
+        ]
+    }
+}"##,
+            ),
+        ),
+        Endpoint::TeamGroupsMembersSetAccessTypePost => (
+            Some(
+                r##"{
+    "access_type": {
+        ".tag": "owner"
+    },
+    "group": {
+        ".tag": "group_id",
+        "group_id": "g:e2db7665347abcd600000000001a2b3c"
+    },
+    "return_members": true,
+    "user": {
+        ".tag": "team_member_id",
+        "team_member_id": "dbmid:efgh5678"
+    }
+}"##,
+            ),
+            Some(
+                r##"[
+    {
+        ".tag": "group_info",
+        "created": 1447255518000,
+        "group_id": "g:e2db7665347abcd600000000001a2b3c",
+        "group_management_type": {
+            ".tag": "user_managed"
+        },
+        "group_name": "project launch",
+        "member_count": 5,
+        "members": [
+            {
+                "access_type": {
+                    ".tag": "member"
+                },
+                "profile": {
+                    "account_id": "dbid:AAH4f99T0taONIb-OurWxbNQ6ywGRopQngc",
+                    "email": "tami@seagull.com",
+                    "email_verified": false,
+                    "external_id": "244423",
+                    "joined_on": "2015-05-12T15:50:38Z",
+                    "membership_type": {
+                        ".tag": "full"
+                    },
+                    "name": {
+                        "abbreviated_name": "FF",
+                        "display_name": "Franz Ferdinand (Personal)",
+                        "familiar_name": "Franz",
+                        "given_name": "Franz",
+                        "surname": "Ferdinand"
+                    },
+                    "secondary_emails": [
+                        {
+                            "email": "grape@strawberry.com",
+                            "is_verified": false
+                        }
+                    ],
+                    "status": {
+                        ".tag": "active"
+                    },
+                    "team_member_id": "dbmid:FDFSVF-DFSDF"
+                }
+            }
+        ]
+    }
+]"##,
+            ),
+        ),
+        Endpoint::TeamLogGetEventsPost => (
+            Some(
+                r##"{
+    "account_id": "dbid:AAHgR8xsQP48a5DQUGPo-Vxsrjd0OByVmho",
+    "category": {
+        ".tag": "sharing"
+    },
+    "limit": 50,
+    "time": {
+        "end_time": "2017-01-26T00:00:00Z",
+        "start_time": "2017-01-25T00:00:00Z"
+    }
+}"##,
+            ),
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu",
+    "events": [
+        {
+            "actor": {
+                ".tag": "user",
+                "user": {
+                    ".tag": "team_member",
+                    "account_id": "dbid:AAHgR8xsQP48a5DQUGPo-Vxsrjd0OByVmho",
+                    "display_name": "John Smith",
+                    "email": "john_smith@acmecorp.com",
+                    "team_member_id": "dbmid:AAFoi-tmvRuQR0jU-3fN4B-9nZo6nHcDO9Q"
+                }
+            },
+            "assets": [
+                {
+                    ".tag": "file",
+                    "display_name": "reports.xls",
+                    "file_id": "id:jQ3r3DfJ2ZAAAAAAAAAAFg",
+                    "path": {
+                        "contextual": "/Contract Work/Draft/reports.xls",
+                        "namespace_relative": {
+                            "is_shared_namespace": false,
+                            "ns_id": "1234",
+                            "relative_path": "/Contract Work/Draft/reports.xls"
+                        }
+                    }
+                }
+            ],
+            "context": {
+                ".tag": "team_member",
+                "account_id": "dbid:AAHgR8xsQP48a5DQUGPo-Vxsrjd0OByVmho",
+                "display_name": "John Smith",
+                "email": "john_smith@acmecorp.com",
+                "team_member_id": "dbmid:AAFoi-tmvRuQR0jU-3fN4B-9nZo6nHcDO9Q"
+            },
+            "details": {
+                ".tag": "shared_content_download_details",
+                "shared_content_access_level": {
+                    ".tag": "viewer"
+                },
+                "shared_content_link": "abc"
+            },
+            "event_category": {
+                ".tag": "sharing"
+            },
+            "event_type": {
+                ".tag": "shared_content_download",
+                "description": "(sharing) Downloaded shared file/folder"
+            },
+            "involve_non_team_member": true,
+            "origin": {
+                "access_method": {
+                    ".tag": "end_user",
+                    "end_user": {
+                        ".tag": "web",
+                        "session_id": "dbwsid:123456789012345678901234567890123456789"
+                    }
+                },
+                "geo_location": {
+                    "city": "San Francisco",
+                    "country": "US",
+                    "ip_address": "45.56.78.100",
+                    "region": "California"
+                }
+            },
+            "participants": [
+                {
+                    ".tag": "user",
+                    "user": {
+                        ".tag": "team_member",
+                        "account_id": "dbid:AAGx4oiLtHdvRdNxUpvvJBXYgR4BS19c9kw",
+                        "display_name": "Jane Doe",
+                        "email": "jane_doe@acmecorp.com",
+                        "team_member_id": "dbmid:AAEFoi-tmvRuQR0jU-3fN4B-9nZo6nHcDO9Q"
+                    }
+                }
+            ],
+            "timestamp": "2017-01-25T15:51:30Z"
+        }
+    ],
+    "has_more": false
+}"##,
+            ),
+        ),
+        Endpoint::TeamLogGetEventsContinuePost => (
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu"
+}"##,
+            ),
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu",
+    "events": [
+        {
+            "actor": {
+                ".tag": "user",
+                "user": {
+                    ".tag": "team_member",
+                    "account_id": "dbid:AAHgR8xsQP48a5DQUGPo-Vxsrjd0OByVmho",
+                    "display_name": "John Smith",
+                    "email": "john_smith@acmecorp.com",
+                    "team_member_id": "dbmid:AAFoi-tmvRuQR0jU-3fN4B-9nZo6nHcDO9Q"
+                }
+            },
+            "assets": [
+                {
+                    ".tag": "file",
+                    "display_name": "reports.xls",
+                    "file_id": "id:jQ3r3DfJ2ZAAAAAAAAAAFg",
+                    "path": {
+                        "contextual": "/Contract Work/Draft/reports.xls",
+                        "namespace_relative": {
+                            "is_shared_namespace": false,
+                            "ns_id": "1234",
+                            "relative_path": "/Contract Work/Draft/reports.xls"
+                        }
+                    }
+                }
+            ],
+            "context": {
+                ".tag": "team_member",
+                "account_id": "dbid:AAHgR8xsQP48a5DQUGPo-Vxsrjd0OByVmho",
+                "display_name": "John Smith",
+                "email": "john_smith@acmecorp.com",
+                "team_member_id": "dbmid:AAFoi-tmvRuQR0jU-3fN4B-9nZo6nHcDO9Q"
+            },
+            "details": {
+                ".tag": "shared_content_download_details",
+                "shared_content_access_level": {
+                    ".tag": "viewer"
+                },
+                "shared_content_link": "abc"
+            },
+            "event_category": {
+                ".tag": "sharing"
+            },
+            "event_type": {
+                ".tag": "shared_content_download",
+                "description": "(sharing) Downloaded shared file/folder"
+            },
+            "involve_non_team_member": true,
+            "origin": {
+                "access_method": {
+                    ".tag": "end_user",
+                    "end_user": {
+                        ".tag": "web",
+                        "session_id": "dbwsid:123456789012345678901234567890123456789"
+                    }
+                },
+                "geo_location": {
+                    "city": "San Francisco",
+                    "country": "US",
+                    "ip_address": "45.56.78.100",
+                    "region": "California"
+                }
+            },
+            "participants": [
+                {
+                    ".tag": "user",
+                    "user": {
+                        ".tag": "team_member",
+                        "account_id": "dbid:AAGx4oiLtHdvRdNxUpvvJBXYgR4BS19c9kw",
+                        "display_name": "Jane Doe",
+                        "email": "jane_doe@acmecorp.com",
+                        "team_member_id": "dbmid:AAEFoi-tmvRuQR0jU-3fN4B-9nZo6nHcDO9Q"
+                    }
+                }
+            ],
+            "timestamp": "2017-01-25T15:51:30Z"
+        }
+    ],
+    "has_more": false
+}"##,
+            ),
+        ),
+        Endpoint::TeamNamespacesListPost => (
+            Some(
+                r##"{
+    "limit": 1
+}"##,
+            ),
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu",
+    "has_more": false,
+    "namespaces": [
+        {
+            "name": "Marketing",
+            "namespace_id": "123456789",
+            "namespace_type": {
+                ".tag": "team_folder"
+            }
+        },
+        {
+            "name": "Franz Ferdinand",
+            "namespace_id": "123456788",
+            "namespace_type": {
+                ".tag": "team_member_folder"
+            },
+            "team_member_id": "dbmid:1234567"
+        }
+    ]
+}"##,
+            ),
+        ),
+        Endpoint::TeamNamespacesListContinuePost => (
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu"
+}"##,
+            ),
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu",
+    "has_more": false,
+    "namespaces": [
+        {
+            "name": "Marketing",
+            "namespace_id": "123456789",
+            "namespace_type": {
+                ".tag": "team_folder"
+            }
+        },
+        {
+            "name": "Franz Ferdinand",
+            "namespace_id": "123456788",
+            "namespace_type": {
+                ".tag": "team_member_folder"
+            },
+            "team_member_id": "dbmid:1234567"
+        }
+    ]
+}"##,
+            ),
+        ),
+        Endpoint::TeamTeamFolderArchivePost => (
+            Some(
+                r##"{
+    "force_async_off": false,
+    "team_folder_id": "123456789"
+}"##,
+            ),
+            Some(
+                r##"{
+    ".tag": "async_job_id",
+    "async_job_id": "34g93hh34h04y384084"
+}"##,
+            ),
+        ),
+        Endpoint::TeamTeamFolderArchiveCheckPost => (
+            Some(
+                r##"{
+    "async_job_id": "34g93hh34h04y384084"
+}"##,
+            ),
+            Some(
+                r##"{
+    ".tag": "complete",
+    "content_sync_settings": [
+        {
+            "id": "id:a4ayc_80_OEAAAAAAAAAXw",
+            "sync_setting": {
+                ".tag": "not_synced"
+            }
+        }
+    ],
+    "is_team_shared_dropbox": false,
+    "name": "Marketing",
+    "status": {
+        ".tag": "archived"
+    },
+    "sync_setting": {
+        ".tag": "default"
+    },
+    "team_folder_id": "123456789"
+}"##,
+            ),
+        ),
+        Endpoint::TeamTeamFolderCreatePost => (
+            Some(
+                r##"{
+    "name": "Marketing",
+    "sync_setting": {
+        ".tag": "not_synced"
+    }
+}"##,
+            ),
+            Some(
+                r##"{
+    "content_sync_settings": [
+        {
+            "id": "id:a4ayc_80_OEAAAAAAAAAXw",
+            "sync_setting": {
+                ".tag": "not_synced"
+            }
+        }
+    ],
+    "is_team_shared_dropbox": false,
+    "name": "Marketing",
+    "status": {
+        ".tag": "active"
+    },
+    "sync_setting": {
+        ".tag": "default"
+    },
+    "team_folder_id": "123456789"
+}"##,
+            ),
+        ),
+        Endpoint::TeamTeamFolderListPost => (
+            Some(
+                r##"{
+    "limit": 100
+}"##,
+            ),
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu",
+    "has_more": false,
+    "team_folders": [
+        {
+            "content_sync_settings": [
+                {
+                    "id": "id:a4ayc_80_OEAAAAAAAAAXw",
+                    "sync_setting": {
+                        ".tag": "not_synced"
+                    }
+                }
+            ],
+            "is_team_shared_dropbox": false,
+            "name": "Marketing",
+            "status": {
+                ".tag": "active"
+            },
+            "sync_setting": {
+                ".tag": "default"
+            },
+            "team_folder_id": "123456789"
+        }
+    ]
+}"##,
+            ),
+        ),
+        Endpoint::TeamTeamFolderListContinuePost => (
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu"
+}"##,
+            ),
+            Some(
+                r##"{
+    "cursor": "ZtkX9_EHj3x7PMkVuFIhwKYXEpwpLwyxp9vMKomUhllil9q7eWiAu",
+    "has_more": false,
+    "team_folders": [
+        {
+            "content_sync_settings": [
+                {
+                    "id": "id:a4ayc_80_OEAAAAAAAAAXw",
+                    "sync_setting": {
+                        ".tag": "not_synced"
+                    }
+                }
+            ],
+            "is_team_shared_dropbox": false,
+            "name": "Marketing",
+            "status": {
+                ".tag": "active"
+            },
+            "sync_setting": {
+                ".tag": "default"
+            },
+            "team_folder_id": "123456789"
+        }
+    ]
+}"##,
+            ),
+        ),
+        Endpoint::TeamTeamFolderPermanentlyDeletePost => (
+            Some(
+                r##"{
+    "team_folder_id": "123456789"
+}"##,
+            ),
+            None,
+        ),
+        Endpoint::TeamTeamFolderRenamePost => (
+            Some(
+                r##"{
+    "name": "Sales",
+    "team_folder_id": "123456789"
+}"##,
+            ),
+            Some(
+                r##"{
+    "content_sync_settings": [
+        {
+            "id": "id:a4ayc_80_OEAAAAAAAAAXw",
+            "sync_setting": {
+                ".tag": "not_synced"
+            }
+        }
+    ],
+    "is_team_shared_dropbox": false,
+    "name": "Sales",
+    "status": {
+        ".tag": "active"
+    },
+    "sync_setting": {
+        ".tag": "default"
+    },
+    "team_folder_id": "123456789"
+}"##,
+            ),
+        ),
+        Endpoint::TeamTeamFolderUpdateSyncSettingsPost => (
+            Some(
+                r##"{
+    "content_sync_settings": [
+        {
+            "id": "id:a4ayc_80_OEAAAAAAAAAXw",
+            "sync_setting": {
+                ".tag": "not_synced"
+            }
+        }
+    ],
+    "sync_setting": {
+        ".tag": "default"
+    },
+    "team_folder_id": "123456789"
+}"##,
+            ),
+            Some(
+                r##"{
+    "content_sync_settings": [
+        {
+            "id": "id:a4ayc_80_OEAAAAAAAAAXw",
+            "sync_setting": {
+                ".tag": "not_synced"
+            }
+        }
+    ],
+    "is_team_shared_dropbox": false,
+    "name": "Marketing",
+    "status": {
+        ".tag": "active"
+    },
+    "sync_setting": {
+        ".tag": "default"
+    },
+    "team_folder_id": "123456789"
 }"##,
             ),
         ),
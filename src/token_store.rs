@@ -0,0 +1,218 @@
+//! Persisting the access token across process restarts.
+//!
+//! A [`Client`](crate::Client) given a [`TokenStore`] through
+//! [`Client::with_token_store`](crate::Client::with_token_store) starts from
+//! the stored token when there is one and saves every refreshed token back,
+//! so a restarted daemon reuses its session instead of paying for a refresh.
+//! Expiry is kept as wall-clock time for the same reason.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use rusty_dropbox_sdk::token_store::JsonFileTokenStore;
+//! use rusty_dropbox_sdk::{Client, RefreshConfig};
+//!
+//! # fn run() -> anyhow::Result<()> {
+//! let store = Arc::new(JsonFileTokenStore::new("/var/lib/mydaemon/dropbox-token.json"));
+//! let client = Client::with_refresh("", 0, RefreshConfig::pkce("app-key", "refresh-token"))
+//!     .with_token_store(store)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// What a [`TokenStore`] keeps.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct StoredToken {
+    pub access_token: String,
+    /// When the access token stops working; `None` if unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// The refresh token the client was built with, saved alongside so an
+    /// app that got it interactively (PKCE, loopback) can rebuild its
+    /// [`RefreshConfig`](crate::RefreshConfig) after a restart. The client
+    /// itself keeps using the one it was given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+}
+
+impl std::fmt::Debug for StoredToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StoredToken")
+            .field("access_token", &"<redacted>")
+            .field("expires_at", &self.expires_at)
+            .field(
+                "refresh_token",
+                &self.refresh_token.as_ref().map(|_| "<redacted>"),
+            )
+            .finish()
+    }
+}
+
+/// Where a client keeps its token between runs. Calls are blocking and
+/// happen right after a refresh, so keep them quick.
+pub trait TokenStore: Send + Sync + std::fmt::Debug {
+    /// The saved token, or `None` if nothing has been saved yet.
+    fn load(&self) -> Result<Option<StoredToken>>;
+
+    fn save(&self, token: &StoredToken) -> Result<()>;
+
+    /// Forget the saved token, e.g. after it was revoked.
+    fn delete(&self) -> Result<()>;
+}
+
+/// Keeps the token in memory only. Handy in tests, or to share one session
+/// between clients that were built separately.
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    token: Mutex<Option<StoredToken>>,
+}
+
+impl MemoryTokenStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn load(&self) -> Result<Option<StoredToken>> {
+        Ok(self.token.lock().unwrap().clone())
+    }
+
+    fn save(&self, token: &StoredToken) -> Result<()> {
+        *self.token.lock().unwrap() = Some(token.clone());
+        Ok(())
+    }
+
+    fn delete(&self) -> Result<()> {
+        *self.token.lock().unwrap() = None;
+        Ok(())
+    }
+}
+
+/// Keeps the token in a JSON file. On Unix the file is created with mode
+/// `0600`. Saves go through a temporary file and a rename, so a crash
+/// mid-write never leaves a truncated token behind.
+#[derive(Debug, Clone)]
+pub struct JsonFileTokenStore {
+    path: PathBuf,
+}
+
+impl JsonFileTokenStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl TokenStore for JsonFileTokenStore {
+    fn load(&self) -> Result<Option<StoredToken>> {
+        let bytes = match std::fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err).with_context(|| format!("read {}", self.path.display())),
+        };
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .with_context(|| format!("parse {}", self.path.display()))
+    }
+
+    fn save(&self, token: &StoredToken) -> Result<()> {
+        let json = serde_json::to_vec_pretty(token).context("serialise StoredToken")?;
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+
+        // A temp file left over from an interrupted save may have been
+        // created with wider permissions; start from a fresh one so the
+        // 0o600 mode below applies.
+        match std::fs::remove_file(&tmp) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err).with_context(|| format!("remove {}", tmp.display())),
+        }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options
+            .open(&tmp)
+            .with_context(|| format!("create {}", tmp.display()))?;
+        file.write_all(&json)
+            .and_then(|_| file.sync_all())
+            .with_context(|| format!("write {}", tmp.display()))?;
+        std::fs::rename(&tmp, &self.path)
+            .with_context(|| format!("rename {} to {}", tmp.display(), self.path.display()))
+    }
+
+    fn delete(&self) -> Result<()> {
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err).with_context(|| format!("remove {}", self.path.display())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> StoredToken {
+        StoredToken {
+            access_token: "sl.secret".into(),
+            expires_at: Some("2030-01-01T00:00:00Z".parse().unwrap()),
+            refresh_token: Some("rt".into()),
+        }
+    }
+
+    #[test]
+    fn json_file_round_trip() {
+        let dir = std::env::temp_dir().join(format!("dropbox-token-store-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let store = JsonFileTokenStore::new(dir.join("token.json"));
+
+        assert_eq!(store.load().unwrap(), None);
+        // A stale, world-readable temp file from an interrupted save.
+        let tmp = dir.join("token.json.tmp");
+        std::fs::write(&tmp, b"stale").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&tmp, std::fs::Permissions::from_mode(0o644)).unwrap();
+        }
+        store.save(&sample()).unwrap();
+        assert_eq!(store.load().unwrap(), Some(sample()));
+        assert!(!tmp.exists());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(store.path())
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        store.delete().unwrap();
+        store.delete().unwrap();
+        assert_eq!(store.load().unwrap(), None);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn debug_redacts_tokens() {
+        let out = format!("{:?}", sample());
+        assert!(!out.contains("sl.secret"));
+        assert!(!out.contains("\"rt\""));
+    }
+}
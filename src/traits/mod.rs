@@ -12,10 +12,25 @@ use serde::{Deserialize, Serialize};
 /// let result = request.call().await?;
 /// let result = request.call_sync()?;
 /// ```
+///
+/// `call_with` / `call_sync_with` execute the request through a
+/// [`Client`](crate::Client) instead: the request's own `access_token` is
+/// ignored and the client's token is used, refreshed first if expired and
+/// once more on a 401. [`Client::execute`](crate::Client::execute) is the
+/// usual way in.
 #[allow(unused)]
 pub trait Service<O: Sized> {
+    /// Blocking call with the request's own `access_token`. That token has
+    /// no refresh credentials, so an expired token fails with
+    /// `ApiError::Unauthorized`; use
+    /// [`Client::execute_sync`](crate::Client::execute_sync) (or
+    /// `call_sync_with`) on a refreshing client for refresh-and-replay.
     fn call_sync(&self) -> Result<Option<O>>;
+    /// Async call with the request's own `access_token`; like `call_sync`,
+    /// it never refreshes. Use [`Client::execute`](crate::Client::execute).
     fn call(&self) -> BoxFuture<'static, Result<Option<O>>>;
+    fn call_sync_with(&self, client: &crate::Client) -> Result<Option<O>>;
+    fn call_with(&self, client: &crate::Client) -> BoxFuture<'static, Result<Option<O>>>;
 }
 
 pub trait Utils<'a> {
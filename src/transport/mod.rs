@@ -0,0 +1,276 @@
+//! Pluggable HTTP transport.
+//!
+//! Every request the SDK makes — endpoint calls from the service macros,
+//! OAuth calls in [`auth`](crate::auth) and the streaming
+//! [`helpers`](crate::helpers) — is expressed as an [`HttpRequest`] and
+//! handed to the [`Transport`] of the [`Client`](crate::Client) it runs
+//! through. [`ReqwestTransport`] is the default; swap in your own with
+//! [`Client::with_transport`](crate::Client::with_transport) to route
+//! traffic through an instrumented HTTP stack or an in-process fake.
+//!
+//! A test fake only needs [`Transport::send`]:
+//!
+//! ```
+//! use futures::future::BoxFuture;
+//! use rusty_dropbox_sdk::transport::{HttpRequest, HttpResponse, Transport};
+//!
+//! #[derive(Debug)]
+//! struct AlwaysEmpty;
+//!
+//! impl Transport for AlwaysEmpty {
+//!     fn send(&self, _request: HttpRequest) -> BoxFuture<'_, anyhow::Result<HttpResponse>> {
+//!         Box::pin(async { Ok(HttpResponse::new(200, Vec::new(), "{}")) })
+//!     }
+//! }
+//! ```
+
+mod reqwest_transport;
+
+pub use reqwest_transport::{HttpConfig, ReqwestTransport};
+
+use anyhow::Result;
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use futures::stream::{BoxStream, StreamExt, TryStreamExt};
+
+/// Chunked body used by the streaming helpers, in both directions.
+pub type BodyStream = BoxStream<'static, std::io::Result<Bytes>>;
+
+/// One outgoing request. Every Dropbox v2 call is a `POST`, so the method is
+/// implied. `headers` already include `Authorization` when the call is
+/// authenticated.
+///
+/// The `Debug` output masks the `Authorization` and `Dropbox-API-Arg` header
+/// values and prints only the body length.
+#[derive(Clone)]
+pub struct HttpRequest {
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Bytes>,
+}
+
+impl std::fmt::Debug for HttpRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let headers: Vec<(&str, &str)> = self
+            .headers
+            .iter()
+            .map(|(k, v)| {
+                let sensitive = k.eq_ignore_ascii_case("Authorization")
+                    || k.eq_ignore_ascii_case("Dropbox-API-Arg");
+                (
+                    k.as_str(),
+                    if sensitive { "<redacted>" } else { v.as_str() },
+                )
+            })
+            .collect();
+        f.debug_struct("HttpRequest")
+            .field("url", &self.url)
+            .field("headers", &headers)
+            .field("body_len", &self.body.as_ref().map(Bytes::len))
+            .finish()
+    }
+}
+
+impl HttpRequest {
+    /// First value of header `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+}
+
+/// A fully-buffered response.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Bytes,
+}
+
+impl HttpResponse {
+    pub fn new(status: u16, headers: Vec<(String, String)>, body: impl Into<Bytes>) -> Self {
+        Self {
+            status,
+            headers,
+            body: body.into(),
+        }
+    }
+
+    /// First value of header `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// A response whose body is still arriving.
+pub struct StreamingResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: BodyStream,
+}
+
+impl std::fmt::Debug for StreamingResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamingResponse")
+            .field("status", &self.status)
+            .field("headers", &self.headers)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StreamingResponse {
+    /// First value of header `name`, compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Drain the body into memory.
+    pub async fn collect(self) -> Result<HttpResponse> {
+        let chunks: Vec<Bytes> = self.body.try_collect().await?;
+        Ok(HttpResponse {
+            status: self.status,
+            headers: self.headers,
+            body: Bytes::from(chunks.concat()),
+        })
+    }
+}
+
+/// Sends [`HttpRequest`]s. Implementations must be cheap to share: a
+/// [`Client`](crate::Client) holds its transport behind an `Arc` and every
+/// derived client reuses it.
+pub trait Transport: Send + Sync + std::fmt::Debug {
+    /// Send one request and buffer the whole response.
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>>;
+
+    /// Blocking variant used by `call_sync` and the other `*_sync` entry
+    /// points. The default drives [`Transport::send`] with
+    /// `futures::executor::block_on`, which suits runtime-agnostic fakes;
+    /// transports whose futures need a reactor must override it.
+    fn send_sync(&self, request: HttpRequest) -> Result<HttpResponse> {
+        futures::executor::block_on(self.send(request))
+    }
+
+    /// Send with an optional streamed request body and stream the response
+    /// body back. The default buffers both ends around [`Transport::send`].
+    fn send_streaming(
+        &self,
+        mut request: HttpRequest,
+        body: Option<BodyStream>,
+    ) -> BoxFuture<'_, Result<StreamingResponse>> {
+        Box::pin(async move {
+            if let Some(body) = body {
+                let chunks: Vec<Bytes> = body.try_collect().await?;
+                request.body = Some(Bytes::from(chunks.concat()));
+            }
+            let response = self.send(request).await?;
+            Ok(StreamingResponse {
+                status: response.status,
+                headers: response.headers,
+                body: futures::stream::once(async move { Ok(response.body) }).boxed(),
+            })
+        })
+    }
+}
+
+pub(crate) fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api;
+    use crate::Client;
+    use std::sync::{Arc, Mutex};
+
+    /// In-memory transport: records every request and answers with a
+    /// canned response.
+    #[derive(Debug)]
+    struct Recorder {
+        seen: Mutex<Vec<HttpRequest>>,
+        reply: HttpResponse,
+    }
+
+    impl Transport for Recorder {
+        fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+            self.seen.lock().unwrap().push(request);
+            let reply = self.reply.clone();
+            Box::pin(async move { Ok(reply) })
+        }
+    }
+
+    fn recorder(body: &'static str) -> Arc<Recorder> {
+        Arc::new(Recorder {
+            seen: Mutex::new(Vec::new()),
+            reply: HttpResponse::new(200, Vec::new(), body),
+        })
+    }
+
+    const RAW_SPACE_USAGE: &str = r#"{"used":1,"allocation":{".tag":"individual","allocated":2}}"#;
+
+    #[tokio::test]
+    async fn requests_run_through_custom_transport() {
+        let fake = recorder(RAW_SPACE_USAGE);
+        let client = Client::new("tok").with_transport(fake.clone());
+
+        let req = api::users::get_space_usage::GetSpaceUsageRequest::default();
+        let resp = client.execute(&req).await.expect("fake transport call");
+        assert!(resp.is_some());
+
+        let seen = fake.seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        assert_eq!(
+            seen[0].url,
+            "https://api.dropboxapi.com/2/users/get_space_usage"
+        );
+        assert_eq!(seen[0].header("authorization"), Some("Bearer tok"));
+    }
+
+    #[test]
+    fn blocking_calls_use_default_block_on() {
+        let fake = recorder(RAW_SPACE_USAGE);
+        let client = Client::new("tok").with_transport(fake.clone());
+
+        let req = api::users::get_space_usage::GetSpaceUsageRequest::default();
+        let resp = client.execute_sync(&req).expect("fake transport call");
+        assert!(resp.is_some());
+        assert_eq!(fake.seen.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn default_streaming_buffers_request_body() {
+        let fake = recorder("ok");
+        let body: BodyStream = futures::stream::iter(vec![
+            Ok(Bytes::from_static(b"he")),
+            Ok(Bytes::from_static(b"llo")),
+        ])
+        .boxed();
+        let request = HttpRequest {
+            url: "https://content.dropboxapi.com/2/files/upload".into(),
+            headers: Vec::new(),
+            body: None,
+        };
+        let response = fake
+            .send_streaming(request, Some(body))
+            .await
+            .expect("send_streaming")
+            .collect()
+            .await
+            .expect("collect");
+        assert_eq!(&response.body[..], b"ok");
+        assert_eq!(
+            fake.seen.lock().unwrap()[0].body.as_deref(),
+            Some(&b"hello"[..])
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Default [`Transport`] on top of `reqwest`, configured by [`HttpConfig`].
+//!
+//! `Client::new` / `Client::with_refresh` share one default transport (plain
+//! User-Agent, reqwest defaults); call
+//! [`Client::with_http_config`](crate::Client::with_http_config) to derive a
+//! client with its own timeouts, proxy, root certificates or pool limits.
+
+use anyhow::{Context, Result};
+use futures::future::BoxFuture;
+use futures::stream::{StreamExt, TryStreamExt};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use super::{BodyStream, HttpRequest, HttpResponse, StreamingResponse, Transport};
+use crate::USER_AGENT;
+
+/// Transport settings for one [`Client`](crate::Client). Every field is
+/// optional; `HttpConfig::default()` reproduces the SDK's built-in behaviour.
+#[derive(Debug, Clone, Default)]
+pub struct HttpConfig {
+    /// Time allowed to establish the TCP/TLS connection.
+    pub connect_timeout: Option<Duration>,
+    /// Total time allowed per request, from send until the body is read.
+    /// Longpoll endpoints need several minutes here. The blocking transport
+    /// falls back to reqwest's 30s default when unset.
+    pub timeout: Option<Duration>,
+    /// Proxy URL applied to every scheme, e.g. `http://proxy.internal:3128`.
+    pub proxy: Option<String>,
+    /// Extra PEM-encoded root certificates to trust (e.g. a corporate
+    /// TLS-inspecting proxy CA).
+    pub root_certificates: Vec<Vec<u8>>,
+    /// Maximum idle connections kept per host.
+    pub pool_max_idle_per_host: Option<usize>,
+    /// How long an idle pooled connection is kept before being closed.
+    pub pool_idle_timeout: Option<Duration>,
+    /// Appended to the SDK's User-Agent, e.g. `my-app/1.2`.
+    pub user_agent_suffix: Option<String>,
+}
+
+impl HttpConfig {
+    fn user_agent(&self) -> String {
+        match &self.user_agent_suffix {
+            Some(suffix) => format!("{} {}", USER_AGENT, suffix),
+            None => USER_AGENT.to_string(),
+        }
+    }
+
+    fn certificates(&self) -> Result<Vec<reqwest::Certificate>> {
+        self.root_certificates
+            .iter()
+            .map(|pem| reqwest::Certificate::from_pem(pem).context("invalid root certificate"))
+            .collect()
+    }
+
+    fn proxy(&self) -> Result<Option<reqwest::Proxy>> {
+        self.proxy
+            .as_deref()
+            .map(|url| reqwest::Proxy::all(url).context("invalid proxy URL"))
+            .transpose()
+    }
+
+    fn build_async(&self) -> Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder().user_agent(self.user_agent());
+        if let Some(t) = self.connect_timeout {
+            builder = builder.connect_timeout(t);
+        }
+        if let Some(t) = self.timeout {
+            builder = builder.timeout(t);
+        }
+        if let Some(proxy) = self.proxy()? {
+            builder = builder.proxy(proxy);
+        }
+        for cert in self.certificates()? {
+            builder = builder.add_root_certificate(cert);
+        }
+        if let Some(n) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(n);
+        }
+        if let Some(t) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(t);
+        }
+        builder.build().context("build async HTTP client")
+    }
+
+    fn build_sync(&self) -> Result<reqwest::blocking::Client> {
+        let mut builder = reqwest::blocking::Client::builder().user_agent(self.user_agent());
+        if let Some(t) = self.connect_timeout {
+            builder = builder.connect_timeout(t);
+        }
+        if let Some(t) = self.timeout {
+            builder = builder.timeout(t);
+        }
+        if let Some(proxy) = self.proxy()? {
+            builder = builder.proxy(proxy);
+        }
+        for cert in self.certificates()? {
+            builder = builder.add_root_certificate(cert);
+        }
+        if let Some(n) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(n);
+        }
+        if let Some(t) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(t);
+        }
+        builder.build().context("build blocking HTTP client")
+    }
+}
+
+/// The reqwest clients behind one `HttpConfig`. The async client is built
+/// eagerly so configuration errors surface from `new`; the blocking client
+/// is built on first sync call, because reqwest's blocking client spins up
+/// (and on drop tears down) its own runtime thread, which panics if that
+/// happens inside an async context.
+#[derive(Debug)]
+pub struct ReqwestTransport {
+    config: HttpConfig,
+    async_client: reqwest::Client,
+    sync_client: OnceLock<reqwest::blocking::Client>,
+}
+
+impl ReqwestTransport {
+    /// Fails if the proxy URL or a root certificate is invalid.
+    pub fn new(config: HttpConfig) -> Result<Self> {
+        let async_client = config.build_async()?;
+        Ok(Self {
+            config,
+            async_client,
+            sync_client: OnceLock::new(),
+        })
+    }
+
+    /// Process-wide transport for `HttpConfig::default()`, shared by every
+    /// client that never picked its own.
+    pub(crate) fn shared_default() -> Arc<Self> {
+        static DEFAULT: OnceLock<Arc<ReqwestTransport>> = OnceLock::new();
+        DEFAULT
+            .get_or_init(|| Arc::new(Self::new(HttpConfig::default()).expect("async client")))
+            .clone()
+    }
+
+    pub fn config(&self) -> &HttpConfig {
+        &self.config
+    }
+
+    fn sync_client(&self) -> Result<&reqwest::blocking::Client> {
+        if let Some(c) = self.sync_client.get() {
+            return Ok(c);
+        }
+        let built = self.config.build_sync()?;
+        Ok(self.sync_client.get_or_init(|| built))
+    }
+
+    fn async_request(&self, request: HttpRequest) -> reqwest::RequestBuilder {
+        let mut builder = self.async_client.post(&request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+        builder
+    }
+}
+
+fn header_pairs(headers: &reqwest::header::HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter_map(|(k, v)| Some((k.as_str().to_string(), v.to_str().ok()?.to_string())))
+        .collect()
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: HttpRequest) -> BoxFuture<'_, Result<HttpResponse>> {
+        let builder = self.async_request(request);
+        Box::pin(async move {
+            let r = builder.send().await?;
+            let status = r.status().as_u16();
+            let headers = header_pairs(r.headers());
+            let body = r.bytes().await?;
+            Ok(HttpResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+
+    fn send_sync(&self, request: HttpRequest) -> Result<HttpResponse> {
+        let mut builder = self.sync_client()?.post(&request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+        let r = builder.send()?;
+        let status = r.status().as_u16();
+        let headers = header_pairs(r.headers());
+        let body = r.bytes()?;
+        Ok(HttpResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    fn send_streaming(
+        &self,
+        request: HttpRequest,
+        body: Option<BodyStream>,
+    ) -> BoxFuture<'_, Result<StreamingResponse>> {
+        let mut builder = self.async_request(request);
+        if let Some(body) = body {
+            builder = builder.body(reqwest::Body::wrap_stream(body));
+        }
+        Box::pin(async move {
+            let r = builder.send().await?;
+            let status = r.status().as_u16();
+            let headers = header_pairs(r.headers());
+            let body = r.bytes_stream().map_err(std::io::Error::other).boxed();
+            Ok(StreamingResponse {
+                status,
+                headers,
+                body,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_agent_appends_suffix() {
+        let cfg = HttpConfig {
+            user_agent_suffix: Some("my-app/1.2".into()),
+            ..Default::default()
+        };
+        assert_eq!(cfg.user_agent(), format!("{} my-app/1.2", USER_AGENT));
+        assert_eq!(HttpConfig::default().user_agent(), USER_AGENT);
+    }
+
+    #[test]
+    fn rejects_invalid_root_certificate() {
+        let cfg = HttpConfig {
+            root_certificates: vec![b"not a pem".to_vec()],
+            ..Default::default()
+        };
+        assert!(ReqwestTransport::new(cfg).is_err());
+    }
+}